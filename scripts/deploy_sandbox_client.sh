@@ -1,3 +1,3 @@
 #!/bin/sh
 
-cargo run --no-default-features  --features "client,tls_no_verify"
+cargo run --no-default-features --features "client,tls_pin" -- --pin-cert certs/certificate.crt
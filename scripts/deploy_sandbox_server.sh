@@ -1,3 +1,3 @@
 #!/bin/sh
 
-. ./scripts/env.sh && cargo run --no-default-features --features "server" -- 0.0.0.0:4000 --cert certs/certificate.crt --key certs/private.key
+. ./scripts/env.sh && cargo run --no-default-features --features "server" -- serve 0.0.0.0:4000 --cert certs/certificate.crt --key certs/private.key
@@ -15,3 +15,6 @@ export DB_ACC_PASS="PASSWORD"
 
 export DB_PORTFOLIO_USER="portfolio_schema_usr"
 export DB_PORTFOLIO_PASS="PASSWORD"
+
+export JWT_SECRET="sandbox-jwt-secret-do-not-use-in-production"
+export SALT_SECRET="sandbox-salt-secret-do-not-use-in-production"
@@ -20,9 +20,10 @@ fn main() {
         rt.block_on(async move {
             IP.scope("0.0.0.0:0000".parse().unwrap(), async move {
                 // for main task logging
-                libtrader_init_server()
-                    .await
-                    .expect("failed running server");
+                if let Err(err) = libtrader_init_server().await {
+                    eprintln!("failed running server: {}", err);
+                    std::process::exit(1);
+                }
             })
             .await;
         });
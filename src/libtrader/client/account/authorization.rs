@@ -1,25 +1,30 @@
-use data_encoding::HEXUPPER;
-use ring::digest;
-use std::io;
-
 use crate::common::account::hash::hash;
 use crate::common::message::inst::CommandInst;
 use crate::common::message::message::Message;
-use crate::common::message::message_builder::message_builder;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
 use crate::common::message::message_type::MessageType;
+use crate::common::message::request::LoginRequest;
+use crate::common::message::response::LoginResponse;
 use crate::common::misc::assert_msg::assert_msg;
 use crate::common::misc::return_flags::ReturnFlags;
+use ring::digest;
 
 use crate::client::network::cmd::req_server_salt::req_server_salt;
 
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
 use tokio::net::TcpStream;
 use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
 
 /// Client authentication procedure.
 ///
 /// Takes in the username, email and password. Data is hashed and then sent to the server for
-/// further hashing and confirmation of authentication. A session token is returned.
+/// further hashing and confirmation of authentication. A session token and a refresh token are
+/// returned.
 /// The function is not complete.
 ///
 /// Currently only sends authentication request and does not process any returned values.
@@ -29,13 +34,75 @@ use tokio_rustls::client::TlsStream;
 /// email - The raw email to be used.
 /// password - The raw password to be used.
 ///
-/// Returns: nothing on success, and ReturnFlags on failure.
+/// Returns: the session and refresh tokens on success, and ClientError on failure.
 pub async fn acc_auth(
-    socket: &mut TlsStream<TcpStream>,
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
     username: &str,
     email: &str,
     password: &str,
-) -> io::Result<String> {
+) -> ClientResult<LoginResponse> {
+    /* generate message to be sent to the server */
+    let request = login_request(socket, username, email, password).await?;
+    let message = payload_builder(
+        MessageType::Command,
+        CommandInst::LoginMethod1 as i64,
+        &request,
+    );
+    socket.send(message).await?;
+
+    /* decode response */
+    let response: Message = read_response(socket, ReturnFlags::ClientAccUnauthorized).await?;
+
+    if assert_msg(
+        &response,
+        MessageType::ServerReturn,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) && response.data.len() != 0
+        && response.instruction == 1
+    {
+        /* authorized */
+        let response: LoginResponse = response
+            .payload()
+            .map_err(|_| ClientError::InvalidResponse(ReturnFlags::ClientAccInvalidSessionId))?;
+        return Ok(response);
+    } else {
+        return Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientAccUnauthorized,
+        ));
+    }
+}
+
+/// Hashes the credentials of an account as the server expects them.
+///
+/// Gets the client salts of the account from the server, and hashes the email and password with
+/// them. The result proves the credentials on login, and on requests that change or delete the
+/// account.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+/// username - The raw username to be used.
+/// email - The raw email to be used.
+/// password - The raw password to be used.
+///
+/// Returns: the hashed credentials on success, and ClientError on failure.
+///
+/// Example:
+/// ```rust
+///     let credentials = login_request(&mut socket, "n1ckn8me", "email@example.com", "password").await?;
+/// ```
+pub async fn login_request(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    username: &str,
+    email: &str,
+    password: &str,
+) -> ClientResult<LoginRequest> {
     /*
      * get email salt
      * */
@@ -62,59 +129,9 @@ pub async fn acc_auth(
         250_000,
     );
 
-    /* generate message to be sent to the server */
-    let data = object! {
-        hashed_email: HEXUPPER.encode(&hashed_email),
-        hashed_password: HEXUPPER.encode(&hashed_password),
-        username: username
-    };
-    let message = message_builder(
-        MessageType::Command,
-        CommandInst::LoginMethod1 as i64,
-        3,
-        0,
-        0,
-        data.dump().as_bytes().to_vec(),
-    );
-    socket
-        .write_all(&bincode::serialize(&message).unwrap())
-        .await?;
-
-    /* decode response */
-    let mut buf = Vec::with_capacity(4096);
-    socket.read_buf(&mut buf).await?;
-    let response: Message = bincode::deserialize(&buf).map_err(|_| {
-        io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!("{}", ReturnFlags::ClientAccUnauthorized),
-        )
-    })?;
-
-    if assert_msg(
-        &response,
-        MessageType::ServerReturn,
-        true,
-        1,
-        false,
-        0,
-        false,
-        0,
-        false,
-        0,
-    ) && response.data.len() != 0
-        && response.instruction == 1
-    {
-        /* authorized */
-        return Ok(String::from_utf8(response.data).map_err(|_| {
-            io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("{}", ReturnFlags::ClientAccInvalidSessionId),
-            )
-        })?);
-    } else {
-        return Err(io::Error::new(
-            io::ErrorKind::ConnectionRefused,
-            format!("{}", ReturnFlags::ClientAccUnauthorized),
-        ));
-    }
+    Ok(LoginRequest {
+        username: username.to_string(),
+        hashed_email: hashed_email.to_vec(),
+        hashed_password: hashed_password.to_vec(),
+    })
 }
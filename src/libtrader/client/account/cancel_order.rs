@@ -0,0 +1,80 @@
+use crate::common::message::inst::CommandInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::CancelOrderRequest;
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Cancels a working order on the connected TLS server.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+/// auth_jwt - The JWT token authorizing the cancellation.
+/// order_id - The id of the order to cancel.
+///
+/// Returns: nothing on success, ClientError on error containing reason of failure.
+///
+/// Example:
+/// ```rust
+///     match acc_cancel_order(&mut socket, jwt, order.id).await {
+///         Ok(()) => println!("cancelled"),
+///         Err(err) => panic!("can not cancel order! error: {}", err)
+///     };
+/// ```
+pub async fn acc_cancel_order(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    auth_jwt: String,
+    order_id: i64,
+) -> ClientResult<()> {
+    if auth_jwt.is_empty() {
+        return Err(ClientError::InvalidRequest(
+            ReturnFlags::ClientAccUnauthorized,
+        ));
+    }
+
+    /* build message request */
+    let message = payload_builder(
+        MessageType::Command,
+        CommandInst::CancelOrder as i64,
+        &CancelOrderRequest {
+            jwt: auth_jwt,
+            order_id,
+        },
+    );
+    socket.send(message).await?;
+
+    /* decode response */
+    let response: Message = read_response(socket, ReturnFlags::ClientAccCancelOrderError).await?;
+
+    if assert_msg(
+        &response,
+        MessageType::ServerReturn,
+        true,
+        0,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) && response.instruction == 1
+    {
+        /* cancelled */
+        Ok(())
+    } else {
+        /* server rejected the cancellation */
+        Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientAccCancelOrderError,
+        ))
+    }
+}
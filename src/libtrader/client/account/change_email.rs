@@ -0,0 +1,93 @@
+use crate::client::account::hash_email::hash_email;
+use ring::digest;
+
+use crate::common::message::inst::CommandInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::{ChangeEmailRequest, LoginRequest};
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::network::cmd::get_server_salt::get_server_salt;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Changes the email of the account on the connected TLS server.
+///
+/// Hashes the new email as ```acc_create()``` does, with a new server salt and client salt.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+/// auth_jwt - The JWT token of the session.
+/// credentials - The current credentials, hashed with ```login_request()```.
+/// new_email - The raw new email.
+///
+/// Returns: nothing on success, ClientError on error containing the reason of failure.
+///
+/// Example:
+/// ```rust
+///     let credentials = login_request(&mut socket, "n1ckn8me", "old@example.com", "pass").await?;
+///     acc_change_email(&mut socket, jwt, credentials, "new@example.com").await?;
+/// ```
+pub async fn acc_change_email(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    auth_jwt: String,
+    credentials: LoginRequest,
+    new_email: &str,
+) -> ClientResult<()> {
+    if auth_jwt.is_empty() {
+        return Err(ClientError::InvalidRequest(
+            ReturnFlags::ClientAccUnauthorized,
+        ));
+    }
+
+    /* hash the new email with new salts */
+    let email_server_salt: [u8; digest::SHA512_OUTPUT_LEN / 2] = get_server_salt(socket).await?;
+    let email_hash = hash_email(&new_email.as_bytes().to_vec(), email_server_salt);
+
+    /* build message request */
+    let message = payload_builder(
+        MessageType::Command,
+        CommandInst::ChangeEmail as i64,
+        &ChangeEmailRequest {
+            jwt: auth_jwt,
+            credentials,
+            email_hash: email_hash.0.to_vec(),
+            email_client_salt: email_hash.1.to_vec(),
+        },
+    );
+    socket.send(message).await?;
+
+    /* decode response */
+    let response: Message = read_response(socket, ReturnFlags::ClientAccChangeEmailError).await?;
+
+    if assert_msg(
+        &response,
+        MessageType::ServerReturn,
+        true,
+        0,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) && response.instruction == 1
+    {
+        /* changed */
+        Ok(())
+    } else {
+        /* server rejected the change */
+        Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientAccChangeEmailError,
+        ))
+    }
+}
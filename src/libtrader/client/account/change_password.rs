@@ -0,0 +1,98 @@
+use crate::client::account::hash_pwd::hash_pwd;
+use ring::digest;
+
+use crate::common::message::inst::CommandInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::{ChangePasswordRequest, LoginRequest};
+use crate::common::message::response::LoginResponse;
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::network::cmd::get_server_salt::get_server_salt;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Changes the password of the account on the connected TLS server.
+///
+/// Hashes the new password as ```acc_create()``` does, with a new server salt and client salt.
+/// The server logs out every session of the account and returns the tokens of a new one.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+/// auth_jwt - The JWT token of the session.
+/// credentials - The current credentials, hashed with ```login_request()```.
+/// new_password - The raw new password.
+///
+/// Returns: the tokens of the new session on success, ClientError on error containing the reason
+/// of failure.
+///
+/// Example:
+/// ```rust
+///     let credentials = login_request(&mut socket, "n1ckn8me", "email@example.com", "old").await?;
+///     let tokens = acc_change_password(&mut socket, jwt, credentials, "new").await?;
+/// ```
+pub async fn acc_change_password(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    auth_jwt: String,
+    credentials: LoginRequest,
+    new_password: &str,
+) -> ClientResult<LoginResponse> {
+    if auth_jwt.is_empty() {
+        return Err(ClientError::InvalidRequest(
+            ReturnFlags::ClientAccUnauthorized,
+        ));
+    }
+
+    /* hash the new password with new salts */
+    let password_server_salt: [u8; digest::SHA512_OUTPUT_LEN / 2] = get_server_salt(socket).await?;
+    let password_hash = hash_pwd(&new_password.as_bytes().to_vec(), password_server_salt);
+
+    /* build message request */
+    let message = payload_builder(
+        MessageType::Command,
+        CommandInst::ChangePassword as i64,
+        &ChangePasswordRequest {
+            jwt: auth_jwt,
+            credentials,
+            password_hash: password_hash.0.to_vec(),
+            password_client_salt: password_hash.1.to_vec(),
+        },
+    );
+    socket.send(message).await?;
+
+    /* decode response */
+    let response: Message =
+        read_response(socket, ReturnFlags::ClientAccChangePasswordError).await?;
+
+    if assert_msg(
+        &response,
+        MessageType::ServerReturn,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) && !response.data.is_empty()
+        && response.instruction == 1
+    {
+        response
+            .payload()
+            .map_err(|_| ClientError::InvalidResponse(ReturnFlags::ClientAccInvalidSessionId))
+    } else {
+        Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientAccChangePasswordError,
+        ))
+    }
+}
@@ -0,0 +1,92 @@
+use crate::common::account::position::Position;
+use crate::common::message::inst::CommandInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::ClosePositionRequest;
+use crate::common::message::response::PositionResponse;
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Closes shares of an open position on the connected TLS server.
+///
+/// The server sells long positions at the bid price and buys back short positions at the ask
+/// price.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+/// auth_jwt - The JWT token authorizing the close.
+/// position_id - The id of the position to close.
+/// shares_amount - The number of shares to close.
+///
+/// Returns: the updated position on success, ClientError on error containing reason of failure.
+///
+/// Example:
+/// ```rust
+///     match acc_close_position(&mut socket, jwt, position.id, 5).await {
+///         Ok(position) => println!("realized {}", position.realized_pnl()),
+///         Err(err) => panic!("can not close position! error: {}", err)
+///     };
+/// ```
+pub async fn acc_close_position(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    auth_jwt: String,
+    position_id: i64,
+    shares_amount: i64,
+) -> ClientResult<Position> {
+    if auth_jwt.is_empty() {
+        return Err(ClientError::InvalidRequest(
+            ReturnFlags::ClientAccUnauthorized,
+        ));
+    }
+
+    /* build message request */
+    let message = payload_builder(
+        MessageType::Command,
+        CommandInst::ClosePosition as i64,
+        &ClosePositionRequest {
+            jwt: auth_jwt,
+            position_id,
+            shares_amount,
+        },
+    );
+    socket.send(message).await?;
+
+    /* decode response */
+    let response: Message = read_response(socket, ReturnFlags::ClientAccClosePositionError).await?;
+
+    if assert_msg(
+        &response,
+        MessageType::ServerReturn,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) && response.instruction == 1
+        && !response.data.is_empty()
+    {
+        /* returned the updated position */
+        response
+            .payload::<PositionResponse>()
+            .map(|response| response.position)
+            .map_err(|_| ClientError::InvalidResponse(ReturnFlags::ClientAccClosePositionError))
+    } else {
+        /* server rejected the close */
+        Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientAccClosePositionError,
+        ))
+    }
+}
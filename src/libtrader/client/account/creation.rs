@@ -1,22 +1,25 @@
-use data_encoding::HEXUPPER;
-use ring::digest;
-use std::io;
-
 use crate::client::account::hash_email::hash_email;
 use crate::client::account::hash_pwd::hash_pwd;
+use ring::digest;
 
 use crate::common::message::inst::CommandInst;
 use crate::common::message::message::Message;
-use crate::common::message::message_builder::message_builder;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
 use crate::common::message::message_type::MessageType;
+use crate::common::message::request::RegisterRequest;
 use crate::common::misc::assert_msg::assert_msg;
 use crate::common::misc::return_flags::ReturnFlags;
 
 use crate::client::network::cmd::get_server_salt::get_server_salt;
 
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
 use tokio::net::TcpStream;
 use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
 
 /// Requests a TLS server to create an account.
 ///
@@ -31,7 +34,7 @@ use tokio_rustls::client::TlsStream;
 /// email - The email to send to the server.
 /// password - The password to send to the server.
 ///
-/// Returns: nothing on success, ReturnFlag on error containing the reason of failure.
+/// Returns: nothing on success, ClientError on error containing the reason of failure.
 ///
 /// Example:
 /// ```rust
@@ -41,11 +44,11 @@ use tokio_rustls::client::TlsStream;
 ///     }
 /// ```
 pub async fn acc_create(
-    socket: &mut TlsStream<TcpStream>,
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
     username: &str,
     email: &str,
     password: &str,
-) -> io::Result<()> {
+) -> ClientResult<()> {
     /*
      * get two server salts for email, and password
      * */
@@ -59,39 +62,26 @@ pub async fn acc_create(
     let password_hash = hash_pwd(&password.as_bytes().to_vec(), password_server_salt);
 
     /* generate message to be sent to the server */
-    let data = object! {
-        email_hash: HEXUPPER.encode(&email_hash.0),
-        email_client_salt: HEXUPPER.encode(&email_hash.1),
-        password_hash: HEXUPPER.encode(&password_hash.0),
-        password_client_salt: HEXUPPER.encode(&password_hash.1),
-        username: username
-    };
-    let message = message_builder(
+    let message = payload_builder(
         MessageType::Command,
         CommandInst::Register as i64,
-        5,
-        0,
-        0,
-        data.dump().as_bytes().to_vec(),
+        &RegisterRequest {
+            username: username.to_string(),
+            email_hash: email_hash.0.to_vec(),
+            email_client_salt: email_hash.1.to_vec(),
+            password_hash: password_hash.0.to_vec(),
+            password_client_salt: password_hash.1.to_vec(),
+        },
     );
-    socket
-        .write_all(&bincode::serialize(&message).unwrap())
-        .await?;
+    socket.send(message).await?;
 
     /* decode response */
-    let mut buf = Vec::with_capacity(4096);
-    socket.read_buf(&mut buf).await?;
-    let response: Message = bincode::deserialize(&buf).map_err(|_| {
-        io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!("{}", ReturnFlags::ClientTlsReadError),
-        )
-    })?;
-    if !assert_msg(
+    let response: Message = read_response(socket, ReturnFlags::ClientTlsReadError).await?;
+    if assert_msg(
         &response,
         MessageType::ServerReturn,
         true,
-        1,
+        0,
         false,
         0,
         false,
@@ -104,9 +94,8 @@ pub async fn acc_create(
         return Ok(());
     } else {
         /* server rejected account creation */
-        return Err(io::Error::new(
-            io::ErrorKind::ConnectionRefused,
-            format!("{}", ReturnFlags::ClientAccCreationFailed),
+        return Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientAccCreationFailed,
         ));
     }
 }
@@ -0,0 +1,80 @@
+use crate::common::message::inst::CommandInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::{DeleteAccountRequest, LoginRequest};
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Deletes the account on the connected TLS server.
+///
+/// The positions, orders, transactions and sessions of the account are deleted with it.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+/// auth_jwt - The JWT token of the session.
+/// credentials - The current credentials, hashed with ```login_request()```.
+///
+/// Returns: nothing on success, ClientError on error containing the reason of failure.
+///
+/// Example:
+/// ```rust
+///     let credentials = login_request(&mut socket, "n1ckn8me", "email@example.com", "pass").await?;
+///     acc_delete(&mut socket, jwt, credentials).await?;
+/// ```
+pub async fn acc_delete(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    auth_jwt: String,
+    credentials: LoginRequest,
+) -> ClientResult<()> {
+    if auth_jwt.is_empty() {
+        return Err(ClientError::InvalidRequest(
+            ReturnFlags::ClientAccUnauthorized,
+        ));
+    }
+
+    /* build message request */
+    let message = payload_builder(
+        MessageType::Command,
+        CommandInst::DeleteAccount as i64,
+        &DeleteAccountRequest {
+            jwt: auth_jwt,
+            credentials,
+        },
+    );
+    socket.send(message).await?;
+
+    /* decode response */
+    let response: Message = read_response(socket, ReturnFlags::ClientAccDeleteError).await?;
+
+    if assert_msg(
+        &response,
+        MessageType::ServerReturn,
+        true,
+        0,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) && response.instruction == 1
+    {
+        /* deleted */
+        Ok(())
+    } else {
+        /* server rejected the deletion */
+        Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientAccDeleteError,
+        ))
+    }
+}
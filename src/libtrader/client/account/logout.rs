@@ -0,0 +1,84 @@
+use crate::common::message::inst::CommandInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::AuthRequest;
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Logs out of the connected TLS server.
+///
+/// The session of the JWT token is revoked, or every session of the account when
+/// ```all_devices``` is set, after which their tokens are rejected by the server.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+/// auth_jwt - The JWT token of the session.
+/// all_devices - Whether to log out every session of the account.
+///
+/// Returns: nothing on success, ClientError on error containing reason of failure.
+///
+/// Example:
+/// ```rust
+///     match acc_logout(&mut socket, jwt, false).await {
+///         Ok(()) => println!("logged out"),
+///         Err(err) => panic!("can not log out! error: {}", err)
+///     };
+/// ```
+pub async fn acc_logout(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    auth_jwt: String,
+    all_devices: bool,
+) -> ClientResult<()> {
+    if auth_jwt.is_empty() {
+        return Err(ClientError::InvalidRequest(
+            ReturnFlags::ClientAccUnauthorized,
+        ));
+    }
+
+    /* build message request */
+    let instruction = match all_devices {
+        true => CommandInst::LogoutAll,
+        false => CommandInst::Logout,
+    };
+    let message = payload_builder(
+        MessageType::Command,
+        instruction as i64,
+        &AuthRequest { jwt: auth_jwt },
+    );
+    socket.send(message).await?;
+
+    /* decode response */
+    let response: Message = read_response(socket, ReturnFlags::ClientAccLogoutError).await?;
+
+    if assert_msg(
+        &response,
+        MessageType::ServerReturn,
+        true,
+        0,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) && response.instruction == 1
+    {
+        /* logged out */
+        Ok(())
+    } else {
+        /* server rejected the logout */
+        Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientAccLogoutError,
+        ))
+    }
+}
@@ -0,0 +1,102 @@
+use crate::common::account::order::Order;
+use crate::common::message::inst::CommandInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::PlaceOrderRequest;
+use crate::common::message::response::PlaceOrderResponse;
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Places a working order on the connected TLS server.
+///
+/// Only the order type, side, symbol, prices and amount of the passed order are used. The
+/// server fills the order once a tick of its symbol matches it.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+/// auth_jwt - The JWT token authorizing the order.
+/// order - The order to place.
+///
+/// Returns: the placed order on success, ClientError on error containing reason of failure.
+///
+/// Example:
+/// ```rust
+///     let order = Order {
+///         order_type: OrderType::Limit,
+///         is_buy: true,
+///         stock_symbol: "AAPL".into(),
+///         stock_price: 120.0,
+///         stock_amount: 10,
+///         ..Order::default()
+///     };
+///     match acc_place_order(&mut socket, jwt, &order).await {
+///         Ok(order) => println!("placed order {}", order.id),
+///         Err(err) => panic!("can not place order! error: {}", err)
+///     };
+/// ```
+pub async fn acc_place_order(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    auth_jwt: String,
+    order: &Order,
+) -> ClientResult<Order> {
+    if auth_jwt.is_empty() {
+        return Err(ClientError::InvalidRequest(
+            ReturnFlags::ClientAccUnauthorized,
+        ));
+    }
+
+    /* build message request */
+    let message = payload_builder(
+        MessageType::Command,
+        CommandInst::PlaceOrder as i64,
+        &PlaceOrderRequest {
+            jwt: auth_jwt,
+            symbol: order.stock_symbol.clone(),
+            order_type: order.order_type,
+            is_buy: order.is_buy,
+            shares_amount: order.stock_amount,
+            limit_price: order.stock_price,
+            stop_price: order.stop_price,
+        },
+    );
+    socket.send(message).await?;
+
+    /* decode response */
+    let response: Message = read_response(socket, ReturnFlags::ClientAccPlaceOrderError).await?;
+
+    if assert_msg(
+        &response,
+        MessageType::ServerReturn,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) && response.instruction == 1
+        && !response.data.is_empty()
+    {
+        /* returned the placed order */
+        response
+            .payload::<PlaceOrderResponse>()
+            .map(|response| response.order)
+            .map_err(|_| ClientError::InvalidResponse(ReturnFlags::ClientAccPlaceOrderError))
+    } else {
+        /* server rejected the order */
+        Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientAccPlaceOrderError,
+        ))
+    }
+}
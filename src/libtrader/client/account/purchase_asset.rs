@@ -0,0 +1,99 @@
+use crate::common::account::position::Position;
+use crate::common::message::inst::CommandInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::PurchaseAssetRequest;
+use crate::common::message::response::PositionResponse;
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Places a market order on the connected TLS server.
+///
+/// Sends a ```PurchaseAsset``` or ```SellAsset``` command with the JWT token of the client
+/// connection. The server fills buys at the ask price and sells at the bid price.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+/// auth_jwt - The JWT token authorizing the order.
+/// symbol - The symbol of the stock to trade.
+/// shares_amount - The number of shares to trade.
+/// is_buy - Whether to buy or to sell.
+///
+/// Returns: the opened position on success, ClientError on error containing reason of failure.
+///
+/// Example:
+/// ```rust
+///     match acc_purchase_asset(&mut socket, jwt, "AAPL", 10, true).await {
+///         Ok(position) => println!("bought at {}", position.stock_open_price),
+///         Err(err) => panic!("can not purchase asset! error: {}", err)
+///     };
+/// ```
+pub async fn acc_purchase_asset(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    auth_jwt: String,
+    symbol: &str,
+    shares_amount: i64,
+    is_buy: bool,
+) -> ClientResult<Position> {
+    if auth_jwt.is_empty() {
+        return Err(ClientError::InvalidRequest(
+            ReturnFlags::ClientAccUnauthorized,
+        ));
+    }
+
+    /* build message request */
+    let instruction = if is_buy {
+        CommandInst::PurchaseAsset
+    } else {
+        CommandInst::SellAsset
+    };
+    let message = payload_builder(
+        MessageType::Command,
+        instruction as i64,
+        &PurchaseAssetRequest {
+            jwt: auth_jwt,
+            symbol: symbol.to_string(),
+            shares_amount,
+        },
+    );
+    socket.send(message).await?;
+
+    /* decode response */
+    let response: Message = read_response(socket, ReturnFlags::ClientAccPurchaseAssetError).await?;
+
+    if assert_msg(
+        &response,
+        MessageType::ServerReturn,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) && response.instruction == 1
+        && !response.data.is_empty()
+    {
+        /* returned the opened position */
+        response
+            .payload::<PositionResponse>()
+            .map(|response| response.position)
+            .map_err(|_| ClientError::InvalidResponse(ReturnFlags::ClientAccPurchaseAssetError))
+    } else {
+        /* server rejected the order */
+        Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientAccPurchaseAssetError,
+        ))
+    }
+}
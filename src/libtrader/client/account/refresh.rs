@@ -0,0 +1,77 @@
+use crate::common::message::inst::CommandInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::RefreshRequest;
+use crate::common::message::response::LoginResponse;
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Exchanges a refresh token for a new session token and refresh token.
+///
+/// Avoids hashing the credentials again as ```acc_auth()``` does. The refresh token can only be
+/// exchanged once, the returned refresh token replaces it.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+/// refresh_token - The refresh token returned by the last login or refresh.
+///
+/// Returns: the session and refresh tokens on success, and ClientError on failure.
+///
+/// Example:
+/// ```rust
+///     let tokens = acc_refresh(&mut socket, tokens.refresh_token).await?;
+/// ```
+pub async fn acc_refresh(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    refresh_token: String,
+) -> ClientResult<LoginResponse> {
+    if refresh_token.is_empty() {
+        return Err(ClientError::InvalidRequest(
+            ReturnFlags::ClientAccUnauthorized,
+        ));
+    }
+
+    /* build message request */
+    let message = payload_builder(
+        MessageType::Command,
+        CommandInst::RefreshToken as i64,
+        &RefreshRequest { refresh_token },
+    );
+    socket.send(message).await?;
+
+    /* decode response */
+    let response: Message = read_response(socket, ReturnFlags::ClientAccRefreshTokenError).await?;
+
+    if assert_msg(
+        &response,
+        MessageType::ServerReturn,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) && !response.data.is_empty()
+        && response.instruction == 1
+    {
+        response
+            .payload()
+            .map_err(|_| ClientError::InvalidResponse(ReturnFlags::ClientAccInvalidSessionId))
+    } else {
+        Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientAccRefreshTokenError,
+        ))
+    }
+}
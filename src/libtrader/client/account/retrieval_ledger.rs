@@ -0,0 +1,86 @@
+use crate::common::account::ledger::Ledger;
+
+use crate::common::message::inst::DataTransferInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::AuthRequest;
+use crate::common::message::response::LedgerResponse;
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Retrieves from the connected TLS server an authorized cash balance and ledger history.
+///
+/// Sends a request for the cash ledger with the JWT token of the client connection. Handles any
+/// response and returns.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+/// auth_jwt - The JWT token to authorize with.
+///
+/// Returns: the ledger on success, ClientError on error containing reason of failure.
+///
+/// Example:
+/// ```rust
+///     match acc_retrieve_ledger(&mut socket, jwt).await {
+///         Ok(ledger) => println!("buying power: {}", ledger.cash_balance),
+///         Err(err) => panic!("can not retrieve ledger! error: {}", err)
+///     };
+/// ```
+pub async fn acc_retrieve_ledger(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    auth_jwt: String,
+) -> ClientResult<Ledger> {
+    if auth_jwt.is_empty() {
+        return Err(ClientError::InvalidRequest(
+            ReturnFlags::ClientAccUnauthorized,
+        ));
+    }
+
+    /* build message request */
+    let message = payload_builder(
+        MessageType::DataTransfer,
+        DataTransferInst::GetUserLedger as i64,
+        &AuthRequest { jwt: auth_jwt },
+    );
+    socket.send(message).await?;
+
+    /* decode response */
+    let response: Message =
+        read_response(socket, ReturnFlags::ClientAccRetrieveLedgerError).await?;
+
+    if assert_msg(
+        &response,
+        MessageType::ServerReturn,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) && !response.data.is_empty()
+        && response.instruction == 1
+    {
+        /* returned data */
+        response
+            .payload::<LedgerResponse>()
+            .map(|response| response.ledger)
+            .map_err(|_| ClientError::InvalidResponse(ReturnFlags::ClientAccRetrieveLedgerError))
+    } else {
+        /* could not get data */
+        Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientAccRetrieveLedgerError,
+        ))
+    }
+}
@@ -1,16 +1,21 @@
-use std::io;
-
 use crate::common::account::portfolio::Portfolio;
 use crate::common::message::inst::DataTransferInst;
 use crate::common::message::message::Message;
-use crate::common::message::message_builder::message_builder;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
 use crate::common::message::message_type::MessageType;
+use crate::common::message::request::AuthRequest;
+use crate::common::message::response::PortfolioResponse;
 use crate::common::misc::assert_msg::assert_msg;
 use crate::common::misc::return_flags::ReturnFlags;
 
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
 use tokio::net::TcpStream;
 use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
 
 /// Retrieves from the connected TLS server an authorized portfolio.
 ///
@@ -21,7 +26,7 @@ use tokio_rustls::client::TlsStream;
 /// tls_client - TLS client to use containing the JWT token to authorize.
 /// poll - For event handling.
 ///
-/// Returns: portfolio on success, string on error containing reason of failure.
+/// Returns: portfolio on success, ClientError on error containing reason of failure.
 ///
 /// Example:
 /// ```rust
@@ -31,39 +36,26 @@ use tokio_rustls::client::TlsStream;
 ///     };
 /// ```
 pub async fn acc_retrieve_portfolio(
-    socket: &mut TlsStream<TcpStream>,
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
     auth_jwt: String,
-) -> io::Result<Portfolio> {
+) -> ClientResult<Portfolio> {
     if auth_jwt.is_empty() == true {
-        return Err(io::Error::new(
-            io::ErrorKind::PermissionDenied,
-            "ACC_RETRIEVE_PORTFOLIO: JWT TOKEN EMPTY",
+        return Err(ClientError::InvalidRequest(
+            ReturnFlags::ClientAccUnauthorized,
         ));
     }
 
     /* build message request */
-    let message = message_builder(
+    let message = payload_builder(
         MessageType::Command,
         DataTransferInst::GetUserPortfolio as i64,
-        1,
-        0,
-        0,
-        bincode::serialize(&auth_jwt).unwrap(),
+        &AuthRequest { jwt: auth_jwt },
     );
-    socket
-        .write_all(&bincode::serialize(&message).unwrap())
-        .await?;
+    socket.send(message).await?;
 
     /* decode response */
-    let mut buf = Vec::with_capacity(4096);
-    socket.read_buf(&mut buf).await?;
-
-    let response: Message = bincode::deserialize(&buf).map_err(|_| {
-        io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!("{}", ReturnFlags::ClientAccRetrievePortfolioError),
-        )
-    })?;
+    let response: Message =
+        read_response(socket, ReturnFlags::ClientAccRetrievePortfolioError).await?;
 
     if assert_msg(
         &response,
@@ -80,13 +72,17 @@ pub async fn acc_retrieve_portfolio(
         && response.data.len() != 0
     {
         /* returned data */
-        let portfolio: Portfolio = bincode::deserialize(&response.data).unwrap();
+        let portfolio: Portfolio = response
+            .payload::<PortfolioResponse>()
+            .map(|response| response.portfolio)
+            .map_err(|_| {
+                ClientError::InvalidResponse(ReturnFlags::ClientAccRetrievePortfolioError)
+            })?;
         return Ok(portfolio);
     } else {
         /* could not get data */
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("{}", ReturnFlags::ClientAccRetrievePortfolioError),
+        return Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientAccRetrievePortfolioError,
         ));
     }
 }
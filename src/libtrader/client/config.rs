@@ -0,0 +1,160 @@
+use std::io;
+use std::path::PathBuf;
+
+use argh::FromArgs;
+use serde::Deserialize;
+
+use crate::common::misc::config_file::read_config_file;
+
+/// Client Options
+///
+/// Options given on the command line take precedence over the environment, which takes
+/// precedence over the configuration file.
+#[derive(FromArgs, Default)]
+pub struct ClientArgs {
+    /// configuration file, defaults to $PT_CLIENT_CONFIG
+    #[argh(option)]
+    pub config: Option<PathBuf>,
+
+    /// server address, defaults to $PT_SERVER_ADDR
+    #[argh(option)]
+    pub server_addr: Option<String>,
+
+    /// server name checked against the certificate, defaults to $PT_SERVER_NAME
+    #[argh(option)]
+    pub server_name: Option<String>,
+
+    /// PEM bundle of additional trusted CA certificates, defaults to $PT_CA_FILE
+    #[argh(option)]
+    pub ca_file: Option<PathBuf>,
+
+    /// PEM file of the server certificates to pin, defaults to $PT_PIN_CERT
+    #[argh(option)]
+    pub pin_cert: Option<PathBuf>,
+}
+
+/// Where and how a client connects.
+///
+/// Members:
+/// addr - The address of the server.
+/// server_name - The name the server certificate is checked against, also sent as SNI.
+/// ca_file - A PEM bundle of CA certificates trusted besides the webpki roots.
+/// pin_cert - A PEM file of server certificates, the only certificates then accepted.
+///
+/// The configuration file sets the same members, with ```server_addr``` for ```addr```:
+/// ```toml
+///     server_addr = "trader.example.com:4000"
+///     server_name = "trader.example.com"
+///     pin_cert = "certs/certificate.crt"
+/// ```
+#[derive(PartialEq, Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClientOptions {
+    #[serde(rename = "server_addr")]
+    pub addr: String,
+    pub server_name: String,
+    pub ca_file: Option<PathBuf>,
+    pub pin_cert: Option<PathBuf>,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        ClientOptions {
+            addr: "localhost:4000".to_string(),
+            server_name: "localhost".to_string(),
+            ca_file: None,
+            pin_cert: None,
+        }
+    }
+}
+
+impl ClientOptions {
+    /// Loads the client options of the running program.
+    ///
+    /// Reads the command line, the configuration file given with ```--config``` or
+    /// ```PT_CLIENT_CONFIG```, and the ```PT_*``` environment variables.
+    ///
+    /// Returns: the client options on success, and the reason of failure on error.
+    ///
+    /// Example:
+    /// ```rust
+    ///     let options = ClientOptions::load()?;
+    ///     let config = gen_tls_client_config(&options)?;
+    /// ```
+    pub fn load() -> io::Result<ClientOptions> {
+        ClientOptions::from_args(argh::from_env())
+    }
+
+    /// Builds client options from parsed command line arguments, the configuration file and
+    /// the environment.
+    pub fn from_args(args: ClientArgs) -> io::Result<ClientOptions> {
+        let config = args
+            .config
+            .clone()
+            .or_else(|| std::env::var_os("PT_CLIENT_CONFIG").map(PathBuf::from));
+        let mut options = match config {
+            Some(path) => read_config_file(&path)?,
+            None => ClientOptions::default(),
+        };
+
+        options.apply(|key| std::env::var(format!("PT_{}", key.to_uppercase())).ok());
+
+        if let Some(addr) = args.server_addr {
+            options.addr = addr;
+        }
+        if let Some(server_name) = args.server_name {
+            options.server_name = server_name;
+        }
+        if let Some(ca_file) = args.ca_file {
+            options.ca_file = Some(ca_file);
+        }
+        if let Some(pin_cert) = args.pin_cert {
+            options.pin_cert = Some(pin_cert);
+        }
+        Ok(options)
+    }
+
+    /// Applies the value of every known key found by ```lookup```.
+    fn apply<F: Fn(&str) -> Option<String>>(&mut self, lookup: F) {
+        if let Some(addr) = lookup("server_addr") {
+            self.addr = addr;
+        }
+        if let Some(server_name) = lookup("server_name") {
+            self.server_name = server_name;
+        }
+        if let Some(ca_file) = lookup("ca_file") {
+            self.ca_file = Some(PathBuf::from(ca_file));
+        }
+        if let Some(pin_cert) = lookup("pin_cert") {
+            self.pin_cert = Some(PathBuf::from(pin_cert));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_client_options_apply() {
+        let mut options = ClientOptions::default();
+        options.apply(|key| match key {
+            "server_name" => Some("trader.example.com".to_string()),
+            "ca_file" => Some("ca.pem".to_string()),
+            _ => None,
+        });
+        assert_eq!(options.addr, ClientOptions::default().addr);
+        assert_eq!(options.server_name, "trader.example.com");
+        assert_eq!(options.ca_file, Some(PathBuf::from("ca.pem")));
+        assert_eq!(options.pin_cert, None);
+    }
+
+    #[test]
+    fn test_client_options_file() {
+        let options: ClientOptions =
+            toml::from_str("server_addr = \"trader.example.com:4000\" # production\n").unwrap();
+        assert_eq!(options.addr, "trader.example.com:4000");
+        assert_eq!(options.server_name, ClientOptions::default().server_name);
+        assert!(toml::from_str::<ClientOptions>("addr = \"localhost\"\n").is_err());
+    }
+}
@@ -1,11 +1,8 @@
 use std::io;
-use std::net::ToSocketAddrs;
-
-use tokio::net::TcpStream;
-use tokio_rustls::webpki::DNSNameRef;
-use tokio_rustls::TlsConnector;
 
+use crate::client::config::ClientOptions;
 use crate::client::network::gen_tls_client_config::gen_tls_client_config;
+use crate::client::session::Session;
 
 use rand::distributions::Alphanumeric;
 use rand::{thread_rng, Rng};
@@ -94,20 +91,11 @@ pub async fn libtrader_init_client() -> std::io::Result<()> {
     // Initialize log.
     libtrader_init_log()?;
 
-    let addr = ("0.0.0.0", 4000)
-        .to_socket_addrs()?
-        .next()
-        .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound))?;
-    let domain = "localhost";
-    let config = gen_tls_client_config()?;
-
-    let connector = TlsConnector::from(config);
-    let stream = TcpStream::connect(&addr).await?;
-
-    let domain = DNSNameRef::try_from_ascii_str(&domain)
-        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "invalid dnsname"))?;
+    let options = ClientOptions::load()?;
+    let config = gen_tls_client_config(&options)?;
 
-    let mut socket = connector.connect(domain, stream).await?;
+    let mut session = Session::connect(options.addr.as_str(), &options.server_name, config).await?;
+    println!("speaking protocol version {}", session.version());
 
     let username: String = thread_rng()
         .sample_iter(&Alphanumeric)
@@ -125,32 +113,22 @@ pub async fn libtrader_init_client() -> std::io::Result<()> {
         .map(char::from)
         .collect();
 
-    use crate::client::account::creation::acc_create;
-    match acc_create(&mut socket, &username, &email, &password).await {
+    match session.register(&username, &email, &password).await {
         Ok(_) => println!("we created it"),
         Err(err) => panic!("panik! {}", err),
     }
 
-    use crate::client::account::authorization::acc_auth;
-    let mut jwt: String = String::new();
-    println!("{}", jwt); // this is for removing pisky warnings,
-                         // this is fine as long as this code is sandbox
-    match acc_auth(&mut socket, &username, &email, &password).await {
-        Ok(auth) => {
-            jwt = auth;
-            println!("we accessed it, the token: {}", jwt);
-        }
+    match session.login(&username, &email, &password).await {
+        Ok(_) => println!("we accessed it, the token: {}", session.jwt().unwrap()),
         Err(err) => panic!("panik! {}", err),
     }
 
-    use crate::client::account::retrieval_portfolio::acc_retrieve_portfolio;
-    match acc_retrieve_portfolio(&mut socket, String::from(jwt.as_str())).await {
+    match session.portfolio().await {
         Ok(portfolio) => println!("we got portfolio {:#?}", portfolio),
         Err(err) => panic!("panik! {}", err),
     }
 
-    use crate::client::account::retrieval_transaction::acc_retrieve_transaction;
-    match acc_retrieve_transaction(&mut socket, jwt).await {
+    match session.transactions().await {
         Ok(transaction) => println!("we got the transactions {:#?}", transaction),
         Err(err) => panic!("panik! {}", err),
     }
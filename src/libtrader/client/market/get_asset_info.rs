@@ -0,0 +1,80 @@
+use crate::common::generic::company::Company;
+
+use crate::common::message::inst::DataTransferInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::AssetInfoRequest;
+use crate::common::message::response::AssetInfoResponse;
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Retrieves from the connected TLS server the company info of an asset.
+///
+/// Sends a request for the company listed under the symbol. Handles any response and returns.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+/// symbol - The symbol of the asset.
+///
+/// Returns: the company on success, ClientError on error containing reason of failure.
+///
+/// Example:
+/// ```rust
+///     match get_asset_info(&mut socket, "AAPL").await {
+///         Ok(company) => println!("sector: {}", company.sector),
+///         Err(err) => panic!("can not retrieve asset info! error: {}", err)
+///     };
+/// ```
+pub async fn get_asset_info(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    symbol: &str,
+) -> ClientResult<Company> {
+    /* build message request */
+    let message = payload_builder(
+        MessageType::DataTransfer,
+        DataTransferInst::GetAssetInfo as i64,
+        &AssetInfoRequest {
+            symbol: symbol.to_string(),
+        },
+    );
+    socket.send(message).await?;
+
+    /* decode response */
+    let response: Message = read_response(socket, ReturnFlags::ClientGetAssetInfoError).await?;
+
+    if assert_msg(
+        &response,
+        MessageType::ServerReturn,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) && !response.data.is_empty()
+        && response.instruction == DataTransferInst::GetAssetInfo as i64
+    {
+        /* returned data */
+        response
+            .payload::<AssetInfoResponse>()
+            .map(|response| response.company)
+            .map_err(|_| ClientError::InvalidResponse(ReturnFlags::ClientGetAssetInfoError))
+    } else {
+        /* could not get data */
+        Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientGetAssetInfoError,
+        ))
+    }
+}
@@ -0,0 +1,132 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::common::generic::stock_val::StockVal;
+
+use crate::common::message::inst::DataTransferInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::{AssetValueCurrentRequest, AssetValueRequest};
+use crate::common::message::response::{AssetValueCurrentResponse, AssetValueResponse};
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Sends a market data request and decodes the returned payload.
+async fn request_asset_value<Req: Serialize, Resp: DeserializeOwned>(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    instruction: DataTransferInst,
+    request: &Req,
+) -> ClientResult<Resp> {
+    let instruction = instruction as i64;
+
+    /* build message request */
+    let message = payload_builder(MessageType::DataTransfer, instruction, request);
+    socket.send(message).await?;
+
+    /* decode response */
+    let response: Message = read_response(socket, ReturnFlags::ClientGetAssetValueError).await?;
+
+    if assert_msg(
+        &response,
+        MessageType::ServerReturn,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) && !response.data.is_empty()
+        && response.instruction == instruction
+    {
+        /* returned data */
+        response
+            .payload()
+            .map_err(|_| ClientError::InvalidResponse(ReturnFlags::ClientGetAssetValueError))
+    } else {
+        /* could not get data */
+        Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientGetAssetValueError,
+        ))
+    }
+}
+
+/// Retrieves from the connected TLS server the quotes of an asset between two epochs.
+///
+/// At most ```MAX_ASSET_VALUE_ROWS``` quotes are returned, the oldest first. Request again
+/// from after the last quote returned to get the rest.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+/// symbol - The symbol of the asset.
+/// start_epoch - The first time epoch, inclusive.
+/// end_epoch - The last time epoch, inclusive.
+///
+/// Returns: the quotes on success, ClientError on error containing reason of failure.
+///
+/// Example:
+/// ```rust
+///     match get_asset_value(&mut socket, "AAPL", 1600000000, 1600086400).await {
+///         Ok(vals) => println!("got {} quotes", vals.len()),
+///         Err(err) => panic!("can not retrieve asset value! error: {}", err)
+///     };
+/// ```
+pub async fn get_asset_value(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    symbol: &str,
+    start_epoch: i64,
+    end_epoch: i64,
+) -> ClientResult<Vec<StockVal>> {
+    let response: AssetValueResponse = request_asset_value(
+        socket,
+        DataTransferInst::GetAssetValue,
+        &AssetValueRequest {
+            symbol: symbol.to_string(),
+            start_epoch,
+            end_epoch,
+        },
+    )
+    .await?;
+    Ok(response.vals)
+}
+
+/// Retrieves from the connected TLS server the latest quote of an asset.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+/// symbol - The symbol of the asset.
+///
+/// Returns: the quote on success, ClientError on error containing reason of failure.
+///
+/// Example:
+/// ```rust
+///     match get_asset_value_current(&mut socket, "AAPL").await {
+///         Ok(val) => println!("bid: {} ask: {}", val.bid_price, val.ask_price),
+///         Err(err) => panic!("can not retrieve asset value! error: {}", err)
+///     };
+/// ```
+pub async fn get_asset_value_current(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    symbol: &str,
+) -> ClientResult<StockVal> {
+    let response: AssetValueCurrentResponse = request_asset_value(
+        socket,
+        DataTransferInst::GetAssetValueCurrent,
+        &AssetValueCurrentRequest {
+            symbol: symbol.to_string(),
+        },
+    )
+    .await?;
+    Ok(response.val)
+}
@@ -0,0 +1,3 @@
+pub mod get_asset_info;
+pub mod get_asset_value;
+pub mod quote_stream;
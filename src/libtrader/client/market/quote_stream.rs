@@ -0,0 +1,147 @@
+use std::io;
+
+use crate::common::generic::stock_val::StockVal;
+
+use crate::common::message::inst::DataTransferInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::{read_message, MessageCodec};
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::QuoteSubscriptionRequest;
+use crate::common::message::response::{QuoteSubscriptionResponse, QuoteUpdate};
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
+
+/// A quote pushed by the server for a subscribed symbol.
+///
+/// Members:
+/// val - The quote.
+/// sequence - The sequence number of the quote, a gap means quotes were skipped.
+/// latest - The newest sequence number published when the quote was sent.
+#[derive(PartialEq, Debug)]
+pub struct StreamedQuote {
+    pub val: StockVal,
+    pub sequence: usize,
+    pub latest: usize,
+}
+
+/// Changes the quote subscription of the connection to the TLS server.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+/// symbols - The symbols to subscribe to or unsubscribe from.
+/// subscribe - Whether to subscribe to the symbols, or unsubscribe from them.
+///
+/// Returns: every symbol subscribed to after the change on success, ClientError on error
+/// containing reason of failure.
+///
+/// Example:
+/// ```rust
+///     match subscribe_quotes(&mut socket, &["AAPL", "MSFT"], true).await {
+///         Ok(symbols) => println!("subscribed to {:?}", symbols),
+///         Err(err) => panic!("can not subscribe to quotes! error: {}", err)
+///     };
+/// ```
+pub async fn subscribe_quotes(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    symbols: &[&str],
+    subscribe: bool,
+) -> ClientResult<Vec<String>> {
+    let instruction = if subscribe {
+        DataTransferInst::SubscribeQuotes
+    } else {
+        DataTransferInst::UnsubscribeQuotes
+    } as i64;
+
+    /* build message request */
+    let message = payload_builder(
+        MessageType::DataTransfer,
+        instruction,
+        &QuoteSubscriptionRequest {
+            symbols: symbols.iter().map(|symbol| symbol.to_string()).collect(),
+        },
+    );
+    socket.send(message).await?;
+
+    /* decode response */
+    let response: Message = read_response(socket, ReturnFlags::ClientSubscribeQuotesError).await?;
+
+    if assert_msg(
+        &response,
+        MessageType::ServerReturn,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) && !response.data.is_empty()
+        && response.instruction == instruction
+    {
+        /* returned data */
+        response
+            .payload::<QuoteSubscriptionResponse>()
+            .map(|response| response.symbols)
+            .map_err(|_| ClientError::InvalidResponse(ReturnFlags::ClientSubscribeQuotesError))
+    } else {
+        /* could not change subscription */
+        Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientSubscribeQuotesError,
+        ))
+    }
+}
+
+/// Waits for the next quote pushed by the TLS server.
+///
+/// Only call it while no request is in flight, since responses and quotes share the
+/// connection.
+///
+/// Arguments:
+/// socket - The TLS stream to use.
+///
+/// Returns: the quote on success, ClientError on error containing reason of failure.
+///
+/// Example:
+/// ```rust
+///     while let Ok(quote) = read_quote(&mut socket).await {
+///         println!("{}: {}", quote.val.symbol, quote.val.ask_price);
+///     }
+/// ```
+pub async fn read_quote(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+) -> ClientResult<StreamedQuote> {
+    let frame = read_message(socket).await.map_err(|err| match err.kind() {
+        io::ErrorKind::InvalidData => {
+            ClientError::InvalidResponse(ReturnFlags::ClientQuoteStreamError)
+        }
+        _ => ClientError::Io(err),
+    })?;
+
+    if frame.msgtype == MessageType::Stream
+        && frame.instruction == DataTransferInst::QuoteUpdate as i64
+    {
+        frame
+            .payload::<QuoteUpdate>()
+            .map(|update| StreamedQuote {
+                val: update.val,
+                sequence: frame.data_message_number,
+                latest: frame.data_message_max,
+            })
+            .map_err(|_| ClientError::InvalidResponse(ReturnFlags::ClientQuoteStreamError))
+    } else {
+        /* not a quote */
+        Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientQuoteStreamError,
+        ))
+    }
+}
@@ -1,4 +1,9 @@
 pub mod account;
+pub mod config;
 pub mod ds;
 pub mod initializer;
+pub mod market;
 pub mod network;
+pub mod session;
+
+pub use session::Session;
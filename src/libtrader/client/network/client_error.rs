@@ -0,0 +1,79 @@
+use std::fmt;
+use std::io;
+
+use crate::common::misc::return_flags::ReturnFlags;
+
+/// The reason a client request failed.
+///
+/// Variants:
+/// Io - The connection to the server failed.
+/// Server - The server rejected the request, with the flag and message it sent back.
+/// InvalidRequest - The request was not sent, since the server would reject it.
+/// InvalidResponse - The server response could not be understood.
+#[derive(Debug)]
+pub enum ClientError {
+    Io(io::Error),
+    Server { flag: ReturnFlags, message: String },
+    InvalidRequest(ReturnFlags),
+    InvalidResponse(ReturnFlags),
+}
+
+/// Result of a client request.
+pub type ClientResult<T> = Result<T, ClientError>;
+
+impl ClientError {
+    /// Returns the flag describing the failure, if there is one.
+    ///
+    /// Example:
+    /// ```rust
+    ///     match acc_retrieve_portfolio(&mut socket, jwt).await {
+    ///         Err(err) if err.flag() == Some(ReturnFlags::ServerAccTokenExpired) => relogin(),
+    ///         ...
+    ///     }
+    /// ```
+    pub fn flag(&self) -> Option<ReturnFlags> {
+        match self {
+            ClientError::Io(_) => None,
+            ClientError::Server { flag, .. } => Some(*flag),
+            ClientError::InvalidRequest(flag) => Some(*flag),
+            ClientError::InvalidResponse(flag) => Some(*flag),
+        }
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Io(err) => write!(f, "connection error: {}", err),
+            ClientError::Server { flag, message } => write!(f, "{}: {}", flag, message),
+            ClientError::InvalidRequest(flag) => write!(f, "{}: {}", flag, flag.message()),
+            ClientError::InvalidResponse(flag) => {
+                write!(f, "{}: invalid server response", flag)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ClientError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(err: io::Error) -> Self {
+        ClientError::Io(err)
+    }
+}
+
+impl From<ClientError> for io::Error {
+    fn from(err: ClientError) -> Self {
+        match err {
+            ClientError::Io(err) => err,
+            err => io::Error::other(err.to_string()),
+        }
+    }
+}
@@ -1,21 +1,26 @@
-use ring::digest;
-use std::io;
-
 use crate::common::message::inst::CommandInst;
 use crate::common::message::message::Message;
-use crate::common::message::message_builder::message_builder;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
 use crate::common::message::message_type::MessageType;
+use crate::common::message::request::SaltRequest;
+use crate::common::message::response::SaltResponse;
 use crate::common::misc::return_flags::ReturnFlags;
+use ring::digest;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
 
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use futures::SinkExt;
 use tokio::net::TcpStream;
 use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
 
 /// Issues a command to the connected TLS server to obtain a stored salt for either email or
 /// password.
 ///
 /// All salts returned are of size ```digest::SHA512_OUTPUT_LEN``` or 64 bytes.
-/// Should be used in contexts that return ```io::Result```.
+/// Should be used in contexts that return ```ClientResult```.
 /// Should be used in Async contexts.
 ///
 /// Arguments:
@@ -23,72 +28,58 @@ use tokio_rustls::client::TlsStream;
 /// username - The username to obtain the salt.
 /// salt_type - The CommmandInst, either GetEmailSalt, or GetPasswordSalt.
 ///
-/// Returns: a ```io::Result<[u8; 64]>```.
+/// Returns: a ```ClientResult<[u8; 64]>```.
 /// Example:
 /// ```rust
 ///     let server_salt: [u8; digest::SHA512_OUTPUT_LEN/2] = req_server_salt(tls_client, "n1ckn8me",
 ///                                                                          CommandInst::GetEmailSalt)?;
 /// ```
 pub async fn req_server_salt(
-    socket: &mut TlsStream<TcpStream>,
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
     username: &str,
     salt_type: i64,
-) -> io::Result<[u8; digest::SHA512_OUTPUT_LEN]> {
+) -> ClientResult<[u8; digest::SHA512_OUTPUT_LEN]> {
     /* enforce salt_type to be either email or password */
     assert_eq!(salt_type >= CommandInst::GetEmailSalt as i64, true);
     assert_eq!(salt_type <= CommandInst::GetPasswordSalt as i64, true);
 
     /* generate message to send */
-    let message = message_builder(
+    let message = payload_builder(
         MessageType::Command,
         salt_type,
-        1,
-        0,
-        0,
-        username.as_bytes().to_vec(),
+        &SaltRequest {
+            username: username.to_string(),
+        },
     );
-    socket
-        .write_all(&bincode::serialize(&message).unwrap())
-        .await?;
-
-    let mut buf = Vec::with_capacity(4096);
-    socket.read_buf(&mut buf).await?;
+    socket.send(message).await?;
 
-    let ret_msg: Message = bincode::deserialize(&buf).map_err(|_| {
-        io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!("{}", ReturnFlags::ClientReqSaltInvMsg),
-        )
-    })?;
+    let ret_msg: Message = read_response(socket, ReturnFlags::ClientReqSaltInvMsg).await?;
 
     match ret_msg.msgtype {
-        MessageType::Command => Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("{}", ReturnFlags::ClientReqSaltInvMsg),
+        MessageType::Command | MessageType::Stream => Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientReqSaltInvMsg,
         )),
         MessageType::DataTransfer => {
-            if ret_msg.data.len() != digest::SHA512_OUTPUT_LEN {
-                Err(io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    format!("{}", ReturnFlags::ClientReqSaltInvMsgRetSize),
+            let salt = ret_msg
+                .payload::<SaltResponse>()
+                .map_err(|_| ClientError::InvalidResponse(ReturnFlags::ClientReqSaltInvMsg))?
+                .salt;
+            if salt.len() != digest::SHA512_OUTPUT_LEN {
+                Err(ClientError::InvalidResponse(
+                    ReturnFlags::ClientReqSaltInvMsgRetSize,
                 ))
             } else if ret_msg.instruction == salt_type {
-                Ok(*array_ref!(ret_msg.data, 0, digest::SHA512_OUTPUT_LEN))
+                Ok(*array_ref!(salt, 0, digest::SHA512_OUTPUT_LEN))
             } else {
-                Err(io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    format!("{}", ReturnFlags::ClientReqSaltInvMsgInst),
+                Err(ClientError::InvalidResponse(
+                    ReturnFlags::ClientReqSaltInvMsgInst,
                 ))
             }
         }
         MessageType::ServerReturn => match ret_msg.instruction {
-            0 => Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("{}", ReturnFlags::ClientReqSaltRej),
-            )),
-            _ => Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("{}", ReturnFlags::ClientReqSaltInvMsg),
+            0 => Err(ClientError::InvalidResponse(ReturnFlags::ClientReqSaltRej)),
+            _ => Err(ClientError::InvalidResponse(
+                ReturnFlags::ClientReqSaltInvMsg,
             )),
         },
     }
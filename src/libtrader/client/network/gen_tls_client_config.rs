@@ -1,6 +1,12 @@
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
 use std::sync::Arc;
 
-use tokio_rustls::rustls::{ClientConfig, KeyLogFile, NoClientSessionStorage};
+use tokio_rustls::rustls::internal::pemfile::certs;
+use tokio_rustls::rustls::{Certificate, ClientConfig, KeyLogFile, NoClientSessionStorage};
+
+use crate::client::config::ClientOptions;
 
 /// A "always accept" certficate verifier.
 ///
@@ -27,6 +33,96 @@ mod danger {
     }
 }
 
+/// A certificate verifier accepting only pinned server certificates.
+///
+/// Self-signed certificates usually fail the checks of webpki, so pinning replaces the
+/// certificate verifier, which requires the "tls_pin" cargo feature.
+#[cfg(feature = "tls_pin")]
+mod pinning {
+    use ring::digest;
+    use tokio_rustls::rustls;
+    use tokio_rustls::rustls::Certificate;
+    use tokio_rustls::webpki;
+
+    /// The pinned certificates are trusted as they are, which allows self-signed deployments.
+    /// The server name is not checked, since the pin already identifies the server.
+    pub struct PinnedCertVerifier {
+        fingerprints: Vec<Vec<u8>>,
+    }
+
+    impl PinnedCertVerifier {
+        pub fn new(pinned: &[Certificate]) -> Self {
+            PinnedCertVerifier {
+                fingerprints: pinned.iter().map(fingerprint).collect(),
+            }
+        }
+    }
+
+    impl rustls::ServerCertVerifier for PinnedCertVerifier {
+        fn verify_server_cert(
+            &self,
+            _roots: &rustls::RootCertStore,
+            presented_certs: &[rustls::Certificate],
+            _dns_name: webpki::DNSNameRef<'_>,
+            _ocsp: &[u8],
+        ) -> Result<rustls::ServerCertVerified, rustls::TLSError> {
+            match presented_certs.first() {
+                Some(cert) if self.fingerprints.contains(&fingerprint(cert)) => {
+                    Ok(rustls::ServerCertVerified::assertion())
+                }
+                _ => Err(rustls::TLSError::General(
+                    "server certificate does not match the pinned certificate".to_string(),
+                )),
+            }
+        }
+    }
+
+    /// Returns the SHA-256 fingerprint of a certificate.
+    fn fingerprint(cert: &Certificate) -> Vec<u8> {
+        digest::digest(&digest::SHA256, &cert.0).as_ref().to_vec()
+    }
+}
+
+/// Accepts only the server certificates of a PEM file.
+#[cfg(feature = "tls_pin")]
+fn pin_certs(config: &mut ClientConfig, path: &Path) -> io::Result<()> {
+    let pinned = load_certs(path)?;
+    config
+        .dangerous()
+        .set_certificate_verifier(Arc::new(pinning::PinnedCertVerifier::new(&pinned)));
+    Ok(())
+}
+
+/// Rejects certificate pinning, not built in.
+#[cfg(not(feature = "tls_pin"))]
+fn pin_certs(_config: &mut ClientConfig, path: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!(
+            "can not pin {}: built without the tls_pin feature",
+            path.display()
+        ),
+    ))
+}
+
+/// Loads the certificates of a PEM file.
+///
+/// Arguments:
+/// path - Path to the PEM file.
+///
+/// Returns: the certificates, an error if the file holds none.
+fn load_certs(path: &Path) -> io::Result<Vec<Certificate>> {
+    let certs = certs(&mut BufReader::new(File::open(path)?))
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "invalid cert"))?;
+    if certs.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("no certificates in {}", path.display()),
+        ));
+    }
+    Ok(certs)
+}
+
 /// Generates a TlsClient Config.
 ///
 /// Uses defualt settings for:
@@ -34,13 +130,18 @@ mod danger {
 /// - TLs Protocol CypherSuite.
 ///
 /// Assumed Settings:
-/// - if cargo feature "tls_no_verify", then certificates are not
-/// checked, else they are.
+/// - the webpki roots and the certificates of ```options.ca_file``` are trusted.
+/// - if ```options.pin_cert``` is set, only the pinned certificates are accepted, which
+///   requires cargo feature "tls_pin".
+/// - if cargo feature "tls_no_verify", then certificates are not checked, else they are.
 /// - No persistent session storage.
 ///
+/// Arguments:
+/// options - The client options naming the trusted certificates.
+///
 /// Returns; the client configuration in an ```std::io::Result<Arc<ClientConfig>>```.
 /// Example already present in rustls' examples on github.
-pub fn gen_tls_client_config() -> std::io::Result<Arc<ClientConfig>> {
+pub fn gen_tls_client_config(options: &ClientOptions) -> std::io::Result<Arc<ClientConfig>> {
     let mut config = ClientConfig::new();
     config.key_log = Arc::new(KeyLogFile::new());
 
@@ -49,6 +150,21 @@ pub fn gen_tls_client_config() -> std::io::Result<Arc<ClientConfig>> {
         .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
     config.ct_logs = Some(&ct_logs::LOGS);
 
+    if let Some(ca_file) = &options.ca_file {
+        for cert in load_certs(ca_file)? {
+            config.root_store.add(&cert).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid CA certificate in {}: {}", ca_file.display(), err),
+                )
+            })?;
+        }
+    }
+
+    if let Some(pin_cert) = &options.pin_cert {
+        pin_certs(&mut config, pin_cert)?;
+    }
+
     let persist = Arc::new(NoClientSessionStorage {});
     config.set_persistence(persist);
 
@@ -0,0 +1,62 @@
+use crate::common::message::handshake::{hello_request, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION};
+use crate::common::message::inst::CommandInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::response::HelloResponse;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::read_response::read_response;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Performs the client side of the connection handshake.
+///
+/// Must be the first exchange on a new connection. Sends the protocol versions and capabilities
+/// of the client, and returns what the server agreed to.
+///
+/// Arguments:
+/// socket - The TLS stream of the new connection.
+///
+/// Returns: the negotiated version and capabilities on success, ClientError on failure;
+/// ```ServerProtocolUpgradeRequired``` if the server does not speak the client's protocol.
+///
+/// Example:
+/// ```rust
+///     let hello = handshake(&mut socket).await?;
+///     println!("speaking protocol version {}", hello.version);
+/// ```
+pub async fn handshake(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+) -> ClientResult<HelloResponse> {
+    let message = payload_builder(
+        MessageType::Command,
+        CommandInst::Hello as i64,
+        &hello_request(),
+    );
+    socket.send(message).await?;
+
+    let response: Message = read_response(socket, ReturnFlags::ClientHandshakeFailed).await?;
+    if response.msgtype != MessageType::ServerReturn
+        || response.instruction != CommandInst::Hello as i64
+    {
+        return Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientHandshakeFailed,
+        ));
+    }
+
+    let hello: HelloResponse = response
+        .payload()
+        .map_err(|_| ClientError::InvalidResponse(ReturnFlags::ClientHandshakeFailed))?;
+    if hello.version < MIN_PROTOCOL_VERSION || hello.version > PROTOCOL_VERSION {
+        return Err(ClientError::InvalidResponse(
+            ReturnFlags::ClientHandshakeFailed,
+        ));
+    }
+    Ok(hello)
+}
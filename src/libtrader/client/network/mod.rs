@@ -1,3 +1,6 @@
+pub mod client_error;
 pub mod cmd;
 pub mod gen_tls_client_config;
 pub mod handle_data;
+pub mod handshake;
+pub mod read_response;
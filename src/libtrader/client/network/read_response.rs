@@ -0,0 +1,85 @@
+use std::io;
+
+use crate::common::message::message::Message;
+use crate::common::message::message_codec::{read_message, MessageCodec};
+use crate::common::message::message_type::MessageType;
+use crate::common::message::response::ErrorResponse;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::network::client_error::{ClientError, ClientResult};
+
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Reads the server response to a request.
+///
+/// Error responses, a ```ServerReturn``` with instruction ```0```, are turned into
+/// ```ClientError::Server``` with the flag and message sent by the server. Stream frames pushed
+/// while waiting for the response are dropped, like any stale tick.
+///
+/// Arguments:
+/// socket - The TLS stream to read from.
+/// flag - The flag to report if the response can not be decoded.
+///
+/// Returns: the response message on success, ClientError on failure.
+///
+/// Example:
+/// ```rust
+///     let response = read_response(socket, ReturnFlags::ClientAccRetrieveOrdersError).await?;
+/// ```
+pub async fn read_response(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    flag: ReturnFlags,
+) -> ClientResult<Message> {
+    let response = loop {
+        let response = read_message(socket).await.map_err(|err| match err.kind() {
+            io::ErrorKind::InvalidData => ClientError::InvalidResponse(flag),
+            _ => ClientError::Io(err),
+        })?;
+        if response.msgtype != MessageType::Stream {
+            break response;
+        }
+    };
+
+    if response.msgtype == MessageType::ServerReturn && response.instruction == 0 {
+        return Err(error_from_response(&response, flag));
+    }
+    Ok(response)
+}
+
+/// Decodes an error response into a ```ClientError```.
+fn error_from_response(response: &Message, flag: ReturnFlags) -> ClientError {
+    match response.payload::<ErrorResponse>() {
+        Ok(error) => ClientError::Server {
+            flag: error.flag,
+            message: error.message,
+        },
+        Err(_) => ClientError::InvalidResponse(flag),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::common::message::message_builder::error_builder;
+
+    #[test]
+    fn test_error_from_response() {
+        let response = error_builder(ReturnFlags::ServerAccTokenExpired);
+        match error_from_response(&response, ReturnFlags::ClientAccUnauthorized) {
+            ClientError::Server { flag, message } => {
+                assert_eq!(flag, ReturnFlags::ServerAccTokenExpired);
+                assert_eq!(message, ReturnFlags::ServerAccTokenExpired.message());
+            }
+            err => panic!("unexpected error {}", err),
+        }
+
+        let mut response = error_builder(ReturnFlags::ServerAccTokenExpired);
+        response.data.truncate(1);
+        assert_eq!(
+            error_from_response(&response, ReturnFlags::ClientAccUnauthorized).flag(),
+            Some(ReturnFlags::ClientAccUnauthorized)
+        );
+    }
+}
@@ -0,0 +1,443 @@
+use std::io;
+use std::sync::Arc;
+
+use crate::common::account::ledger::Ledger;
+use crate::common::account::order::Order;
+use crate::common::account::portfolio::Portfolio;
+use crate::common::account::position::Position;
+use crate::common::account::transaction::Transaction;
+use crate::common::account::valuation::Valuation;
+use crate::common::generic::company::Company;
+use crate::common::generic::stock_val::StockVal;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::response::HelloResponse;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::client::account::authorization::{acc_auth, login_request};
+use crate::client::account::cancel_order::acc_cancel_order;
+use crate::client::account::change_email::acc_change_email;
+use crate::client::account::change_password::acc_change_password;
+use crate::client::account::close_position::acc_close_position;
+use crate::client::account::creation::acc_create;
+use crate::client::account::deletion::acc_delete;
+use crate::client::account::logout::acc_logout;
+use crate::client::account::place_order::acc_place_order;
+use crate::client::account::purchase_asset::acc_purchase_asset;
+use crate::client::account::refresh::acc_refresh;
+use crate::client::account::retrieval_ledger::acc_retrieve_ledger;
+use crate::client::account::retrieval_orders::acc_retrieve_orders;
+use crate::client::account::retrieval_portfolio::acc_retrieve_portfolio;
+use crate::client::account::retrieval_transaction::acc_retrieve_transaction;
+use crate::client::account::retrieval_valuation::acc_retrieve_valuation;
+use crate::client::market::get_asset_info::get_asset_info;
+use crate::client::market::get_asset_value::{get_asset_value, get_asset_value_current};
+use crate::client::market::quote_stream::{read_quote, subscribe_quotes, StreamedQuote};
+use crate::client::network::client_error::{ClientError, ClientResult};
+use crate::client::network::handshake::handshake;
+
+use tokio::net::{TcpStream, ToSocketAddrs};
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::rustls::ClientConfig;
+use tokio_rustls::webpki::DNSNameRef;
+use tokio_rustls::TlsConnector;
+use tokio_util::codec::Framed;
+
+/// The login details kept by a session to log in again once its token expires.
+struct Credentials {
+    username: String,
+    email: String,
+    password: String,
+}
+
+/// Runs an authorized request, renewing the token and retrying once if it has expired.
+macro_rules! authorized {
+    ($session:ident, |$socket:ident, $jwt:ident| $call:expr) => {{
+        let $jwt = $session.token()?;
+        let $socket = &mut $session.socket;
+        match $call.await {
+            Err(err)
+                if err.flag() == Some(ReturnFlags::ServerAccTokenExpired)
+                    && $session.can_renew() =>
+            {
+                $session.renew().await?;
+                let $jwt = $session.token()?;
+                let $socket = &mut $session.socket;
+                $call.await
+            }
+            result => result,
+        }
+    }};
+}
+
+/// A connection to a libtrader server.
+///
+/// Owns the TLS connection and the session token of the logged in account, and exposes every
+/// instruction of the protocol as an async method. Authorized requests made with an expired
+/// token renew it with the refresh token, or log in again with the stored credentials if the
+/// refresh token is no longer valid, and are retried once.
+///
+/// Example:
+/// ```rust
+///     let mut session = Session::connect("localhost:4000", "localhost", config).await?;
+///     session.login("n1ckn8me", "email@example.com", "password").await?;
+///     let portfolio = session.portfolio().await?;
+/// ```
+pub struct Session {
+    socket: Framed<TlsStream<TcpStream>, MessageCodec>,
+    hello: HelloResponse,
+    credentials: Option<Credentials>,
+    jwt: Option<String>,
+    refresh_token: Option<String>,
+}
+
+impl Session {
+    /// Connects to a libtrader server and performs the handshake.
+    ///
+    /// Arguments:
+    /// addr - The address of the server.
+    /// domain - The domain name the server certificate is checked against.
+    /// config - The TLS client configuration.
+    ///
+    /// Returns: the session on success, ClientError on failure.
+    pub async fn connect<A: ToSocketAddrs>(
+        addr: A,
+        domain: &str,
+        config: Arc<ClientConfig>,
+    ) -> ClientResult<Session> {
+        let domain = DNSNameRef::try_from_ascii_str(domain)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "invalid dnsname"))?;
+        let stream = TcpStream::connect(addr).await?;
+        let stream = TlsConnector::from(config).connect(domain, stream).await?;
+        Session::from_stream(stream).await
+    }
+
+    /// Opens a session over an established TLS connection and performs the handshake.
+    pub async fn from_stream(stream: TlsStream<TcpStream>) -> ClientResult<Session> {
+        let mut socket = Framed::new(stream, MessageCodec::new());
+        let hello = handshake(&mut socket).await?;
+        Ok(Session {
+            socket,
+            hello,
+            credentials: None,
+            jwt: None,
+            refresh_token: None,
+        })
+    }
+
+    /// Returns the negotiated protocol version.
+    pub fn version(&self) -> u32 {
+        self.hello.version
+    }
+
+    /// Returns the capabilities shared with the server.
+    pub fn capabilities(&self) -> &[String] {
+        &self.hello.capabilities
+    }
+
+    /// Returns the session token, if logged in.
+    pub fn jwt(&self) -> Option<&str> {
+        self.jwt.as_deref()
+    }
+
+    /// Returns the refresh token, if logged in.
+    ///
+    /// Long-lived clients can store it to resume the session later with ```resume()```. The
+    /// token changes every time the session token is renewed.
+    pub fn refresh_token(&self) -> Option<&str> {
+        self.refresh_token.as_deref()
+    }
+
+    /// Returns whether the session is logged in.
+    pub fn is_logged_in(&self) -> bool {
+        self.jwt.is_some()
+    }
+
+    /// Creates an account. Does not log in.
+    pub async fn register(
+        &mut self,
+        username: &str,
+        email: &str,
+        password: &str,
+    ) -> ClientResult<()> {
+        acc_create(&mut self.socket, username, email, password).await
+    }
+
+    /// Logs in, keeping the credentials to log in again once the token expires.
+    pub async fn login(&mut self, username: &str, email: &str, password: &str) -> ClientResult<()> {
+        let tokens = acc_auth(&mut self.socket, username, email, password).await?;
+        self.credentials = Some(Credentials {
+            username: username.to_string(),
+            email: email.to_string(),
+            password: password.to_string(),
+        });
+        self.jwt = Some(tokens.jwt);
+        self.refresh_token = Some(tokens.refresh_token);
+        Ok(())
+    }
+
+    /// Logs in with a refresh token kept from an earlier session, without the credentials.
+    pub async fn resume(&mut self, refresh_token: &str) -> ClientResult<()> {
+        let tokens = acc_refresh(&mut self.socket, refresh_token.to_string()).await?;
+        self.credentials = None;
+        self.jwt = Some(tokens.jwt);
+        self.refresh_token = Some(tokens.refresh_token);
+        Ok(())
+    }
+
+    /// Logs out, revoking the session token on the server and forgetting the stored
+    /// credentials.
+    pub async fn logout(&mut self) -> ClientResult<()> {
+        self.credentials = None;
+        self.refresh_token = None;
+        match self.jwt.take() {
+            Some(jwt) => acc_logout(&mut self.socket, jwt, false).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Logs out of every device, revoking every session of the account.
+    pub async fn logout_all(&mut self) -> ClientResult<()> {
+        let jwt = self.token()?;
+        self.credentials = None;
+        self.jwt = None;
+        self.refresh_token = None;
+        acc_logout(&mut self.socket, jwt, true).await
+    }
+
+    /// Changes the password, proving the current credentials.
+    ///
+    /// Every other session of the account is logged out, this session continues with new
+    /// tokens.
+    pub async fn change_password(
+        &mut self,
+        username: &str,
+        email: &str,
+        password: &str,
+        new_password: &str,
+    ) -> ClientResult<()> {
+        let credentials = login_request(&mut self.socket, username, email, password).await?;
+        let tokens = authorized!(self, |socket, jwt| acc_change_password(
+            socket,
+            jwt,
+            credentials.clone(),
+            new_password
+        ))?;
+        self.jwt = Some(tokens.jwt);
+        self.refresh_token = Some(tokens.refresh_token);
+        if let Some(credentials) = self.credentials.as_mut() {
+            credentials.password = new_password.to_string();
+        }
+        Ok(())
+    }
+
+    /// Changes the email, proving the current credentials.
+    pub async fn change_email(
+        &mut self,
+        username: &str,
+        email: &str,
+        password: &str,
+        new_email: &str,
+    ) -> ClientResult<()> {
+        let credentials = login_request(&mut self.socket, username, email, password).await?;
+        authorized!(self, |socket, jwt| acc_change_email(
+            socket,
+            jwt,
+            credentials.clone(),
+            new_email
+        ))?;
+        if let Some(credentials) = self.credentials.as_mut() {
+            credentials.email = new_email.to_string();
+        }
+        Ok(())
+    }
+
+    /// Deletes the account, proving the current credentials, and logs out.
+    pub async fn delete_account(
+        &mut self,
+        username: &str,
+        email: &str,
+        password: &str,
+    ) -> ClientResult<()> {
+        let credentials = login_request(&mut self.socket, username, email, password).await?;
+        authorized!(self, |socket, jwt| acc_delete(
+            socket,
+            jwt,
+            credentials.clone()
+        ))?;
+        self.credentials = None;
+        self.jwt = None;
+        self.refresh_token = None;
+        Ok(())
+    }
+
+    /// Retrieves the open positions of the account.
+    pub async fn portfolio(&mut self) -> ClientResult<Portfolio> {
+        authorized!(self, |socket, jwt| acc_retrieve_portfolio(socket, jwt))
+    }
+
+    /// Retrieves the transaction history of the account.
+    pub async fn transactions(&mut self) -> ClientResult<Vec<Transaction>> {
+        authorized!(self, |socket, jwt| acc_retrieve_transaction(socket, jwt))
+    }
+
+    /// Retrieves the cash balance and ledger entries of the account.
+    pub async fn ledger(&mut self) -> ClientResult<Ledger> {
+        authorized!(self, |socket, jwt| acc_retrieve_ledger(socket, jwt))
+    }
+
+    /// Retrieves the orders placed by the account.
+    pub async fn orders(&mut self) -> ClientResult<Vec<Order>> {
+        authorized!(self, |socket, jwt| acc_retrieve_orders(socket, jwt))
+    }
+
+    /// Retrieves the valuation of the account.
+    pub async fn valuation(&mut self) -> ClientResult<Valuation> {
+        authorized!(self, |socket, jwt| acc_retrieve_valuation(socket, jwt))
+    }
+
+    /// Buys shares of an asset at market price.
+    pub async fn purchase_asset(
+        &mut self,
+        symbol: &str,
+        shares_amount: i64,
+    ) -> ClientResult<Position> {
+        authorized!(self, |socket, jwt| acc_purchase_asset(
+            socket,
+            jwt,
+            symbol,
+            shares_amount,
+            true
+        ))
+    }
+
+    /// Sells shares of an asset at market price.
+    pub async fn sell_asset(&mut self, symbol: &str, shares_amount: i64) -> ClientResult<Position> {
+        authorized!(self, |socket, jwt| acc_purchase_asset(
+            socket,
+            jwt,
+            symbol,
+            shares_amount,
+            false
+        ))
+    }
+
+    /// Places an order, returning it as stored by the server.
+    pub async fn place_order(&mut self, order: &Order) -> ClientResult<Order> {
+        authorized!(self, |socket, jwt| acc_place_order(socket, jwt, order))
+    }
+
+    /// Cancels an open order.
+    pub async fn cancel_order(&mut self, order_id: i64) -> ClientResult<()> {
+        authorized!(self, |socket, jwt| acc_cancel_order(socket, jwt, order_id))
+    }
+
+    /// Closes shares of an open position.
+    pub async fn close_position(
+        &mut self,
+        position_id: i64,
+        shares_amount: i64,
+    ) -> ClientResult<Position> {
+        authorized!(self, |socket, jwt| acc_close_position(
+            socket,
+            jwt,
+            position_id,
+            shares_amount
+        ))
+    }
+
+    /// Retrieves the company behind an asset.
+    pub async fn asset_info(&mut self, symbol: &str) -> ClientResult<Company> {
+        get_asset_info(&mut self.socket, symbol).await
+    }
+
+    /// Retrieves the quotes of an asset between two epochs, inclusive.
+    pub async fn asset_value(
+        &mut self,
+        symbol: &str,
+        start_epoch: i64,
+        end_epoch: i64,
+    ) -> ClientResult<Vec<StockVal>> {
+        get_asset_value(&mut self.socket, symbol, start_epoch, end_epoch).await
+    }
+
+    /// Retrieves the latest quote of an asset.
+    pub async fn asset_value_current(&mut self, symbol: &str) -> ClientResult<StockVal> {
+        get_asset_value_current(&mut self.socket, symbol).await
+    }
+
+    /// Subscribes to the quotes of the symbols, returning every symbol subscribed to.
+    pub async fn subscribe_quotes(&mut self, symbols: &[&str]) -> ClientResult<Vec<String>> {
+        self.require_capability("quote_stream")?;
+        subscribe_quotes(&mut self.socket, symbols, true).await
+    }
+
+    /// Unsubscribes from the quotes of the symbols, returning every symbol still subscribed to.
+    pub async fn unsubscribe_quotes(&mut self, symbols: &[&str]) -> ClientResult<Vec<String>> {
+        self.require_capability("quote_stream")?;
+        subscribe_quotes(&mut self.socket, symbols, false).await
+    }
+
+    /// Waits for the next quote of a subscribed symbol.
+    ///
+    /// Quotes pushed while a request is waiting for its response are dropped.
+    pub async fn next_quote(&mut self) -> ClientResult<StreamedQuote> {
+        read_quote(&mut self.socket).await
+    }
+
+    /// Checks that a capability was negotiated with the server before using it.
+    fn require_capability(&self, capability: &str) -> ClientResult<()> {
+        if self.capabilities().iter().any(|cap| cap == capability) {
+            Ok(())
+        } else {
+            Err(ClientError::InvalidRequest(
+                ReturnFlags::CommonCapabilityNotNegotiated,
+            ))
+        }
+    }
+
+    /// Returns a copy of the session token for a request.
+    fn token(&self) -> ClientResult<String> {
+        self.jwt.clone().ok_or(ClientError::InvalidRequest(
+            ReturnFlags::ClientAccUnauthorized,
+        ))
+    }
+
+    /// Returns whether an expired session token can be renewed.
+    fn can_renew(&self) -> bool {
+        self.refresh_token.is_some() || self.credentials.is_some()
+    }
+
+    /// Renews the session token with the refresh token, falling back to logging in again with
+    /// the stored credentials.
+    async fn renew(&mut self) -> ClientResult<()> {
+        self.jwt = None;
+        if let Some(refresh_token) = self.refresh_token.take() {
+            match acc_refresh(&mut self.socket, refresh_token).await {
+                Ok(tokens) => {
+                    self.jwt = Some(tokens.jwt);
+                    self.refresh_token = Some(tokens.refresh_token);
+                    return Ok(());
+                }
+                Err(err) if self.credentials.is_none() => return Err(err),
+                Err(_) => {}
+            }
+        }
+
+        let credentials = self
+            .credentials
+            .as_ref()
+            .ok_or(ClientError::InvalidRequest(
+                ReturnFlags::ClientAccUnauthorized,
+            ))?;
+        let tokens = acc_auth(
+            &mut self.socket,
+            &credentials.username,
+            &credentials.email,
+            &credentials.password,
+        )
+        .await?;
+        self.jwt = Some(tokens.jwt);
+        self.refresh_token = Some(tokens.refresh_token);
+        Ok(())
+    }
+}
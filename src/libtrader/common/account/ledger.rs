@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// A single cash movement on an account.
+///
+/// Members:
+/// transaction_id - The transaction that caused the movement, none for deposits.
+/// amount - The signed amount, positive for credits and negative for debits.
+/// balance - The cash balance after the movement.
+/// time_epoch - The unix epoch of the movement.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct LedgerEntry {
+    pub transaction_id: Option<i64>,
+    pub amount: f64,
+    pub balance: f64,
+    pub time_epoch: i64,
+}
+impl std::fmt::Display for LedgerEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "({:?}, {}, {}, {})",
+            self.transaction_id, self.amount, self.balance, self.time_epoch
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct Ledger {
+    pub cash_balance: f64,
+    pub entries: Vec<LedgerEntry>,
+}
+impl std::fmt::Display for Ledger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {:#?})", self.cash_balance, self.entries)
+    }
+}
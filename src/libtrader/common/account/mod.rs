@@ -1,6 +1,8 @@
 pub mod hash;
+pub mod ledger;
 pub mod order;
 pub mod portfolio;
 pub mod position;
 pub mod session;
 pub mod transaction;
+pub mod valuation;
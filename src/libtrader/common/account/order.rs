@@ -1,24 +1,80 @@
-#[derive(PartialEq, Debug)]
+use serde::{Deserialize, Serialize};
+
+/// The kind of a working order.
+///
+/// Limit - Fills at the limit price or better.
+/// Stop - Becomes a market order once the stop price is reached.
+/// StopLimit - Becomes a limit order once the stop price is reached.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone, Copy)]
+pub enum OrderType {
+    #[default]
+    Limit = 0,
+    Stop = 1,
+    StopLimit = 2,
+}
+impl OrderType {
+    pub fn from_i16(val: i16) -> Option<OrderType> {
+        match val {
+            _ if val == OrderType::Limit as i16 => Some(OrderType::Limit),
+            _ if val == OrderType::Stop as i16 => Some(OrderType::Stop),
+            _ if val == OrderType::StopLimit as i16 => Some(OrderType::StopLimit),
+            _ => None,
+        }
+    }
+}
+impl std::fmt::Display for OrderType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// A working order.
+///
+/// Members:
+/// id - The DB entry id of the order.
+/// order_type - The kind of the order.
+/// is_buy - Whether the order buys or sells.
+/// stock_symbol - The symbol of the ordered stock.
+/// stock_price - The limit price, unused by stop orders.
+/// stop_price - The stop price, unused by limit orders.
+/// stock_amount - The ordered number of shares.
+/// stock_filled - The number of shares filled so far.
+/// is_triggered - Whether the stop price was reached, always true for limit orders.
+/// is_filled - Whether all shares are filled.
+/// is_cancelled - Whether the order was cancelled before filling.
+/// create_epoch - The unix epoch at which the order was placed.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
 pub struct Order {
+    pub id: i64,
+    pub order_type: OrderType,
     pub is_buy: bool,
     pub stock_symbol: String,
     pub stock_price: f64,
-    pub stock_amount: isize,
-    pub stock_filled: isize,
+    pub stop_price: f64,
+    pub stock_amount: i64,
+    pub stock_filled: i64,
+    pub is_triggered: bool,
     pub is_filled: bool,
+    pub is_cancelled: bool,
+    pub create_epoch: i64,
 }
 impl std::fmt::Display for Order {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "({}, {}, {}, {}, {}, {}, {})",
+            "({}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {})",
+            self.id,
+            self.order_type,
             self.is_buy,
             self.stock_symbol,
             self.stock_price,
-            self.stock_amount,
+            self.stop_price,
             self.stock_amount,
             self.stock_filled,
-            self.is_filled
+            self.is_triggered,
+            self.is_filled,
+            self.is_cancelled,
+            self.create_epoch
         )
     }
 }
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
 pub struct Position {
+    pub id: i64,
     pub is_buy: bool,
     pub stock_symbol: String,
     pub stock_open_amount: i64,
@@ -18,7 +19,8 @@ impl std::fmt::Display for Position {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "({}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {})",
+            "({}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {})",
+            self.id,
             self.is_buy,
             self.stock_symbol,
             self.stock_open_amount,
@@ -33,3 +35,83 @@ impl std::fmt::Display for Position {
         )
     }
 }
+
+impl Position {
+    /// Closes shares of the position.
+    ///
+    /// Long positions are closed by selling and short positions (```is_buy == false```) by
+    /// buying back. The close price is kept as the average over all closes.
+    ///
+    /// Arguments:
+    /// shares_amount - The number of shares to close, at most the shares still open.
+    /// price - The price per share the shares are closed at.
+    /// close_epoch - The unix epoch of the close.
+    ///
+    /// Example:
+    /// ```rust
+    ///     position.close(5, quote.bid_price, now);
+    /// ```
+    pub fn close(&mut self, shares_amount: i64, price: f64, close_epoch: i64) {
+        self.stock_close_cost += price * shares_amount as f64;
+        self.stock_close_amount += shares_amount;
+        self.stock_close_price = self.stock_close_cost / self.stock_close_amount as f64;
+        self.close_epoch = close_epoch;
+        self.is_open = self.stock_close_amount < self.stock_open_amount;
+    }
+
+    /// Returns the realized profit and loss of the closed shares.
+    ///
+    /// Long positions gain when closed above the open price, short positions gain when closed
+    /// below it.
+    pub fn realized_pnl(&self) -> f64 {
+        let opened_cost = self.stock_open_price * self.stock_close_amount as f64;
+        if self.is_buy {
+            self.stock_close_cost - opened_cost
+        } else {
+            opened_cost - self.stock_close_cost
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_position_close() {
+        /* long position closed in two steps */
+        let mut position = Position {
+            is_buy: true,
+            stock_open_amount: 10,
+            stock_open_price: 100.0,
+            stock_open_cost: 1000.0,
+            is_open: true,
+            ..Position::default()
+        };
+        position.close(4, 110.0, 1);
+        assert!(position.is_open);
+        assert_eq!(position.stock_close_amount, 4);
+        assert_eq!(position.realized_pnl(), 40.0);
+
+        position.close(6, 95.0, 2);
+        assert!(!position.is_open);
+        assert_eq!(position.stock_close_amount, 10);
+        assert_eq!(position.stock_close_cost, 1010.0);
+        assert_eq!(position.stock_close_price, 101.0);
+        assert_eq!(position.close_epoch, 2);
+        assert_eq!(position.realized_pnl(), 10.0);
+
+        /* short position gains when bought back lower */
+        let mut position = Position {
+            is_buy: false,
+            stock_open_amount: 10,
+            stock_open_price: 100.0,
+            stock_open_cost: 1000.0,
+            is_open: true,
+            ..Position::default()
+        };
+        position.close(10, 90.0, 1);
+        assert!(!position.is_open);
+        assert_eq!(position.realized_pnl(), 100.0);
+    }
+}
@@ -1,19 +1,60 @@
-use chrono::{DateTime, Utc};
-use std::net::Ipv4Addr;
+use std::net::IpAddr;
 
+/// A session issued at login, identified by the ```jti``` of its JWT token.
+///
+/// Members:
+/// sess_id - The id of the session, the ```jti``` of its token.
+/// user_id - The user the session was issued to.
+/// client_ip - The address the user logged in from.
+/// issue_date - The unix epoch at which the session was issued.
+/// expiry_date - The unix epoch at which the session expires.
+/// is_active - Whether the session is still valid, false once logged out or revoked.
 #[derive(PartialEq, Debug)]
 pub struct SessionID {
     pub sess_id: String,
-    pub client_ip: Ipv4Addr,
-    pub expiry_date: DateTime<Utc>,
+    pub user_id: i64,
+    pub client_ip: IpAddr,
+    pub issue_date: i64,
+    pub expiry_date: i64,
     pub is_active: bool,
 }
 impl std::fmt::Display for SessionID {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "({}, {}, {}, {})",
-            self.sess_id, self.client_ip, self.expiry_date, self.is_active
+            "({}, {}, {}, {}, {}, {})",
+            self.sess_id,
+            self.user_id,
+            self.client_ip,
+            self.issue_date,
+            self.expiry_date,
+            self.is_active
         )
     }
 }
+
+/// A refresh token issued with a session, stored by the hash of the token.
+///
+/// Every refresh token is single use: exchanging it issues a new session and refresh token in
+/// the same family. Exchanging a refresh token twice revokes its whole family.
+///
+/// Members:
+/// token_hash - The SHA-256 of the refresh token, in hex.
+/// family_id - The id shared by every refresh token descending from the same login.
+/// user_id - The user the token was issued to.
+/// sess_id - The session issued along with the token.
+/// issue_date - The unix epoch at which the token was issued.
+/// expiry_date - The unix epoch at which the token expires.
+/// is_used - Whether the token was already exchanged.
+/// is_revoked - Whether the token was logged out or revoked.
+#[derive(PartialEq, Debug)]
+pub struct RefreshToken {
+    pub token_hash: String,
+    pub family_id: String,
+    pub user_id: i64,
+    pub sess_id: String,
+    pub issue_date: i64,
+    pub expiry_date: i64,
+    pub is_used: bool,
+    pub is_revoked: bool,
+}
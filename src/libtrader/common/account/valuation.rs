@@ -0,0 +1,255 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::account::position::Position;
+use crate::common::generic::company::Company;
+use crate::common::generic::stock_val::StockVal;
+
+/// An open position marked to the latest quote.
+///
+/// Members:
+/// position_id - The DB entry id of the position.
+/// stock_symbol - The symbol of the position's stock.
+/// sector - The sector of the position's company.
+/// is_buy - Whether the position is long or short.
+/// shares_amount - The number of shares still open.
+/// open_price - The price per share the position was opened at.
+/// mark_price - The price per share the position is marked at.
+/// market_value - The unsigned value of the open shares at the mark price.
+/// unrealized_pnl - The profit and loss of the open shares at the mark price.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct PositionValuation {
+    pub position_id: i64,
+    pub stock_symbol: String,
+    pub sector: String,
+    pub is_buy: bool,
+    pub shares_amount: i64,
+    pub open_price: f64,
+    pub mark_price: f64,
+    pub market_value: f64,
+    pub unrealized_pnl: f64,
+}
+impl std::fmt::Display for PositionValuation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "({}, {}, {}, {}, {}, {}, {}, {}, {})",
+            self.position_id,
+            self.stock_symbol,
+            self.sector,
+            self.is_buy,
+            self.shares_amount,
+            self.open_price,
+            self.mark_price,
+            self.market_value,
+            self.unrealized_pnl
+        )
+    }
+}
+
+/// A valuation snapshot of an account.
+///
+/// Members:
+/// cash - The cash balance.
+/// positions - The open positions marked to the latest quotes.
+/// unrealized_pnl - The summed unrealized profit and loss of the open positions.
+/// gross_exposure - The summed market value of long and short positions.
+/// net_exposure - The market value of long positions minus that of short positions.
+/// total_equity - The cash plus the net exposure.
+/// sector_allocation - The share of the gross exposure held in each sector.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct Valuation {
+    pub cash: f64,
+    pub positions: Vec<PositionValuation>,
+    pub unrealized_pnl: f64,
+    pub gross_exposure: f64,
+    pub net_exposure: f64,
+    pub total_equity: f64,
+    pub sector_allocation: BTreeMap<String, f64>,
+}
+impl std::fmt::Display for Valuation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "({}, {:#?}, {}, {}, {}, {}, {:#?})",
+            self.cash,
+            self.positions,
+            self.unrealized_pnl,
+            self.gross_exposure,
+            self.net_exposure,
+            self.total_equity,
+            self.sector_allocation
+        )
+    }
+}
+
+impl Valuation {
+    /// Values an account's open positions against the latest quotes.
+    ///
+    /// Long positions are marked at the bid price and short positions at the ask price, which
+    /// are the prices they could be closed at. Positions without a quote are marked at their
+    /// open price, and companies without a known sector are grouped under "Unknown".
+    ///
+    /// Arguments:
+    /// cash - The cash balance of the account.
+    /// positions - The positions of the account, closed positions are skipped.
+    /// quotes - The latest quote by symbol.
+    /// companies - The companies by symbol.
+    ///
+    /// Returns: the valuation snapshot.
+    ///
+    /// Example:
+    /// ```rust
+    ///     let valuation = Valuation::new(cash, &portfolio.open_positions, &quotes, &companies);
+    ///     println!("equity: {}", valuation.total_equity);
+    /// ```
+    pub fn new(
+        cash: f64,
+        positions: &[Position],
+        quotes: &HashMap<String, StockVal>,
+        companies: &HashMap<String, Company>,
+    ) -> Valuation {
+        let mut valuation = Valuation {
+            cash,
+            ..Valuation::default()
+        };
+
+        let mut sector_exposure: BTreeMap<String, f64> = BTreeMap::new();
+        for position in positions.iter().filter(|position| position.is_open) {
+            let shares_amount = position.stock_open_amount - position.stock_close_amount;
+            let mark_price = match quotes.get(&position.stock_symbol) {
+                Some(quote) if position.is_buy => quote.bid_price,
+                Some(quote) => quote.ask_price,
+                None => position.stock_open_price,
+            };
+            let market_value = mark_price * shares_amount as f64;
+            let unrealized_pnl = if position.is_buy {
+                (mark_price - position.stock_open_price) * shares_amount as f64
+            } else {
+                (position.stock_open_price - mark_price) * shares_amount as f64
+            };
+            let sector = match companies.get(&position.stock_symbol) {
+                Some(company) if !company.sector.is_empty() => company.sector.clone(),
+                _ => "Unknown".to_string(),
+            };
+
+            valuation.unrealized_pnl += unrealized_pnl;
+            valuation.gross_exposure += market_value;
+            if position.is_buy {
+                valuation.net_exposure += market_value;
+            } else {
+                valuation.net_exposure -= market_value;
+            }
+            *sector_exposure.entry(sector.clone()).or_default() += market_value;
+
+            valuation.positions.push(PositionValuation {
+                position_id: position.id,
+                stock_symbol: position.stock_symbol.clone(),
+                sector,
+                is_buy: position.is_buy,
+                shares_amount,
+                open_price: position.stock_open_price,
+                mark_price,
+                market_value,
+                unrealized_pnl,
+            });
+        }
+
+        valuation.total_equity = valuation.cash + valuation.net_exposure;
+        if valuation.gross_exposure > 0.0 {
+            for (sector, exposure) in sector_exposure {
+                valuation
+                    .sector_allocation
+                    .insert(sector, exposure / valuation.gross_exposure);
+            }
+        }
+        valuation
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_valuation_new() {
+        let positions = vec![
+            Position {
+                id: 1,
+                is_buy: true,
+                stock_symbol: "AAA".into(),
+                stock_open_amount: 10,
+                stock_open_price: 100.0,
+                stock_close_amount: 5,
+                is_open: true,
+                ..Position::default()
+            },
+            Position {
+                id: 2,
+                is_buy: false,
+                stock_symbol: "BBB".into(),
+                stock_open_amount: 10,
+                stock_open_price: 50.0,
+                is_open: true,
+                ..Position::default()
+            },
+            Position {
+                id: 3,
+                is_buy: true,
+                stock_symbol: "AAA".into(),
+                stock_open_amount: 10,
+                stock_close_amount: 10,
+                is_open: false,
+                ..Position::default()
+            },
+        ];
+        let mut quotes = HashMap::new();
+        quotes.insert(
+            "AAA".to_string(),
+            StockVal {
+                ask_price: 121.0,
+                bid_price: 120.0,
+                ..StockVal::default()
+            },
+        );
+        quotes.insert(
+            "BBB".to_string(),
+            StockVal {
+                ask_price: 40.0,
+                bid_price: 39.0,
+                ..StockVal::default()
+            },
+        );
+        let mut companies = HashMap::new();
+        companies.insert(
+            "AAA".to_string(),
+            Company {
+                symbol: "AAA".into(),
+                sector: "Technology".into(),
+                ..Company::default()
+            },
+        );
+
+        let valuation = Valuation::new(1000.0, &positions, &quotes, &companies);
+        assert_eq!(valuation.positions.len(), 2);
+
+        /* the long is marked at the bid */
+        assert_eq!(valuation.positions[0].mark_price, 120.0);
+        assert_eq!(valuation.positions[0].market_value, 600.0);
+        assert_eq!(valuation.positions[0].unrealized_pnl, 100.0);
+
+        /* the short is marked at the ask */
+        assert_eq!(valuation.positions[1].mark_price, 40.0);
+        assert_eq!(valuation.positions[1].market_value, 400.0);
+        assert_eq!(valuation.positions[1].unrealized_pnl, 100.0);
+        assert_eq!(valuation.positions[1].sector, "Unknown");
+
+        assert_eq!(valuation.unrealized_pnl, 200.0);
+        assert_eq!(valuation.gross_exposure, 1000.0);
+        assert_eq!(valuation.net_exposure, 200.0);
+        assert_eq!(valuation.total_equity, 1200.0);
+        assert_eq!(valuation.sector_allocation["Technology"], 0.6);
+        assert_eq!(valuation.sector_allocation["Unknown"], 0.4);
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Default, Eq, PartialEq, Clone, Debug)]
+#[serde(default)]
 pub struct Company {
     pub id: i64,
     pub symbol: String,
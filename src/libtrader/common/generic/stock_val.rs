@@ -1,9 +1,11 @@
 use postgres_types::{FromSql, ToSql};
 use serde::{Deserialize, Serialize};
 
-#[derive(Default, PartialEq, Debug, ToSql, FromSql, Serialize, Deserialize)]
+#[derive(Default, PartialEq, Clone, Debug, ToSql, FromSql, Serialize, Deserialize)]
 pub struct StockVal {
+    #[serde(default)]
     pub id: i64,
+    pub symbol: String,
     pub isin: String,
     pub time_epoch: i64,
     pub ask_price: f64,
@@ -14,8 +16,14 @@ impl std::fmt::Display for StockVal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "({}, {}, {}, {}, {}, {})",
-            self.id, self.isin, self.time_epoch, self.ask_price, self.bid_price, self.volume
+            "({}, {}, {}, {}, {}, {}, {})",
+            self.id,
+            self.symbol,
+            self.isin,
+            self.time_epoch,
+            self.ask_price,
+            self.bid_price,
+            self.volume
         )
     }
 }
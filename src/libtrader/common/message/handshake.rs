@@ -0,0 +1,124 @@
+use crate::common::message::inst::DataTransferInst;
+use crate::common::message::request::HelloRequest;
+
+/// The newest protocol version spoken by this build.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The oldest protocol version still spoken by this build.
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// The optional features supported by this build.
+///
+/// The instructions of a capability are only served on connections that negotiated it, see
+/// ```required_capability```.
+pub const CAPABILITIES: &[&str] = &["quote_stream"];
+
+/// Builds the hello sent by a client to open a connection.
+pub fn hello_request() -> HelloRequest {
+    HelloRequest {
+        min_version: MIN_PROTOCOL_VERSION,
+        max_version: PROTOCOL_VERSION,
+        capabilities: CAPABILITIES.iter().map(|cap| cap.to_string()).collect(),
+    }
+}
+
+/// Picks the protocol version to use with a peer.
+///
+/// Arguments:
+/// min_version - The oldest version the peer speaks.
+/// max_version - The newest version the peer speaks.
+///
+/// Returns: the newest version both sides speak, nothing if there is none.
+///
+/// Example:
+/// ```rust
+///     match negotiate_version(request.min_version, request.max_version) {
+///         Some(version) => println!("speaking version {}", version),
+///         None => println!("upgrade required"),
+///     }
+/// ```
+pub fn negotiate_version(min_version: u32, max_version: u32) -> Option<u32> {
+    let version = std::cmp::min(max_version, PROTOCOL_VERSION);
+    if version >= std::cmp::max(min_version, MIN_PROTOCOL_VERSION) {
+        Some(version)
+    } else {
+        None
+    }
+}
+
+/// Returns the capabilities supported by both this build and the peer.
+pub fn negotiate_capabilities(capabilities: &[String]) -> Vec<String> {
+    capabilities
+        .iter()
+        .filter(|cap| CAPABILITIES.contains(&cap.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Returns the capability a connection must have negotiated to send an instruction.
+///
+/// Arguments:
+/// instruction - The instruction of a request.
+///
+/// Returns: the capability, nothing if every connection may send the instruction.
+///
+/// Example:
+/// ```rust
+///     if let Some(capability) = required_capability(message.instruction) {
+///         println!("{} needs {}", message.instruction, capability);
+///     }
+/// ```
+pub fn required_capability(instruction: i64) -> Option<&'static str> {
+    match instruction {
+        _ if instruction == DataTransferInst::SubscribeQuotes as i64
+            || instruction == DataTransferInst::UnsubscribeQuotes as i64 =>
+        {
+            Some("quote_stream")
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_negotiate_version() {
+        assert_eq!(
+            negotiate_version(MIN_PROTOCOL_VERSION, PROTOCOL_VERSION),
+            Some(PROTOCOL_VERSION)
+        );
+        assert_eq!(
+            negotiate_version(MIN_PROTOCOL_VERSION, PROTOCOL_VERSION + 5),
+            Some(PROTOCOL_VERSION)
+        );
+        assert_eq!(
+            negotiate_version(PROTOCOL_VERSION + 1, PROTOCOL_VERSION + 5),
+            None
+        );
+        assert_eq!(negotiate_version(0, MIN_PROTOCOL_VERSION - 1), None);
+    }
+
+    #[test]
+    fn test_negotiate_capabilities() {
+        let caps = vec!["quote_stream".to_string(), "teleport".to_string()];
+        assert_eq!(
+            negotiate_capabilities(&caps),
+            vec!["quote_stream".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_required_capability() {
+        assert_eq!(
+            required_capability(DataTransferInst::SubscribeQuotes as i64),
+            Some("quote_stream")
+        );
+        assert!(CAPABILITIES.contains(&"quote_stream"));
+        assert_eq!(
+            required_capability(DataTransferInst::GetAssetValue as i64),
+            None
+        );
+    }
+}
@@ -1,6 +1,11 @@
 #[allow(dead_code)]
 static INST_SWITCH_STATE: isize = 0;
 
+/*
+ * NOTE: CommandInst and DataTransferInst share a single instruction namespace, so no two
+ * instructions may have the same number. ```Hello``` keeps its number in every protocol version,
+ * for the handshake to work with any client.
+ * */
 #[derive(PartialEq, Debug)]
 pub enum CommandInst {
     LoginMethod1 = 1,
@@ -11,6 +16,16 @@ pub enum CommandInst {
     GenHashSalt = 6,
     GetEmailSalt = 7,
     GetPasswordSalt = 8,
+    PlaceOrder = 13,
+    CancelOrder = 14,
+    ClosePosition = 16,
+    Hello = 21,
+    Logout = 22,
+    LogoutAll = 23,
+    RefreshToken = 24,
+    ChangePassword = 25,
+    ChangeEmail = 26,
+    DeleteAccount = 27,
 }
 impl std::fmt::Display for CommandInst {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -19,16 +34,22 @@ impl std::fmt::Display for CommandInst {
 }
 
 #[allow(dead_code)]
-static INST_COMMAND_MAX_ID: isize = CommandInst::GetPasswordSalt as isize;
+static INST_COMMAND_MAX_ID: isize = CommandInst::DeleteAccount as isize;
 
 #[derive(PartialEq, Debug)]
 pub enum DataTransferInst {
-    GetAssetInfo = 6,
-    GetAssetValue = 7,
-    GetAssetValueCurrent = 8,
+    GetAssetInfo = 18,
+    GetAssetValue = 19,
+    GetAssetValueCurrent = 20,
     GetUserInfo = 9,
     GetUserPortfolio = 10,
     GetUserTransactionHist = 11,
+    GetUserLedger = 12,
+    GetUserOrders = 15,
+    GetUserValuation = 17,
+    SubscribeQuotes = 28,
+    UnsubscribeQuotes = 29,
+    QuoteUpdate = 30,
 }
 impl std::fmt::Display for DataTransferInst {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -37,4 +58,51 @@ impl std::fmt::Display for DataTransferInst {
 }
 
 #[allow(dead_code)]
-static INST_DATA_MAX_ID: isize = DataTransferInst::GetUserTransactionHist as isize;
+static INST_DATA_MAX_ID: isize = DataTransferInst::QuoteUpdate as isize;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_inst_namespace_unique() {
+        let mut insts = vec![
+            CommandInst::LoginMethod1 as i64,
+            CommandInst::LoginMethod2 as i64,
+            CommandInst::Register as i64,
+            CommandInst::PurchaseAsset as i64,
+            CommandInst::SellAsset as i64,
+            CommandInst::GenHashSalt as i64,
+            CommandInst::GetEmailSalt as i64,
+            CommandInst::GetPasswordSalt as i64,
+            CommandInst::PlaceOrder as i64,
+            CommandInst::CancelOrder as i64,
+            CommandInst::ClosePosition as i64,
+            CommandInst::Hello as i64,
+            CommandInst::Logout as i64,
+            CommandInst::LogoutAll as i64,
+            CommandInst::RefreshToken as i64,
+            CommandInst::ChangePassword as i64,
+            CommandInst::ChangeEmail as i64,
+            CommandInst::DeleteAccount as i64,
+            DataTransferInst::GetAssetInfo as i64,
+            DataTransferInst::GetAssetValue as i64,
+            DataTransferInst::GetAssetValueCurrent as i64,
+            DataTransferInst::GetUserInfo as i64,
+            DataTransferInst::GetUserPortfolio as i64,
+            DataTransferInst::GetUserTransactionHist as i64,
+            DataTransferInst::GetUserLedger as i64,
+            DataTransferInst::GetUserOrders as i64,
+            DataTransferInst::GetUserValuation as i64,
+            DataTransferInst::SubscribeQuotes as i64,
+            DataTransferInst::UnsubscribeQuotes as i64,
+            DataTransferInst::QuoteUpdate as i64,
+        ];
+        let count = insts.len();
+        insts.sort_unstable();
+        insts.dedup();
+        assert_eq!(insts.len(), count);
+        /* instruction 0 is reserved for error responses */
+        assert!(!insts.contains(&0));
+    }
+}
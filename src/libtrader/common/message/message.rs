@@ -1,7 +1,18 @@
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 use crate::common::message::message_type::MessageType;
 
+/// A protocol message.
+///
+/// Requests and their responses are single messages, with ```data_message_number``` and
+/// ```data_message_max``` set to ```0```. Stream frames, of type ```Stream```, are pushed by the
+/// server without a request:
+/// data_message_number - The sequence number of the frame in the server's stream, starting at
+/// ```1```. Gaps are frames the client was not subscribed to, or that were dropped because the
+/// client fell behind.
+/// data_message_max - The newest sequence number the server had published when the frame was
+/// sent. The difference to ```data_message_number``` is how far the client is behind.
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
 pub struct Message {
     pub msgtype: MessageType,
@@ -11,6 +22,14 @@ pub struct Message {
     pub data_message_max: usize,
     pub data: Vec<u8>,
 }
+impl Message {
+    /// Decodes the typed payload of the message.
+    ///
+    /// Returns: the payload on success, and the bincode error on a malformed payload.
+    pub fn payload<T: DeserializeOwned>(&self) -> bincode::Result<T> {
+        bincode::deserialize(&self.data)
+    }
+}
 impl std::fmt::Display for Message {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
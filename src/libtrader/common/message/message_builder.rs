@@ -1,5 +1,9 @@
+use serde::Serialize;
+
 use crate::common::message::message::Message;
 use crate::common::message::message_type::MessageType;
+use crate::common::message::response::ErrorResponse;
+use crate::common::misc::return_flags::ReturnFlags;
 
 pub fn message_builder(
     msg_type: MessageType,
@@ -18,3 +22,83 @@ pub fn message_builder(
     message.data = data;
     message
 }
+
+/// Builds a message carrying a typed request or response payload.
+///
+/// The payload is the only argument of the message, and is encoded with bincode.
+///
+/// Example:
+/// ```rust
+///     let message = payload_builder(
+///         MessageType::DataTransfer,
+///         DataTransferInst::GetUserPortfolio as i64,
+///         &AuthRequest { jwt },
+///     );
+/// ```
+pub fn payload_builder<T: Serialize>(msg_type: MessageType, inst: i64, payload: &T) -> Message {
+    message_builder(
+        msg_type,
+        inst,
+        1,
+        0,
+        0,
+        bincode::serialize(payload).unwrap(),
+    )
+}
+
+/// Builds a stream frame carrying a typed payload.
+///
+/// See ```Message``` for the meaning of the sequence numbers.
+///
+/// Arguments:
+/// inst - The instruction of the frame.
+/// sequence - The sequence number of the frame.
+/// latest - The newest sequence number published.
+/// payload - The payload of the frame.
+///
+/// Example:
+/// ```rust
+///     let frame = stream_builder(
+///         DataTransferInst::QuoteUpdate as i64,
+///         tick.sequence,
+///         latest,
+///         &QuoteUpdate { val },
+///     );
+/// ```
+pub fn stream_builder<T: Serialize>(
+    inst: i64,
+    sequence: usize,
+    latest: usize,
+    payload: &T,
+) -> Message {
+    message_builder(
+        MessageType::Stream,
+        inst,
+        1,
+        sequence,
+        latest,
+        bincode::serialize(payload).unwrap(),
+    )
+}
+
+/// Builds the error response to a failed request.
+///
+/// The response is a ```ServerReturn``` with instruction ```0```, carrying the flag and its
+/// human-readable message.
+///
+/// Example:
+/// ```rust
+///     tls_connection
+///         .send(error_builder(ReturnFlags::ServerAccUnauthorized))
+///         .await?;
+/// ```
+pub fn error_builder(flag: ReturnFlags) -> Message {
+    payload_builder(
+        MessageType::ServerReturn,
+        0,
+        &ErrorResponse {
+            message: flag.message().to_string(),
+            flag,
+        },
+    )
+}
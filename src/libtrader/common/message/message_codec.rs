@@ -0,0 +1,129 @@
+use std::io;
+
+use bytes::{Bytes, BytesMut};
+use futures::StreamExt;
+use tokio::io::AsyncRead;
+use tokio_util::codec::{Decoder, Encoder, Framed, LengthDelimitedCodec};
+
+use crate::common::message::message::Message;
+
+/// Maximum size of a single encoded message, 16 MiB.
+pub static MESSAGE_MAX_LENGTH: usize = 16 * 1024 * 1024;
+
+/// Frames messages on a byte stream.
+///
+/// Every message is sent as a 4 byte big endian length prefix followed by the bincode encoded
+/// ```Message```. Used by both the server and the client so that messages larger than one TLS
+/// record, or several messages in one read, are decoded correctly.
+///
+/// Example:
+/// ```rust
+///     let mut framed = Framed::new(tls_stream, MessageCodec::new());
+///     framed.send(message).await?;
+///     let response = read_message(&mut framed).await?;
+/// ```
+pub struct MessageCodec {
+    inner: LengthDelimitedCodec,
+}
+
+impl MessageCodec {
+    pub fn new() -> Self {
+        MessageCodec {
+            inner: LengthDelimitedCodec::builder()
+                .max_frame_length(MESSAGE_MAX_LENGTH)
+                .new_codec(),
+        }
+    }
+}
+
+impl Default for MessageCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for MessageCodec {
+    type Item = Message;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<Message>> {
+        match self.inner.decode(src)? {
+            Some(frame) => bincode::deserialize(&frame).map(Some).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("MESSAGE_CODEC_INVALID_MSG: {}", err),
+                )
+            }),
+            None => Ok(None),
+        }
+    }
+}
+
+impl Encoder<Message> for MessageCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: Message, dst: &mut BytesMut) -> io::Result<()> {
+        let data = bincode::serialize(&item).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("MESSAGE_CODEC_INVALID_MSG: {}", err),
+            )
+        })?;
+        self.inner.encode(Bytes::from(data), dst)
+    }
+}
+
+/// Reads the next message of a framed stream.
+///
+/// Arguments:
+/// framed - The framed stream to read from.
+///
+/// Returns: the message on success, ```io::ErrorKind::UnexpectedEof``` if the stream is closed,
+/// and ```io::ErrorKind::InvalidData``` if the message could not be decoded.
+///
+/// Example:
+/// ```rust
+///     let response = read_message(&mut socket).await?;
+/// ```
+pub async fn read_message<T>(framed: &mut Framed<T, MessageCodec>) -> io::Result<Message>
+where
+    T: AsyncRead + Unpin,
+{
+    framed
+        .next()
+        .await
+        .unwrap_or_else(|| Err(io::Error::from(io::ErrorKind::UnexpectedEof)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::common::message::message_builder::message_builder;
+    use crate::common::message::message_type::MessageType;
+
+    #[test]
+    fn test_message_codec_split_and_coalesced() {
+        let mut codec = MessageCodec::new();
+        let big = message_builder(MessageType::DataTransfer, 1, 1, 0, 1, vec![7u8; 100_000]);
+        let small = message_builder(MessageType::ServerReturn, 2, 0, 0, 0, Vec::new());
+
+        let mut encoded = BytesMut::new();
+        codec.encode(big, &mut encoded).unwrap();
+        codec.encode(small, &mut encoded).unwrap();
+
+        /* feed the bytes in small chunks */
+        let mut src = BytesMut::new();
+        let mut decoded = Vec::new();
+        for chunk in encoded.chunks(4096) {
+            src.extend_from_slice(chunk);
+            while let Some(message) = codec.decode(&mut src).unwrap() {
+                decoded.push(message);
+            }
+        }
+
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].data.len(), 100_000);
+        assert_eq!(decoded[1].instruction, 2);
+        assert!(src.is_empty());
+    }
+}
@@ -5,6 +5,7 @@ pub enum MessageType {
     Command = 0,
     DataTransfer = 1,
     ServerReturn = 2,
+    Stream = 3,
 }
 impl std::fmt::Display for MessageType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
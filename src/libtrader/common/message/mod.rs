@@ -1,4 +1,8 @@
+pub mod handshake;
 pub mod inst;
 pub mod message;
 pub mod message_builder;
+pub mod message_codec;
 pub mod message_type;
+pub mod request;
+pub mod response;
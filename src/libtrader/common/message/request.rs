@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::account::order::OrderType;
+
+/// Opens a connection, sent with ```Hello``` as the first message of every connection.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct HelloRequest {
+    pub min_version: u32,
+    pub max_version: u32,
+    pub capabilities: Vec<String>,
+}
+
+/// Requests a stored salt of a user, sent with ```GetEmailSalt``` and ```GetPasswordSalt```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct SaltRequest {
+    pub username: String,
+}
+
+/// Registers a new account, sent with ```Register```.
+///
+/// The hashes are the client side hashes of the email and password, which the server hashes
+/// again with its own salts.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct RegisterRequest {
+    pub username: String,
+    pub email_hash: Vec<u8>,
+    pub email_client_salt: Vec<u8>,
+    pub password_hash: Vec<u8>,
+    pub password_client_salt: Vec<u8>,
+}
+
+/// Logs in an account, sent with ```LoginMethod1```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct LoginRequest {
+    pub username: String,
+    pub hashed_email: Vec<u8>,
+    pub hashed_password: Vec<u8>,
+}
+
+/// Changes the password of an account, sent with ```ChangePassword```.
+///
+/// ```credentials``` proves the current email and password, the new password is hashed as in
+/// ```RegisterRequest``` with a new client salt.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct ChangePasswordRequest {
+    pub jwt: String,
+    pub credentials: LoginRequest,
+    pub password_hash: Vec<u8>,
+    pub password_client_salt: Vec<u8>,
+}
+
+/// Changes the email of an account, sent with ```ChangeEmail```.
+///
+/// ```credentials``` proves the current email and password, the new email is hashed as in
+/// ```RegisterRequest``` with a new client salt.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct ChangeEmailRequest {
+    pub jwt: String,
+    pub credentials: LoginRequest,
+    pub email_hash: Vec<u8>,
+    pub email_client_salt: Vec<u8>,
+}
+
+/// Deletes an account along with its portfolio and sessions, sent with ```DeleteAccount```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct DeleteAccountRequest {
+    pub jwt: String,
+    pub credentials: LoginRequest,
+}
+
+/// Exchanges a refresh token for a new pair of tokens, sent with ```RefreshToken```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+/// Carries only the session token, sent with the account data retrieval instructions.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct AuthRequest {
+    pub jwt: String,
+}
+
+/// Executes a market order, sent with ```PurchaseAsset``` and ```SellAsset```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct PurchaseAssetRequest {
+    pub jwt: String,
+    pub symbol: String,
+    pub shares_amount: i64,
+}
+
+/// Places a working order, sent with ```PlaceOrder```.
+///
+/// ```limit_price``` is ignored by stop orders, and ```stop_price``` by limit orders.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct PlaceOrderRequest {
+    pub jwt: String,
+    pub symbol: String,
+    pub order_type: OrderType,
+    pub is_buy: bool,
+    pub shares_amount: i64,
+    pub limit_price: f64,
+    pub stop_price: f64,
+}
+
+/// Cancels a working order, sent with ```CancelOrder```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct CancelOrderRequest {
+    pub jwt: String,
+    pub order_id: i64,
+}
+
+/// Closes part or all of an open position, sent with ```ClosePosition```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct ClosePositionRequest {
+    pub jwt: String,
+    pub position_id: i64,
+    pub shares_amount: i64,
+}
+
+/// Requests the company listed under a symbol, sent with ```GetAssetInfo```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct AssetInfoRequest {
+    pub symbol: String,
+}
+
+/// The most quotes returned for one ```AssetValueRequest```, so that the response fits in a
+/// message.
+pub static MAX_ASSET_VALUE_ROWS: i64 = 10_000;
+
+/// Requests the quotes of a symbol between two epochs, sent with ```GetAssetValue```.
+///
+/// At most ```MAX_ASSET_VALUE_ROWS``` quotes are returned, the oldest first.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct AssetValueRequest {
+    pub symbol: String,
+    pub start_epoch: i64,
+    pub end_epoch: i64,
+}
+
+/// Requests the latest quote of a symbol, sent with ```GetAssetValueCurrent```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct AssetValueCurrentRequest {
+    pub symbol: String,
+}
+
+/// Adds or removes symbols from the quote stream of the connection, sent with
+/// ```SubscribeQuotes``` and ```UnsubscribeQuotes```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct QuoteSubscriptionRequest {
+    pub symbols: Vec<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::common::message::message_builder::{message_builder, payload_builder};
+    use crate::common::message::message_type::MessageType;
+
+    #[test]
+    fn test_request_payload() {
+        let request = PlaceOrderRequest {
+            jwt: "token".into(),
+            symbol: "AAPL".into(),
+            order_type: OrderType::StopLimit,
+            is_buy: true,
+            shares_amount: 10,
+            limit_price: 120.0,
+            stop_price: 115.0,
+        };
+        let message = payload_builder(MessageType::Command, 13, &request);
+        assert_eq!(message.argument_count, 1);
+        assert_eq!(message.payload::<PlaceOrderRequest>().unwrap(), request);
+
+        /* a malformed payload is an error, not a panic */
+        let message = message_builder(MessageType::Command, 13, 1, 0, 0, vec![1, 2, 3]);
+        assert!(message.payload::<PlaceOrderRequest>().is_err());
+    }
+}
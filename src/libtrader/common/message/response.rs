@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::account::ledger::Ledger;
+use crate::common::account::order::Order;
+use crate::common::account::portfolio::Portfolio;
+use crate::common::account::position::Position;
+use crate::common::account::transaction::Transaction;
+use crate::common::account::valuation::Valuation;
+use crate::common::generic::company::Company;
+use crate::common::generic::stock_val::StockVal;
+use crate::common::misc::return_flags::ReturnFlags;
+
+/// The negotiated protocol version and capabilities, answering ```Hello```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct HelloResponse {
+    pub version: u32,
+    pub capabilities: Vec<String>,
+}
+
+/// A generated or stored salt, answering the salt instructions.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct SaltResponse {
+    pub salt: Vec<u8>,
+}
+
+/// The session tokens of a logged in account, answering ```LoginMethod1``` and
+/// ```RefreshToken```.
+///
+/// ```jwt``` authorizes requests until it expires, ```refresh_token``` can be exchanged once for
+/// a new pair of tokens with ```RefreshToken```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct LoginResponse {
+    pub jwt: String,
+    pub refresh_token: String,
+}
+
+/// The position opened or closed, answering ```PurchaseAsset```, ```SellAsset``` and
+/// ```ClosePosition```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct PositionResponse {
+    pub position: Position,
+}
+
+/// The stored order, answering ```PlaceOrder```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct PlaceOrderResponse {
+    pub order: Order,
+}
+
+/// Answers ```GetUserPortfolio```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct PortfolioResponse {
+    pub portfolio: Portfolio,
+}
+
+/// Answers ```GetUserTransactionHist```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct TransactionsResponse {
+    pub transactions: Vec<Transaction>,
+}
+
+/// Answers ```GetUserLedger```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct LedgerResponse {
+    pub ledger: Ledger,
+}
+
+/// Answers ```GetUserOrders```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct OrdersResponse {
+    pub orders: Vec<Order>,
+}
+
+/// Answers ```GetUserValuation```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct ValuationResponse {
+    pub valuation: Valuation,
+}
+
+/// Answers ```GetAssetInfo```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct AssetInfoResponse {
+    pub company: Company,
+}
+
+/// Answers ```GetAssetValue```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct AssetValueResponse {
+    pub vals: Vec<StockVal>,
+}
+
+/// Answers ```GetAssetValueCurrent```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct AssetValueCurrentResponse {
+    pub val: StockVal,
+}
+
+/// The symbols streamed to the connection, answering ```SubscribeQuotes``` and
+/// ```UnsubscribeQuotes```.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct QuoteSubscriptionResponse {
+    pub symbols: Vec<String>,
+}
+
+/// A new quote of a subscribed symbol, pushed in a ```QuoteUpdate``` stream frame.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct QuoteUpdate {
+    pub val: StockVal,
+}
+
+/// The reason a request failed, answering any instruction with instruction ```0```.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct ErrorResponse {
+    pub flag: ReturnFlags,
+    pub message: String,
+}
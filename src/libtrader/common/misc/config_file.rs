@@ -0,0 +1,82 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Reads a TOML configuration file.
+///
+/// Arguments:
+/// path - Path to the configuration file.
+///
+/// Returns: the configuration on success, and the reason of failure on error, with the line
+/// and column of invalid TOML or of unknown and invalid keys.
+///
+/// Example:
+/// ```rust
+///     let options: ClientOptions = read_config_file(Path::new("client.toml"))?;
+/// ```
+pub fn read_config_file<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+    toml::from_str(&contents).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), err.to_string().trim_end()),
+        )
+    })
+}
+
+/// A setting of a configuration file, written as a string or as a TOML number or boolean.
+///
+/// Settings are validated as text, the same way as the environment variables overriding
+/// them, so ```db_host_port = 5432``` and ```db_host_port = "5432"``` are the same.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ConfigValue {
+    Text(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+}
+
+impl fmt::Display for ConfigValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigValue::Text(text) => write!(f, "{}", text),
+            ConfigValue::Integer(integer) => write!(f, "{}", integer),
+            ConfigValue::Float(float) => write!(f, "{}", float),
+            ConfigValue::Boolean(boolean) => write!(f, "{}", boolean),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(PartialEq, Debug, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Settings {
+        server_addr: Option<ConfigValue>,
+        port: Option<ConfigValue>,
+    }
+
+    #[test]
+    fn test_config_file_toml() {
+        let settings: Settings = toml::from_str(
+            "# sandbox\nserver_addr = \"127.0.0.1:4000 # not a comment\" # a comment\nport = 4000\n",
+        )
+        .unwrap();
+        assert_eq!(
+            settings.server_addr.unwrap().to_string(),
+            "127.0.0.1:4000 # not a comment"
+        );
+        assert_eq!(settings.port.unwrap().to_string(), "4000");
+
+        let err = toml::from_str::<Settings>("port = 4000\nnonsense = \"a\"\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{}", err);
+    }
+}
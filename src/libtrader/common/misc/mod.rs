@@ -1,3 +1,3 @@
 pub mod assert_msg;
+pub mod config_file;
 pub mod return_flags;
-pub mod servers_pool;
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Debug, Serialize, Deserialize)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum ReturnFlags {
     LibtraderInitClientConnect = 1,
     LibtraderInitLogFailed = 2,
@@ -67,7 +67,220 @@ pub enum ReturnFlags {
 
     ClientTlsReadError = 47,
     ClientWaitAndReadBranched = 48,
+
+    ServerPurchaseAssetFailed = 49,
+    ClientAccPurchaseAssetError = 50,
+
+    ServerAccInsufficientFunds = 51,
+    ServerDbCreateLedgerEntryFailed = 52,
+    ServerRetrieveLedgerFailed = 53,
+    ServerRetrieveLedgerInvMsg = 54,
+    ClientAccRetrieveLedgerError = 55,
+
+    ServerDbCreateOrderFailed = 56,
+    ServerDbUpdateOrderFailed = 57,
+    ServerDbSearchOrderNotFound = 58,
+    ServerPlaceOrderInvMsg = 59,
+    ServerPlaceOrderFailed = 60,
+    ServerCancelOrderInvMsg = 61,
+    ServerCancelOrderFailed = 62,
+    ServerRetrieveOrdersInvMsg = 63,
+    ServerRetrieveOrdersFailed = 64,
+    ClientAccPlaceOrderError = 65,
+    ClientAccCancelOrderError = 66,
+    ClientAccRetrieveOrdersError = 67,
+
+    ServerDbSearchPositionNotFound = 68,
+    ServerDbUpdatePositionFailed = 69,
+    ServerClosePositionInvMsg = 70,
+    ServerClosePositionFailed = 71,
+    ClientAccClosePositionError = 72,
+
+    ServerRetrieveValuationInvMsg = 73,
+    ServerRetrieveValuationFailed = 74,
+    ClientAccRetrieveValuationError = 75,
+
+    ClientGetAssetInfoError = 76,
+    ClientGetAssetValueError = 77,
+
+    ServerDbMigrationFailed = 78,
+    ServerDbSchemaAhead = 79,
+
+    ServerAccTokenExpired = 80,
+
+    ServerProtocolUpgradeRequired = 81,
+    ServerHandshakeInvMsg = 82,
+    ClientHandshakeFailed = 83,
+    ServerDbCreateSessionFailed = 84,
+    ServerDbGetSessionFailed = 85,
+    ServerDbUpdateSessionFailed = 86,
+    ServerDbSearchSessionNotFound = 87,
+    ServerAccSessionRevoked = 88,
+    ServerLogoutInvMsg = 89,
+    ServerLogoutFailed = 90,
+    ClientAccLogoutError = 91,
+    ServerDbCreateRefreshTokenFailed = 92,
+    ServerDbUpdateRefreshTokenFailed = 93,
+    ServerAccRefreshTokenExpired = 94,
+    ServerAccRefreshTokenReused = 95,
+    ServerRefreshTokenInvMsg = 96,
+    ClientAccRefreshTokenError = 97,
+    ServerDbGetRefreshTokenFailed = 98,
+    ServerChangePasswordInvMsg = 99,
+    ServerChangePasswordFailed = 100,
+    ServerChangeEmailInvMsg = 101,
+    ServerChangeEmailFailed = 102,
+    ServerDeleteAccountInvMsg = 103,
+    ServerDeleteAccountFailed = 104,
+    ClientAccChangePasswordError = 105,
+    ClientAccChangeEmailError = 106,
+    ClientAccDeleteError = 107,
+    ServerAccLoginThrottled = 108,
+    ServerAccLocked = 109,
+    ServerMarketSourceFailed = 110,
+    ServerSubscribeQuotesInvMsg = 111,
+    ServerSubscribeQuotesTooMany = 112,
+    ClientSubscribeQuotesError = 113,
+    ClientQuoteStreamError = 114,
+    CommonCapabilityNotNegotiated = 115,
 }
+impl ReturnFlags {
+    /// A human-readable description of the flag, fit to show to a user.
+    pub fn message(&self) -> &'static str {
+        match self {
+            ReturnFlags::LibtraderInitClientConnect => "could not connect to the server",
+            ReturnFlags::LibtraderInitLogFailed => "could not initialize logging",
+            ReturnFlags::LibtraderInitFailed => "could not initialize libtrader",
+            ReturnFlags::CommonGenLogDirCreationFailed => "could not create the log directory",
+            ReturnFlags::CommonTlsBadConfig => "invalid TLS configuration",
+            ReturnFlags::CommonGetCompanyFailed => "could not retrieve the company",
+            ReturnFlags::CommonGetStockFailed => "could not retrieve the stock",
+            ReturnFlags::ServerDbConnectFailed => "the server could not reach its database",
+            ReturnFlags::ServerDbWriteFailed => "the server could not write to its database",
+            ReturnFlags::ServerDbUserHashNotFound => "invalid username or password",
+            ReturnFlags::ServerDbUserSaltNotFound => "invalid username or password",
+            ReturnFlags::ServerDbCreateTransactionFailed => "could not record the transaction",
+            ReturnFlags::ServerDbCreatePositionFailed => "could not record the position",
+            ReturnFlags::ServerDbCreateStockFailed => "could not record the stock value",
+            ReturnFlags::ServerDbCreateCompanyFailed => "could not record the company",
+            ReturnFlags::ServerDbSearchStockNotFound => "no such stock",
+            ReturnFlags::ServerDbSearchCompanyNotFound => "no such company",
+            ReturnFlags::ServerRegisterInvMsg => "invalid registration request",
+            ReturnFlags::ServerLoginInvMsg => "invalid login request",
+            ReturnFlags::ServerPurchaseAssetInvMsg => "invalid purchase request",
+            ReturnFlags::ServerAccUnauthorized => "not authorized",
+            ReturnFlags::ServerAccUserExists => {
+                "an account with this username or email already exists"
+            }
+            ReturnFlags::ServerGetAssetDataInvMsg => "invalid asset value request",
+            ReturnFlags::ServerGetAssetInfoInvMsg => "invalid asset info request",
+            ReturnFlags::ServerGetUserIdNotFound => "no such user",
+            ReturnFlags::ServerRetrieveTransactionFailed => {
+                "could not retrieve the transaction history"
+            }
+            ReturnFlags::ServerRetrieveTransactionInvMsg => "invalid transaction history request",
+            ReturnFlags::ServerRetrievePortfolioFailed => "could not retrieve the portfolio",
+            ReturnFlags::ServerRetrievePortfolioInvMsg => "invalid portfolio request",
+            ReturnFlags::ServerCreateJwtTokenFailed => "could not create a session token",
+            ReturnFlags::ServerTlsConnWriteFailed => "could not write to the connection",
+            ReturnFlags::ServerTlsConnProcessFailed => "could not process the connection",
+            ReturnFlags::ServerTlsConnReadPlainFailed => "could not read from the connection",
+            ReturnFlags::ServerTlsServerAcceptFailed => "could not accept the connection",
+            ReturnFlags::ServerHandleDataRcvdInvMsg => "invalid request",
+            ReturnFlags::ClientAccRetrievePortfolioError => "could not retrieve the portfolio",
+            ReturnFlags::ClientAccRetrieveTransactionError => {
+                "could not retrieve the transaction history"
+            }
+            ReturnFlags::ClientAccCreationFailed => "could not create the account",
+            ReturnFlags::ClientAccInvalidSessionId => "invalid session",
+            ReturnFlags::ClientAccUnauthorized => "not logged in",
+            ReturnFlags::ClientReqSaltFailed => "could not request the salt",
+            ReturnFlags::ClientReqSaltInvMsg => "invalid salt response",
+            ReturnFlags::ClientReqSaltInvMsgRetSize => "invalid salt response size",
+            ReturnFlags::ClientReqSaltInvMsgInst => "invalid salt response instruction",
+            ReturnFlags::ClientReqSaltRej => "the salt request was rejected",
+            ReturnFlags::ClientGenSaltFailed => "could not generate a salt",
+            ReturnFlags::ClientTlsReadError => "could not read from the connection",
+            ReturnFlags::ClientWaitAndReadBranched => "unexpected response",
+            ReturnFlags::ServerPurchaseAssetFailed => "could not complete the purchase",
+            ReturnFlags::ClientAccPurchaseAssetError => "could not complete the purchase",
+            ReturnFlags::ServerAccInsufficientFunds => "insufficient funds",
+            ReturnFlags::ServerDbCreateLedgerEntryFailed => "could not record the ledger entry",
+            ReturnFlags::ServerRetrieveLedgerFailed => "could not retrieve the ledger",
+            ReturnFlags::ServerRetrieveLedgerInvMsg => "invalid ledger request",
+            ReturnFlags::ClientAccRetrieveLedgerError => "could not retrieve the ledger",
+            ReturnFlags::ServerDbCreateOrderFailed => "could not record the order",
+            ReturnFlags::ServerDbUpdateOrderFailed => "could not update the order",
+            ReturnFlags::ServerDbSearchOrderNotFound => "no such order",
+            ReturnFlags::ServerPlaceOrderInvMsg => "invalid order",
+            ReturnFlags::ServerPlaceOrderFailed => "could not place the order",
+            ReturnFlags::ServerCancelOrderInvMsg => "invalid cancel request",
+            ReturnFlags::ServerCancelOrderFailed => "could not cancel the order",
+            ReturnFlags::ServerRetrieveOrdersInvMsg => "invalid orders request",
+            ReturnFlags::ServerRetrieveOrdersFailed => "could not retrieve the orders",
+            ReturnFlags::ClientAccPlaceOrderError => "could not place the order",
+            ReturnFlags::ClientAccCancelOrderError => "could not cancel the order",
+            ReturnFlags::ClientAccRetrieveOrdersError => "could not retrieve the orders",
+            ReturnFlags::ServerDbSearchPositionNotFound => "no such position",
+            ReturnFlags::ServerDbUpdatePositionFailed => "could not update the position",
+            ReturnFlags::ServerClosePositionInvMsg => "invalid close position request",
+            ReturnFlags::ServerClosePositionFailed => "could not close the position",
+            ReturnFlags::ClientAccClosePositionError => "could not close the position",
+            ReturnFlags::ServerRetrieveValuationInvMsg => "invalid valuation request",
+            ReturnFlags::ServerRetrieveValuationFailed => "could not retrieve the valuation",
+            ReturnFlags::ClientAccRetrieveValuationError => "could not retrieve the valuation",
+            ReturnFlags::ClientGetAssetInfoError => "could not retrieve the asset info",
+            ReturnFlags::ClientGetAssetValueError => "could not retrieve the asset value",
+            ReturnFlags::ServerDbMigrationFailed => "could not migrate the database schema",
+            ReturnFlags::ServerDbSchemaAhead => "the database schema is newer than the server",
+            ReturnFlags::ServerAccTokenExpired => "the session has expired",
+            ReturnFlags::ServerProtocolUpgradeRequired => {
+                "the client protocol version is not supported, upgrade required"
+            }
+            ReturnFlags::ServerHandshakeInvMsg => "invalid handshake",
+            ReturnFlags::ClientHandshakeFailed => "could not negotiate the protocol",
+            ReturnFlags::ServerDbCreateSessionFailed => "could not record the session",
+            ReturnFlags::ServerDbGetSessionFailed => "could not retrieve the session",
+            ReturnFlags::ServerDbUpdateSessionFailed => "could not update the session",
+            ReturnFlags::ServerDbSearchSessionNotFound => "no such session",
+            ReturnFlags::ServerAccSessionRevoked => "the session has been logged out",
+            ReturnFlags::ServerLogoutInvMsg => "invalid logout request",
+            ReturnFlags::ServerLogoutFailed => "could not log out",
+            ReturnFlags::ClientAccLogoutError => "could not log out",
+            ReturnFlags::ServerDbCreateRefreshTokenFailed => "could not record the refresh token",
+            ReturnFlags::ServerDbUpdateRefreshTokenFailed => "could not update the refresh token",
+            ReturnFlags::ServerAccRefreshTokenExpired => "the refresh token has expired",
+            ReturnFlags::ServerAccRefreshTokenReused => {
+                "the refresh token was already used, every session it issued is logged out"
+            }
+            ReturnFlags::ServerRefreshTokenInvMsg => "invalid refresh request",
+            ReturnFlags::ClientAccRefreshTokenError => "could not refresh the session",
+            ReturnFlags::ServerDbGetRefreshTokenFailed => "could not retrieve the refresh token",
+            ReturnFlags::ServerChangePasswordInvMsg => "invalid password change request",
+            ReturnFlags::ServerChangePasswordFailed => "could not change the password",
+            ReturnFlags::ServerChangeEmailInvMsg => "invalid email change request",
+            ReturnFlags::ServerChangeEmailFailed => "could not change the email",
+            ReturnFlags::ServerDeleteAccountInvMsg => "invalid account deletion request",
+            ReturnFlags::ServerDeleteAccountFailed => "could not delete the account",
+            ReturnFlags::ClientAccChangePasswordError => "could not change the password",
+            ReturnFlags::ClientAccChangeEmailError => "could not change the email",
+            ReturnFlags::ClientAccDeleteError => "could not delete the account",
+            ReturnFlags::ServerAccLoginThrottled => "too many failed logins, try again later",
+            ReturnFlags::ServerAccLocked => "too many failed logins, logins are locked for a while",
+            ReturnFlags::ServerMarketSourceFailed => {
+                "could not read quotes from the market data source"
+            }
+            ReturnFlags::ServerSubscribeQuotesInvMsg => "invalid quote subscription request",
+            ReturnFlags::ServerSubscribeQuotesTooMany => "too many symbols subscribed",
+            ReturnFlags::ClientSubscribeQuotesError => "could not change the quote subscription",
+            ReturnFlags::ClientQuoteStreamError => "could not read the quote stream",
+            ReturnFlags::CommonCapabilityNotNegotiated => {
+                "the feature was not negotiated with the server"
+            }
+        }
+    }
+}
+
 impl std::fmt::Display for ReturnFlags {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:#?}", self)
@@ -1,2 +0,0 @@
-pub static SERVERS_IPS: [&str; 2] = ["", ""];
-pub static SERVERS_CERTS: [&str; 2] = ["", ""];
@@ -5,8 +5,12 @@ use serde::{Deserialize, Serialize};
 /// Members:
 /// user_id - The user id that is authorized.
 /// exp - The unix epoch at which this claim expires.
+/// iat - The unix epoch at which this claim was issued.
+/// jti - The unique id of the token, in hex.
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct JWTClaim {
     pub user_id: i64,
     pub exp: u64,
+    pub iat: u64,
+    pub jti: String,
 }
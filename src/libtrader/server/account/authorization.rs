@@ -1,140 +1,247 @@
 use data_encoding::HEXUPPER;
-use ring::pbkdf2;
+use log::warn;
+use ring::{digest, pbkdf2};
 use std::num::NonZeroU32;
+use std::sync::{LazyLock, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::common::message::message::Message;
-use crate::common::message::message_builder::message_builder;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
 use crate::common::message::message_type::MessageType;
+use crate::common::message::request::LoginRequest;
 use crate::common::misc::return_flags::ReturnFlags;
+use crate::common::sessions::jwt_claim::JWTClaim;
 
 use crate::server::db::cmd::get_user_hash::get_user_hash;
 use crate::server::db::cmd::get_user_id::get_user_id;
 use crate::server::db::cmd::get_user_salt::get_user_salt;
+use crate::server::ds::login_throttle::LoginThrottle;
+use crate::server::initializer::IP;
 
-use crate::server::network::jwt_wrapper::create_jwt_token;
+use crate::server::account::session::{acc_create_session, verify_session};
 
-use tokio::io::AsyncWriteExt;
+use futures::SinkExt;
 use tokio::net::TcpStream;
 use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+/// The failed logins of every username and client address.
+static LOGIN_THROTTLE: LazyLock<Mutex<LoginThrottle>> =
+    LazyLock::new(|| Mutex::new(LoginThrottle::default()));
 
 pub async fn acc_auth(
     sql_conn: &tokio_postgres::Client,
-    tls_connection: &mut TlsStream<TcpStream>,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
     message: &Message,
 ) -> Result<(), ReturnFlags> {
     /*
      * Parse account data.
      * */
-    /* get json data */
-    let stringified_data = std::str::from_utf8(&message.data).unwrap();
-    let data = json::parse(&stringified_data).unwrap();
-    /* get email, password, and username hashes */
-    let email_hash = HEXUPPER
-        .decode(data["hashed_email"].as_str().unwrap().as_bytes())
-        .unwrap();
-    let password_hash = HEXUPPER
-        .decode(data["hashed_password"].as_str().unwrap().as_bytes())
-        .unwrap();
-    let username = data["username"].as_str().unwrap();
+    let request: LoginRequest = message
+        .payload()
+        .map_err(|_| ReturnFlags::ServerLoginInvMsg)?;
 
-    /*
-     * Get server salts
-     * */
-    let email_salt = HEXUPPER
-        .decode(
-            get_user_salt(sql_conn, username, true, true)
-                .await
-                .unwrap()
-                .as_bytes(),
-        )
-        .unwrap();
-    let password_salt = HEXUPPER
-        .decode(
-            get_user_salt(sql_conn, username, false, true)
-                .await
-                .unwrap()
-                .as_bytes(),
-        )
-        .unwrap();
+    /* verify creds */
+    let user_id = verify_credentials(sql_conn, &request).await?;
 
     /*
-     * Get server hashes
+     * Generate JWT token
      * */
-    let email_db = HEXUPPER
-        .decode(
-            get_user_hash(sql_conn, username, true)
-                .await
-                .unwrap()
-                .as_bytes(),
-        )
-        .unwrap();
-    let password_db = HEXUPPER
-        .decode(
-            get_user_hash(sql_conn, username, false)
-                .await
-                .unwrap()
-                .as_bytes(),
-        )
-        .unwrap();
+    /* gen the actual tokens, recording their session */
+    let tokens = acc_create_session(sql_conn, user_id).await?;
 
     /*
-     * Verify creds
+     * Send the JWT and refresh tokens
      * */
-    let email_ret = pbkdf2::verify(
-        pbkdf2::PBKDF2_HMAC_SHA512,
-        NonZeroU32::new(350_000).unwrap(),
-        &email_salt,
-        &email_hash,
-        &email_db,
-    );
-    match email_ret.is_ok() {
-        true => {}
-        false => return Err(ReturnFlags::ServerAccUnauthorized),
-    };
-    let pass_ret = pbkdf2::verify(
-        pbkdf2::PBKDF2_HMAC_SHA512,
-        NonZeroU32::new(500_000).unwrap(),
-        &password_salt,
-        &password_hash,
-        &password_db,
-    );
-    match pass_ret.is_ok() {
-        true => {}
-        false => return Err(ReturnFlags::ServerAccUnauthorized),
-    };
+    let message = payload_builder(MessageType::ServerReturn, 1, &tokens);
+    tls_connection
+        .send(message)
+        .await
+        .map_err(|_| ReturnFlags::ServerTlsConnWriteFailed)?;
+
+    Ok(())
+}
+
+/// A login attempt started in ```LOGIN_THROTTLE``` for every key, released if it is dropped
+/// before it ends.
+struct LoginAttempt {
+    keys: Vec<String>,
+    is_ended: bool,
+}
+
+impl LoginAttempt {
+    /// Starts the attempt of every key, the attempt counts as failed until it ends.
+    fn begin(keys: Vec<String>, now: u64) -> Result<LoginAttempt, ReturnFlags> {
+        let mut throttle = LOGIN_THROTTLE.lock().unwrap();
+        for (started, key) in keys.iter().enumerate() {
+            if let Err(err) = throttle.begin(key, now) {
+                warn!("VERIFY_CREDENTIALS_THROTTLED: {}", key);
+                for key in &keys[..started] {
+                    throttle.release(key);
+                }
+                return Err(err);
+            }
+        }
+        Ok(LoginAttempt {
+            keys,
+            is_ended: false,
+        })
+    }
+
+    /// Ends the attempt with the result of the verification.
+    fn end(mut self, result: &Result<i64, ReturnFlags>, now: u64) {
+        let mut throttle = LOGIN_THROTTLE.lock().unwrap();
+        for key in &self.keys {
+            match result {
+                Ok(_) if key.starts_with("user:") => throttle.success(key),
+                Err(ReturnFlags::ServerAccUnauthorized) => throttle.failure(key, now),
+                _ => throttle.release(key),
+            }
+        }
+        self.is_ended = true;
+    }
+}
+
+impl Drop for LoginAttempt {
+    fn drop(&mut self) {
+        if !self.is_ended {
+            let mut throttle = LOGIN_THROTTLE.lock().unwrap();
+            for key in &self.keys {
+                throttle.release(key);
+            }
+        }
+    }
+}
+
+/// Verifies the credentials of an account.
+///
+/// Hashes the client hashes of the email and password again with the server salts, and compares
+/// them to the stored hashes. Failed attempts are throttled per username and per client
+/// address, see ```LoginThrottle```.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// request - The username and the client hashes of the email and password.
+///
+/// Returns: the id of the account on success, ```ServerAccUnauthorized``` if the credentials do
+/// not match, ```ServerAccLoginThrottled``` or ```ServerAccLocked``` after too many failures.
+///
+/// Example:
+/// ```rust
+///     let user_id = verify_credentials(sql_conn, &request.credentials).await?;
+/// ```
+pub async fn verify_credentials(
+    sql_conn: &tokio_postgres::Client,
+    request: &LoginRequest,
+) -> Result<i64, ReturnFlags> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let mut keys = vec![format!("user:{}", request.username)];
+    if let Ok(addr) = IP.try_with(|addr| addr.ip()) {
+        keys.push(format!("ip:{}", addr));
+    }
+
+    let attempt = LoginAttempt::begin(keys, now)?;
+    let result = check_credentials(sql_conn, request).await;
+    attempt.end(&result, now);
+    result
+}
+
+/// Compares the credentials of an account to the stored hashes.
+async fn check_credentials(
+    sql_conn: &tokio_postgres::Client,
+    request: &LoginRequest,
+) -> Result<i64, ReturnFlags> {
+    let username = request.username.as_str();
 
     /*
-     * Generate JWT token
+     * Get server salts and hashes
      * */
-    /* get user id*/
-    let user_id = get_user_id(sql_conn, username).await?;
-
-    /* gen the actual token */
-    use std::time::{Duration, SystemTime, UNIX_EPOCH};
-    let beginning_of_time = SystemTime::now() + Duration::from_secs(4 * 60 * 60);
-    let jwt_token = create_jwt_token(
-        user_id,
-        beginning_of_time
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs(),
-    )?;
+    let (email_salt, password_salt, email_db, password_db) =
+        match stored_credentials(sql_conn, username).await {
+            Ok(stored) => stored,
+            Err(_) => {
+                /* hash anyway, so that unknown usernames take as long as wrong passwords */
+                let zeros = vec![0u8; digest::SHA512_OUTPUT_LEN];
+                (zeros.clone(), zeros.clone(), zeros.clone(), zeros)
+            }
+        };
 
     /*
-     * Send the JWT token
+     * Verify creds, off the async workers since the hashing takes a while
      * */
-    let message = message_builder(
-        MessageType::ServerReturn,
-        1,
-        1,
-        0,
-        0,
-        jwt_token.as_bytes().to_vec(),
-    );
-    tls_connection
-        .write_all(bincode::serialize(&message).unwrap().as_slice())
-        .await
-        .expect("could not write to client");
+    let hashed_email = request.hashed_email.clone();
+    let hashed_password = request.hashed_password.clone();
+    let is_verified = tokio::task::spawn_blocking(move || {
+        pbkdf2::verify(
+            pbkdf2::PBKDF2_HMAC_SHA512,
+            NonZeroU32::new(350_000).unwrap(),
+            &email_salt,
+            &hashed_email,
+            &email_db,
+        )
+        .is_ok()
+            && pbkdf2::verify(
+                pbkdf2::PBKDF2_HMAC_SHA512,
+                NonZeroU32::new(500_000).unwrap(),
+                &password_salt,
+                &hashed_password,
+                &password_db,
+            )
+            .is_ok()
+    })
+    .await
+    .unwrap_or(false);
+    if !is_verified {
+        return Err(ReturnFlags::ServerAccUnauthorized);
+    }
 
-    Ok(())
+    get_user_id(sql_conn, username).await
+}
+
+/// Returns the server salts and hashes of the email and password of an account.
+async fn stored_credentials(
+    sql_conn: &tokio_postgres::Client,
+    username: &str,
+) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>), ReturnFlags> {
+    let decode = |hex: String| {
+        HEXUPPER
+            .decode(hex.as_bytes())
+            .map_err(|_| ReturnFlags::ServerAccUnauthorized)
+    };
+    Ok((
+        decode(get_user_salt(sql_conn, username, true, true).await?)?,
+        decode(get_user_salt(sql_conn, username, false, true).await?)?,
+        decode(get_user_hash(sql_conn, username, true).await?)?,
+        decode(get_user_hash(sql_conn, username, false).await?)?,
+    ))
+}
+
+/// Authorizes a sensitive request with both a session token and the current credentials.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// jwt - The JWT token of the session.
+/// credentials - The username and the client hashes of the current email and password.
+///
+/// Returns: the JWTClaim on success, ```ServerAccUnauthorized``` if the credentials do not match
+/// the account of the session.
+///
+/// Example:
+/// ```rust
+///     let claim = reauthorize(sql_conn, request.jwt, &request.credentials).await?;
+/// ```
+pub async fn reauthorize(
+    sql_conn: &tokio_postgres::Client,
+    jwt: String,
+    credentials: &LoginRequest,
+) -> Result<JWTClaim, ReturnFlags> {
+    let claim = verify_session(sql_conn, jwt).await?;
+    if verify_credentials(sql_conn, credentials).await? != claim.user_id {
+        return Err(ReturnFlags::ServerAccUnauthorized);
+    }
+    Ok(claim)
 }
@@ -0,0 +1,67 @@
+use log::warn;
+
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::message_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::CancelOrderRequest;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::session::verify_session;
+use crate::server::config::server_config;
+use crate::server::db::cmd::update_order::cancel_order;
+use crate::server::db::initializer::db_connect;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Cancels a working order of an authorized user.
+///
+/// Shares that were filled before the cancellation stay filled.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// tls_connection - The TLS connection to respond on.
+/// message - The cancel message, containing the JWT token and the order id.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+pub async fn acc_cancel_order(
+    sql_conn: &tokio_postgres::Client,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> Result<(), ReturnFlags> {
+    /*
+     * Parse cancel data.
+     * */
+    let CancelOrderRequest { jwt, order_id } = message
+        .payload()
+        .map_err(|_| ReturnFlags::ServerCancelOrderInvMsg)?;
+
+    /* verify JWT token */
+    let token = match verify_session(sql_conn, jwt).await {
+        Ok(token) => token,
+        Err(err) => {
+            warn!("ACC_CANCEL_ORDER_UNAUTH_TOKEN");
+            return Err(err);
+        }
+    };
+
+    /* connect to SQL database using user ```portfolio_schema_user``` */
+    let sql_conn = db_connect(&server_config().db_portfolio)
+        .await
+        .map_err(|_| ReturnFlags::ServerDbConnectFailed)?;
+
+    /* cancel the order */
+    cancel_order(&sql_conn, token.user_id, order_id).await?;
+
+    /* confirm the cancellation */
+    let message = message_builder(MessageType::ServerReturn, 1, 0, 0, 0, Vec::new());
+    tls_connection
+        .send(message)
+        .await
+        .map_err(|_| ReturnFlags::ServerTlsConnWriteFailed)?;
+
+    Ok(())
+}
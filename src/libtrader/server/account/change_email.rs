@@ -0,0 +1,69 @@
+use data_encoding::HEXUPPER;
+use log::info;
+
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::message_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::ChangeEmailRequest;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::authorization::reauthorize;
+use crate::server::account::hash_email::hash_email;
+use crate::server::db::cmd::update_account::update_email;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Changes the email of an authorized user.
+///
+/// The new client hash is hashed again with a new server salt, and both salts replace the old
+/// ones.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// tls_connection - The TLS connection to respond on.
+/// message - The email change message.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+pub async fn acc_change_email(
+    sql_conn: &tokio_postgres::Client,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> Result<(), ReturnFlags> {
+    let request: ChangeEmailRequest = message
+        .payload()
+        .map_err(|_| ReturnFlags::ServerChangeEmailInvMsg)?;
+    if request.email_hash.is_empty() || request.email_client_salt.is_empty() {
+        return Err(ReturnFlags::ServerChangeEmailInvMsg);
+    }
+
+    /* verify JWT token and current creds */
+    let claim = reauthorize(sql_conn, request.jwt, &request.credentials).await?;
+
+    /* hash the new email, off the async workers since the hashing takes a while */
+    let email_hash = request.email_hash;
+    let email_server_hash = tokio::task::spawn_blocking(move || hash_email(&email_hash))
+        .await
+        .map_err(|_| ReturnFlags::ServerChangeEmailFailed)?;
+    update_email(
+        sql_conn,
+        claim.user_id,
+        &HEXUPPER.encode(&email_server_hash.0),
+        &HEXUPPER.encode(&email_server_hash.1),
+        &HEXUPPER.encode(&request.email_client_salt),
+    )
+    .await?;
+    info!("ACC_CHANGE_EMAIL: user {}", claim.user_id);
+
+    /* confirm the change */
+    let message = message_builder(MessageType::ServerReturn, 1, 0, 0, 0, Vec::new());
+    tls_connection
+        .send(message)
+        .await
+        .map_err(|_| ReturnFlags::ServerTlsConnWriteFailed)?;
+
+    Ok(())
+}
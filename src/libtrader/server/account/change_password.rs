@@ -0,0 +1,77 @@
+use data_encoding::HEXUPPER;
+use log::info;
+
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::ChangePasswordRequest;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::authorization::reauthorize;
+use crate::server::account::hash_pwd::hash_pwd;
+use crate::server::account::session::acc_create_session;
+use crate::server::db::cmd::update_account::update_password;
+use crate::server::db::cmd::update_refresh_token::revoke_user_refresh_tokens;
+use crate::server::db::cmd::update_session::revoke_user_sessions;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Changes the password of an authorized user.
+///
+/// The new client hash is hashed again with a new server salt, and both salts replace the old
+/// ones. Every session of the user is logged out, and a new session is returned.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// tls_connection - The TLS connection to respond on.
+/// message - The password change message.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+pub async fn acc_change_password(
+    sql_conn: &tokio_postgres::Client,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> Result<(), ReturnFlags> {
+    let request: ChangePasswordRequest = message
+        .payload()
+        .map_err(|_| ReturnFlags::ServerChangePasswordInvMsg)?;
+    if request.password_hash.is_empty() || request.password_client_salt.is_empty() {
+        return Err(ReturnFlags::ServerChangePasswordInvMsg);
+    }
+
+    /* verify JWT token and current creds */
+    let claim = reauthorize(sql_conn, request.jwt, &request.credentials).await?;
+
+    /* hash the new password, off the async workers since the hashing takes a while */
+    let password_hash = request.password_hash;
+    let password_server_hash = tokio::task::spawn_blocking(move || hash_pwd(&password_hash))
+        .await
+        .map_err(|_| ReturnFlags::ServerChangePasswordFailed)?;
+    update_password(
+        sql_conn,
+        claim.user_id,
+        &HEXUPPER.encode(&password_server_hash.0),
+        &HEXUPPER.encode(&password_server_hash.1),
+        &HEXUPPER.encode(&request.password_client_salt),
+    )
+    .await?;
+
+    /* log out every device, the old password may have leaked */
+    revoke_user_sessions(sql_conn, claim.user_id).await?;
+    revoke_user_refresh_tokens(sql_conn, claim.user_id).await?;
+    info!("ACC_CHANGE_PASSWORD: user {}", claim.user_id);
+
+    /* send the tokens of a new session */
+    let tokens = acc_create_session(sql_conn, claim.user_id).await?;
+    let message = payload_builder(MessageType::ServerReturn, 1, &tokens);
+    tls_connection
+        .send(message)
+        .await
+        .map_err(|_| ReturnFlags::ServerTlsConnWriteFailed)?;
+
+    Ok(())
+}
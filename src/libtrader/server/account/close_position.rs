@@ -0,0 +1,142 @@
+use std::sync::RwLock;
+
+use log::warn;
+
+use crate::common::account::transaction::Transaction;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::ClosePositionRequest;
+use crate::common::message::response::PositionResponse;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::session::verify_session;
+use crate::server::config::server_config;
+use crate::server::db::cmd::create_ledger_entry::create_ledger_entry;
+use crate::server::db::cmd::create_transaction::create_transaction;
+use crate::server::db::cmd::get_position::get_open_position_for_update;
+use crate::server::db::cmd::update_position::update_position_close;
+use crate::server::db::initializer::db_connect;
+use crate::server::ds::global_state::GlobalState;
+use crate::server::market::clock::market_now;
+use crate::server::market::quote_cache::latest_stock_val;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Closes an open position of an authorized user.
+///
+/// Long positions are sold at the latest bid price, short positions are bought back at the
+/// latest ask price. The position update, the transaction and its cash ledger entry are written
+/// in one SQL transaction, and the updated position is sent back to the client. Its realized
+/// profit and loss is available through ```Position::realized_pnl()```.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// state - The global state to price the close with.
+/// tls_connection - The TLS connection to respond on.
+/// message - The close message, containing the JWT token, position id and shares amount.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+pub async fn acc_close_position(
+    sql_conn: &tokio_postgres::Client,
+    state: &RwLock<GlobalState>,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> Result<(), ReturnFlags> {
+    /*
+     * Parse close data.
+     * */
+    let ClosePositionRequest {
+        jwt,
+        position_id,
+        shares_amount,
+    } = message
+        .payload()
+        .map_err(|_| ReturnFlags::ServerClosePositionInvMsg)?;
+    if shares_amount <= 0 {
+        return Err(ReturnFlags::ServerClosePositionInvMsg);
+    }
+
+    /* verify JWT token */
+    let token = match verify_session(sql_conn, jwt).await {
+        Ok(token) => token,
+        Err(err) => {
+            warn!("ACC_CLOSE_POSITION_UNAUTH_TOKEN");
+            return Err(err);
+        }
+    };
+
+    /* connect to SQL database using user ```portfolio_schema_user``` */
+    let mut sql_conn = db_connect(&server_config().db_portfolio)
+        .await
+        .map_err(|_| ReturnFlags::ServerDbConnectFailed)?;
+
+    let sql_transaction = sql_conn
+        .transaction()
+        .await
+        .map_err(|_| ReturnFlags::ServerClosePositionFailed)?;
+
+    /*
+     * Price the close.
+     * */
+    let mut position =
+        get_open_position_for_update(&sql_transaction, token.user_id, position_id).await?;
+    if shares_amount > position.stock_open_amount - position.stock_close_amount {
+        return Err(ReturnFlags::ServerClosePositionInvMsg);
+    }
+    let quote = latest_stock_val(sql_transaction.client(), state, &position.stock_symbol).await?;
+    let price = if position.is_buy {
+        quote.bid_price
+    } else {
+        quote.ask_price
+    };
+
+    /*
+     * Write the close.
+     * */
+    position.close(shares_amount, price, market_now());
+    update_position_close(&sql_transaction, &position).await?;
+
+    let close_cost = price * shares_amount as f64;
+    let transaction = Transaction {
+        stock_symbol: position.stock_symbol.clone(),
+        shares_size: shares_amount,
+        shares_cost: close_cost.round() as i64,
+        is_buy: !position.is_buy,
+    };
+    let transaction_id = create_transaction(&sql_transaction, token.user_id, &transaction).await?;
+
+    /* selling a long credits the proceeds, buying back a short debits the cost */
+    let amount = if position.is_buy {
+        close_cost
+    } else {
+        -close_cost
+    };
+    create_ledger_entry(
+        &sql_transaction,
+        token.user_id,
+        Some(transaction_id),
+        amount,
+    )
+    .await?;
+
+    sql_transaction
+        .commit()
+        .await
+        .map_err(|_| ReturnFlags::ServerClosePositionFailed)?;
+
+    /*
+     * Send the updated position.
+     * */
+    let message = payload_builder(MessageType::ServerReturn, 1, &PositionResponse { position });
+    tls_connection
+        .send(message)
+        .await
+        .map_err(|_| ReturnFlags::ServerTlsConnWriteFailed)?;
+
+    Ok(())
+}
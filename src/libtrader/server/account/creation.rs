@@ -2,11 +2,16 @@ use data_encoding::HEXUPPER;
 
 use crate::common::account::portfolio::Portfolio;
 use crate::common::message::message::Message;
+use crate::common::message::request::RegisterRequest;
 use crate::common::misc::return_flags::ReturnFlags;
 
 use crate::server::account::hash_email::hash_email;
 use crate::server::account::hash_pwd::hash_pwd;
 use crate::server::ds::account::Account;
+use crate::server::market::clock::market_now;
+
+/// The cash balance every newly registered account starts with.
+pub static STARTING_CASH_BALANCE: f64 = 100_000.0;
 
 pub async fn acc_create(
     sql_conn: &tokio_postgres::Client,
@@ -15,57 +20,24 @@ pub async fn acc_create(
     /*
      * Parse account data
      * */
-    /* get json data */
-    let stringified_data = std::str::from_utf8(&message.data).unwrap().to_string();
-    let data = json::parse(&stringified_data).unwrap();
-    /* get email, password salts and client hashes */
-    let email_hash = HEXUPPER
-        .decode(data["email_hash"].as_str().unwrap().to_string().as_bytes())
-        .unwrap();
-    let email_client_salt = HEXUPPER
-        .decode(
-            data["email_client_salt"]
-                .as_str()
-                .unwrap()
-                .to_string()
-                .as_bytes(),
-        )
-        .unwrap();
-    let password_hash = HEXUPPER
-        .decode(
-            data["password_hash"]
-                .as_str()
-                .unwrap()
-                .to_string()
-                .as_bytes(),
-        )
-        .unwrap();
-    let password_client_salt = HEXUPPER
-        .decode(
-            data["password_client_salt"]
-                .as_str()
-                .unwrap()
-                .to_string()
-                .as_bytes(),
-        )
-        .unwrap();
-
-    /* get username */
-    let username: String = data["username"].as_str().unwrap().to_string();
+    let request: RegisterRequest = message
+        .payload()
+        .map_err(|_| ReturnFlags::ServerRegisterInvMsg)?;
 
     /* generate account struct */
     let mut account: Account = Account {
-        username: username,
+        username: request.username,
 
         email_hash: "".to_string(),
         server_email_salt: "".to_string(),
-        client_email_salt: HEXUPPER.encode(&email_client_salt),
+        client_email_salt: HEXUPPER.encode(&request.email_client_salt),
 
         pass_hash: "".to_string(),
         server_pass_salt: "".to_string(),
-        client_pass_salt: HEXUPPER.encode(&password_client_salt),
+        client_pass_salt: HEXUPPER.encode(&request.password_client_salt),
 
         is_pass: true,
+        cash_balance: STARTING_CASH_BALANCE,
         portfolio: Portfolio::default(),
         transactions: Vec::new(),
     };
@@ -87,27 +59,35 @@ pub async fn acc_create(
     }
 
     /*
-     * Hash the email and password.
+     * Hash the email and password, off the async workers since the hashing takes a while.
      * */
-    /* hash the email */
-    let email_server_hash = hash_email(&email_hash);
+    let (email_hash, password_hash) = (request.email_hash, request.password_hash);
+    let (email_server_hash, password_server_hash) =
+        tokio::task::spawn_blocking(move || (hash_email(&email_hash), hash_pwd(&password_hash)))
+            .await
+            .map_err(|_| ReturnFlags::ServerDbWriteFailed)?;
+    /* the email */
     account.email_hash = HEXUPPER.encode(&email_server_hash.0);
     account.server_email_salt = HEXUPPER.encode(&email_server_hash.1);
-    /* hash the password */
-    let password_server_hash = hash_pwd(&password_hash);
+    /* the password */
     account.pass_hash = HEXUPPER.encode(&password_server_hash.0);
     account.server_pass_salt = HEXUPPER.encode(&password_server_hash.1);
 
     /*
      * Write the account to the database.
      * */
-    match sql_conn.execute("INSERT INTO accounts_schema.accounts \
-        (username, email_hash, server_email_salt, client_email_salt, pass_hash, server_pass_salt, client_pass_salt)
+    /* the starting cash balance is deposited in the same statement */
+    let time_epoch = market_now();
+    match sql_conn.execute("WITH account AS (INSERT INTO accounts_schema.accounts \
+        (username, email_hash, server_email_salt, client_email_salt, pass_hash, server_pass_salt, client_pass_salt, cash_balance)
         VALUES \
-        ($1, $2, $3, $4, $5, $6, $7)",
+        ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING id, cash_balance) \
+        INSERT INTO accounts_schema.ledger (user_id, amount, balance, time_epoch) \
+        SELECT id, cash_balance, cash_balance, $9 FROM account",
         &[&account.username,
         &account.email_hash, &account.server_email_salt, &account.client_email_salt,
-        &account.pass_hash, &account.server_pass_salt, &account.client_pass_salt]).await {
+        &account.pass_hash, &account.server_pass_salt, &account.client_pass_salt,
+        &account.cash_balance, &time_epoch]).await {
             Ok(_) => return Ok(()),
             Err(_) => return Err(ReturnFlags::ServerDbWriteFailed),
     }
@@ -0,0 +1,57 @@
+use log::info;
+
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::message_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::DeleteAccountRequest;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::authorization::reauthorize;
+use crate::server::config::server_config;
+use crate::server::db::cmd::delete_account::delete_account;
+use crate::server::db::initializer::db_connect;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Deletes the account of an authorized user.
+///
+/// Removes the positions, orders, transactions and sessions of the account along with it.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// tls_connection - The TLS connection to respond on.
+/// message - The deletion message.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+pub async fn acc_delete(
+    sql_conn: &tokio_postgres::Client,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> Result<(), ReturnFlags> {
+    let request: DeleteAccountRequest = message
+        .payload()
+        .map_err(|_| ReturnFlags::ServerDeleteAccountInvMsg)?;
+
+    /* verify JWT token and current creds */
+    let claim = reauthorize(sql_conn, request.jwt, &request.credentials).await?;
+
+    /* connect to SQL database using user ```accounts_schema_user```, for a transaction */
+    let mut delete_conn = db_connect(&server_config().db_accounts)
+        .await
+        .map_err(|_| ReturnFlags::ServerDbConnectFailed)?;
+    delete_account(&mut delete_conn, claim.user_id).await?;
+    info!("ACC_DELETE: user {}", claim.user_id);
+
+    /* confirm the deletion */
+    let message = message_builder(MessageType::ServerReturn, 1, 0, 0, 0, Vec::new());
+    tls_connection
+        .send(message)
+        .await
+        .map_err(|_| ReturnFlags::ServerTlsConnWriteFailed)?;
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::common::account::position::Position;
+use crate::common::account::transaction::Transaction;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::db::cmd::create_ledger_entry::create_ledger_entry;
+use crate::server::db::cmd::create_position::create_position;
+use crate::server::db::cmd::create_transaction::create_transaction;
+use crate::server::db::cmd::get_position::get_open_exposure;
+use crate::server::db::cmd::get_user_cash::get_user_cash;
+use crate::server::market::clock::market_now;
+
+/// Records a filled order on the postgres SQL database.
+///
+/// Creates the opened position, the matching transaction and its cash ledger entry for a fill.
+/// The proceeds of open short sales are held as collateral: buys are rejected when their cost
+/// exceeds the cash left after them, and short sales when the open short exposure would exceed
+/// the user's equity, cash plus longs minus shorts at their open price. Everything is written
+/// through the passed SQL transaction, the caller is responsible for committing it.
+///
+/// Arguments:
+/// sql_transaction - The SQL transaction to write the fill in.
+/// user_id - The user that owns the fill.
+/// symbol - The symbol of the filled stock.
+/// shares_amount - The number of filled shares.
+/// price - The price per share the fill happened at.
+/// is_buy - Whether the fill opens a long or a short position.
+///
+/// Returns: the opened position on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let sql_transaction = sql_conn.transaction().await?;
+///     let position = execute_fill(&sql_transaction, user_id, "AAPL", 10, 123.4, true).await?;
+///     sql_transaction.commit().await?;
+/// ```
+pub async fn execute_fill(
+    sql_transaction: &tokio_postgres::Transaction<'_>,
+    user_id: i64,
+    symbol: &str,
+    shares_amount: i64,
+    price: f64,
+    is_buy: bool,
+) -> Result<Position, ReturnFlags> {
+    let now = market_now();
+
+    let mut position = Position {
+        is_buy,
+        stock_symbol: symbol.to_string(),
+        stock_open_amount: shares_amount,
+        stock_open_price: price,
+        stock_open_cost: price * shares_amount as f64,
+        open_epoch: now,
+        is_open: true,
+        ..Position::default()
+    };
+    let transaction = Transaction {
+        stock_symbol: symbol.to_string(),
+        shares_size: shares_amount,
+        shares_cost: position.stock_open_cost.round() as i64,
+        is_buy,
+    };
+
+    /* enforce buying power, the cash row lock serializes the fills of a user */
+    let cash = get_user_cash(sql_transaction, user_id).await?;
+    let exposure = get_open_exposure(sql_transaction, user_id).await?;
+    let allowed = if is_buy {
+        position.stock_open_cost <= cash - exposure.short
+    } else {
+        exposure.short + position.stock_open_cost <= cash + exposure.long - exposure.short
+    };
+    if !allowed {
+        return Err(ReturnFlags::ServerAccInsufficientFunds);
+    }
+
+    position.id = create_position(sql_transaction, user_id, &position).await?;
+    let transaction_id = create_transaction(sql_transaction, user_id, &transaction).await?;
+
+    /* buys debit the cost, short sales credit the proceeds */
+    let amount = if is_buy {
+        -position.stock_open_cost
+    } else {
+        position.stock_open_cost
+    };
+    create_ledger_entry(sql_transaction, user_id, Some(transaction_id), amount).await?;
+
+    Ok(position)
+}
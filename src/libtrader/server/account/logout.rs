@@ -0,0 +1,65 @@
+use log::info;
+
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::message_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::AuthRequest;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::session::verify_session;
+use crate::server::db::cmd::update_refresh_token::{
+    revoke_session_refresh_family, revoke_user_refresh_tokens,
+};
+use crate::server::db::cmd::update_session::{revoke_session, revoke_user_sessions};
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Logs out the session of a JWT token, or every session of its user.
+///
+/// The refresh tokens the sessions were issued with are revoked as well.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// tls_connection - The TLS connection to respond on.
+/// message - The logout message, containing the JWT token.
+/// all_devices - Whether to log out every session of the user.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+pub async fn acc_logout(
+    sql_conn: &tokio_postgres::Client,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+    all_devices: bool,
+) -> Result<(), ReturnFlags> {
+    let request: AuthRequest = message
+        .payload()
+        .map_err(|_| ReturnFlags::ServerLogoutInvMsg)?;
+
+    /* verify JWT token, a session already logged out can not log out others */
+    let claim = verify_session(sql_conn, request.jwt).await?;
+
+    if all_devices {
+        let revoked = revoke_user_sessions(sql_conn, claim.user_id).await?;
+        revoke_user_refresh_tokens(sql_conn, claim.user_id).await?;
+        info!(
+            "ACC_LOGOUT_ALL: user {} revoked {} sessions",
+            claim.user_id, revoked
+        );
+    } else {
+        revoke_session(sql_conn, claim.user_id, &claim.jti).await?;
+        revoke_session_refresh_family(sql_conn, claim.user_id, &claim.jti).await?;
+    }
+
+    /* confirm the logout */
+    let message = message_builder(MessageType::ServerReturn, 1, 0, 0, 0, Vec::new());
+    tls_connection
+        .send(message)
+        .await
+        .map_err(|_| ReturnFlags::ServerTlsConnWriteFailed)?;
+
+    Ok(())
+}
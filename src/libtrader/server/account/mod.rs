@@ -1,7 +1,22 @@
 pub mod authorization;
+pub mod cancel_order;
+pub mod change_email;
+pub mod change_password;
+pub mod close_position;
 pub mod creation;
+pub mod deletion;
+pub mod execute_fill;
 pub mod hash;
 pub mod hash_email;
 pub mod hash_pwd;
+pub mod logout;
+pub mod place_order;
+pub mod purchase_asset;
+pub mod refresh;
+pub mod retrieval_ledger;
+pub mod retrieval_orders;
 pub mod retrieval_portfolio;
 pub mod retrieval_transaction;
+pub mod retrieval_valuation;
+pub mod salt;
+pub mod session;
@@ -0,0 +1,100 @@
+use log::warn;
+
+use crate::common::account::order::{Order, OrderType};
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::PlaceOrderRequest;
+use crate::common::message::response::PlaceOrderResponse;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::session::verify_session;
+use crate::server::config::server_config;
+use crate::server::db::cmd::create_order::create_order;
+use crate::server::db::cmd::get_company::get_company_from_db;
+use crate::server::db::initializer::db_connect;
+use crate::server::market::clock::market_now;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Places a working order for an authorized user.
+///
+/// Validates the order and stores it in portfolio_schema.orders, where the order matcher picks it
+/// up on the next tick of its symbol. The stored order is sent back to the client.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// tls_connection - The TLS connection to respond on.
+/// message - The order message, containing the JWT token and the order.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+pub async fn acc_place_order(
+    sql_conn: &tokio_postgres::Client,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> Result<(), ReturnFlags> {
+    /*
+     * Parse order data.
+     * */
+    let request: PlaceOrderRequest = message
+        .payload()
+        .map_err(|_| ReturnFlags::ServerPlaceOrderInvMsg)?;
+    let mut order = Order {
+        order_type: request.order_type,
+        is_buy: request.is_buy,
+        stock_symbol: request.symbol,
+        stock_price: request.limit_price,
+        stop_price: request.stop_price,
+        stock_amount: request.shares_amount,
+        ..Order::default()
+    };
+
+    /* validate order */
+    let needs_limit = order.order_type != OrderType::Stop;
+    let needs_stop = order.order_type != OrderType::Limit;
+    if order.stock_amount <= 0
+        || (needs_limit && order.stock_price <= 0.0)
+        || (needs_stop && order.stop_price <= 0.0)
+    {
+        return Err(ReturnFlags::ServerPlaceOrderInvMsg);
+    }
+    order.is_triggered = !needs_stop;
+
+    /* verify JWT token */
+    let token = match verify_session(sql_conn, request.jwt).await {
+        Ok(token) => token,
+        Err(err) => {
+            warn!("ACC_PLACE_ORDER_UNAUTH_TOKEN");
+            return Err(err);
+        }
+    };
+
+    /* connect to SQL database using user ```portfolio_schema_user``` */
+    let sql_conn = db_connect(&server_config().db_portfolio)
+        .await
+        .map_err(|_| ReturnFlags::ServerDbConnectFailed)?;
+
+    /* only trade listed companies */
+    get_company_from_db(&sql_conn, &order.stock_symbol).await?;
+
+    /*
+     * Store the order.
+     * */
+    order.create_epoch = market_now();
+    order.id = create_order(&sql_conn, token.user_id, &order).await?;
+
+    /*
+     * Send the placed order.
+     * */
+    let message = payload_builder(MessageType::ServerReturn, 1, &PlaceOrderResponse { order });
+    tls_connection
+        .send(message)
+        .await
+        .map_err(|_| ReturnFlags::ServerTlsConnWriteFailed)?;
+
+    Ok(())
+}
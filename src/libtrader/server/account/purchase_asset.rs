@@ -0,0 +1,119 @@
+use std::sync::RwLock;
+
+use log::warn;
+
+use crate::common::message::inst::CommandInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::PurchaseAssetRequest;
+use crate::common::message::response::PositionResponse;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::execute_fill::execute_fill;
+use crate::server::account::session::verify_session;
+use crate::server::config::server_config;
+use crate::server::db::cmd::get_company::get_company_from_db;
+use crate::server::db::initializer::db_connect;
+use crate::server::ds::global_state::GlobalState;
+use crate::server::market::quote_cache::latest_stock_val;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Executes a market order for an authorized user.
+///
+/// Fills ```PurchaseAsset``` at the latest ask price and ```SellAsset``` at the latest bid price
+/// of the requested stock. The resulting position and transaction are written in one SQL
+/// transaction, and the opened position is sent back to the client.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// state - The global state to price the order with.
+/// tls_connection - The TLS connection to respond on.
+/// message - The order message, containing the JWT token, symbol and shares amount.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+pub async fn acc_purchase_asset(
+    sql_conn: &tokio_postgres::Client,
+    state: &RwLock<GlobalState>,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> Result<(), ReturnFlags> {
+    /*
+     * Parse order data.
+     * */
+    let PurchaseAssetRequest {
+        jwt,
+        symbol,
+        shares_amount,
+    } = message
+        .payload()
+        .map_err(|_| ReturnFlags::ServerPurchaseAssetInvMsg)?;
+    if shares_amount <= 0 {
+        return Err(ReturnFlags::ServerPurchaseAssetInvMsg);
+    }
+    let is_buy = message.instruction == CommandInst::PurchaseAsset as i64;
+
+    /* verify JWT token */
+    let token = match verify_session(sql_conn, jwt).await {
+        Ok(token) => token,
+        Err(err) => {
+            warn!("ACC_PURCHASE_ASSET_UNAUTH_TOKEN");
+            return Err(err);
+        }
+    };
+
+    /* connect to SQL database using user ```portfolio_schema_user``` */
+    let mut sql_conn = db_connect(&server_config().db_portfolio)
+        .await
+        .map_err(|_| ReturnFlags::ServerDbConnectFailed)?;
+
+    /*
+     * Price the order.
+     * */
+    /* only trade listed companies */
+    get_company_from_db(&sql_conn, &symbol).await?;
+    /* fill at the latest quote */
+    let quote = latest_stock_val(&sql_conn, state, &symbol).await?;
+    let price = if is_buy {
+        quote.ask_price
+    } else {
+        quote.bid_price
+    };
+
+    /*
+     * Write the fill.
+     * */
+    let sql_transaction = sql_conn
+        .transaction()
+        .await
+        .map_err(|_| ReturnFlags::ServerPurchaseAssetFailed)?;
+    let position = execute_fill(
+        &sql_transaction,
+        token.user_id,
+        &symbol,
+        shares_amount,
+        price,
+        is_buy,
+    )
+    .await?;
+    sql_transaction
+        .commit()
+        .await
+        .map_err(|_| ReturnFlags::ServerPurchaseAssetFailed)?;
+
+    /*
+     * Send the opened position.
+     * */
+    let message = payload_builder(MessageType::ServerReturn, 1, &PositionResponse { position });
+    tls_connection
+        .send(message)
+        .await
+        .map_err(|_| ReturnFlags::ServerTlsConnWriteFailed)?;
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::RefreshRequest;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::session::acc_refresh_session;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Exchanges a refresh token for a new JWT token and refresh token.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// tls_connection - The TLS connection to respond on.
+/// message - The refresh message, containing the refresh token.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+pub async fn acc_refresh(
+    sql_conn: &tokio_postgres::Client,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> Result<(), ReturnFlags> {
+    let request: RefreshRequest = message
+        .payload()
+        .map_err(|_| ReturnFlags::ServerRefreshTokenInvMsg)?;
+
+    /* exchange the refresh token */
+    let tokens = acc_refresh_session(sql_conn, &request.refresh_token).await?;
+
+    /* send the new JWT and refresh tokens */
+    let message = payload_builder(MessageType::ServerReturn, 1, &tokens);
+    tls_connection
+        .send(message)
+        .await
+        .map_err(|_| ReturnFlags::ServerTlsConnWriteFailed)?;
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use log::warn;
+
+use crate::common::account::ledger::{Ledger, LedgerEntry};
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::AuthRequest;
+use crate::common::message::response::LedgerResponse;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::session::verify_session;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+pub async fn acc_retrieve_ledger(
+    sql_conn: &tokio_postgres::Client,
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> Result<(), ReturnFlags> {
+    /* verify JWT token */
+    let request: AuthRequest = message
+        .payload()
+        .map_err(|_| ReturnFlags::ServerRetrieveLedgerInvMsg)?;
+    let token = match verify_session(sql_conn, request.jwt).await {
+        Ok(token) => token,
+        Err(err) => {
+            warn!("ACC_RETRIEVE_LEDGER_UNAUTH_TOKEN");
+            return Err(err);
+        }
+    };
+
+    /* get userId's cash balance */
+    let mut ledger = Ledger {
+        cash_balance: sql_conn
+            .query_one(
+                "SELECT cash_balance FROM accounts_schema.accounts WHERE id = $1",
+                &[&token.user_id],
+            )
+            .await
+            .map_err(|_| ReturnFlags::ServerRetrieveLedgerFailed)?
+            .get(0),
+        entries: Vec::new(),
+    };
+
+    /* get userId's ledger entries */
+    for row in sql_conn
+        .query(
+            "SELECT transaction_id, amount, balance, time_epoch FROM accounts_schema.ledger 
+                         WHERE user_id = $1 ORDER BY id",
+            &[&token.user_id],
+        )
+        .await
+        .map_err(|_| ReturnFlags::ServerRetrieveLedgerFailed)?
+    {
+        ledger.entries.push(LedgerEntry {
+            transaction_id: row.get(0),
+            amount: row.get(1),
+            balance: row.get(2),
+            time_epoch: row.get(3),
+        });
+    }
+
+    /* build message to be send */
+    let message = payload_builder(MessageType::ServerReturn, 1, &LedgerResponse { ledger });
+    socket
+        .send(message)
+        .await
+        .map_err(|_| ReturnFlags::ServerRetrieveLedgerFailed)?;
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use log::warn;
+
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::AuthRequest;
+use crate::common::message::response::OrdersResponse;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::session::verify_session;
+use crate::server::config::server_config;
+use crate::server::db::cmd::get_orders::get_orders_from_db;
+use crate::server::db::initializer::db_connect;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+pub async fn acc_retrieve_orders(
+    sql_conn: &tokio_postgres::Client,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> Result<(), ReturnFlags> {
+    /* verify JWT token */
+    let request: AuthRequest = message
+        .payload()
+        .map_err(|_| ReturnFlags::ServerRetrieveOrdersInvMsg)?;
+    let token = match verify_session(sql_conn, request.jwt).await {
+        Ok(token) => token,
+        Err(err) => {
+            warn!("ACC_RETRIEVE_ORDERS_UNAUTH_TOKEN");
+            return Err(err);
+        }
+    };
+
+    /* connect to SQL database using user ```portfolio_schema_user``` */
+    let sql_conn = db_connect(&server_config().db_portfolio)
+        .await
+        .map_err(|_| ReturnFlags::ServerRetrieveOrdersFailed)?;
+
+    /* get userId's orders */
+    let orders = get_orders_from_db(&sql_conn, token.user_id).await?;
+
+    /* build message to be send */
+    let message = payload_builder(MessageType::ServerReturn, 1, &OrdersResponse { orders });
+    tls_connection
+        .send(message)
+        .await
+        .map_err(|_| ReturnFlags::ServerRetrieveOrdersFailed)?;
+
+    Ok(())
+}
@@ -2,28 +2,34 @@ use log::warn;
 
 use crate::common::account::transaction::Transaction;
 use crate::common::message::message::Message;
-use crate::common::message::message_builder::message_builder;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
 use crate::common::message::message_type::MessageType;
+use crate::common::message::request::AuthRequest;
+use crate::common::message::response::TransactionsResponse;
 use crate::common::misc::return_flags::ReturnFlags;
 
-use crate::server::network::jwt_wrapper::verify_jwt_token;
+use crate::server::account::session::verify_session;
 
-use tokio::io::AsyncWriteExt;
+use futures::SinkExt;
 use tokio::net::TcpStream;
 use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
 
 pub async fn acc_retrieve_transaction(
     sql_conn: &tokio_postgres::Client,
-    socket: &mut TlsStream<TcpStream>,
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
     message: &Message,
 ) -> Result<(), ReturnFlags> {
     /* verify JWT token */
-    let token = match verify_jwt_token(bincode::deserialize(&message.data).unwrap()) {
+    let request: AuthRequest = message
+        .payload()
+        .map_err(|_| ReturnFlags::ServerRetrieveTransactionInvMsg)?;
+    let token = match verify_session(sql_conn, request.jwt).await {
         Ok(token) => token,
-        Err(_) => {
+        Err(err) => {
             warn!("ACC_RETRIEVE_TRANSACTION_UNAUTH_TOKEN");
-            socket.shutdown().await.unwrap();
-            return Err(ReturnFlags::ServerAccUnauthorized);
+            return Err(err);
         }
     };
 
@@ -47,16 +53,13 @@ pub async fn acc_retrieve_transaction(
     }
 
     /* build message to be send */
-    let message = message_builder(
+    let message = payload_builder(
         MessageType::ServerReturn,
         1,
-        1,
-        0,
-        0,
-        bincode::serialize(&transactions).unwrap(),
+        &TransactionsResponse { transactions },
     );
     socket
-        .write_all(&bincode::serialize(&message).unwrap())
+        .send(message)
         .await
         .map_err(|_| ReturnFlags::ServerRetrieveTransactionFailed)?;
 
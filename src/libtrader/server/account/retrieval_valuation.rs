@@ -0,0 +1,112 @@
+use std::collections::{BTreeSet, HashMap};
+
+use log::warn;
+
+use crate::common::account::valuation::Valuation;
+use crate::common::generic::stock_val::StockVal;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::payload_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::AuthRequest;
+use crate::common::message::response::ValuationResponse;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::session::verify_session;
+use crate::server::config::server_config;
+use crate::server::db::cmd::get_company::get_company_from_db;
+use crate::server::db::cmd::get_position::{position_from_row, POSITION_COLUMNS};
+use crate::server::db::cmd::get_stock::get_stocks_from_db_latest;
+use crate::server::db::initializer::db_connect;
+use crate::server::market::clock::market_now;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+pub async fn acc_retrieve_valuation(
+    sql_conn: &tokio_postgres::Client,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> Result<(), ReturnFlags> {
+    /* verify JWT token */
+    let request: AuthRequest = message
+        .payload()
+        .map_err(|_| ReturnFlags::ServerRetrieveValuationInvMsg)?;
+    let token = match verify_session(sql_conn, request.jwt).await {
+        Ok(token) => token,
+        Err(err) => {
+            warn!("ACC_RETRIEVE_VALUATION_UNAUTH_TOKEN");
+            return Err(err);
+        }
+    };
+
+    /* connect to SQL database using user ```portfolio_schema_user``` */
+    let sql_conn = db_connect(&server_config().db_portfolio)
+        .await
+        .map_err(|_| ReturnFlags::ServerRetrieveValuationFailed)?;
+
+    /* get userId's cash balance */
+    let cash: f64 = sql_conn
+        .query_one(
+            "SELECT cash_balance FROM accounts_schema.accounts WHERE id = $1",
+            &[&token.user_id],
+        )
+        .await
+        .map_err(|_| ReturnFlags::ServerRetrieveValuationFailed)?
+        .get(0);
+
+    /* get userId's open positions */
+    let positions: Vec<_> = sql_conn
+        .query(
+            format!(
+                "SELECT {} FROM portfolio_schema.positions WHERE user_id = $1 AND is_open ORDER BY id",
+                POSITION_COLUMNS
+            )
+            .as_str(),
+            &[&token.user_id],
+        )
+        .await
+        .map_err(|_| ReturnFlags::ServerRetrieveValuationFailed)?
+        .iter()
+        .map(position_from_row)
+        .collect();
+
+    /* get the latest quote and company of every held symbol */
+    let symbols: Vec<String> = positions
+        .iter()
+        .map(|position| position.stock_symbol.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let quotes: HashMap<String, StockVal> =
+        get_stocks_from_db_latest(&sql_conn, &symbols, market_now())
+            .await
+            .map_err(|_| ReturnFlags::ServerRetrieveValuationFailed)?
+            .into_iter()
+            .map(|quote| (quote.symbol.clone(), quote))
+            .collect();
+    let mut companies = HashMap::new();
+    for symbol in &symbols {
+        if let Ok(company) = get_company_from_db(&sql_conn, symbol).await {
+            companies.insert(symbol.clone(), company);
+        }
+    }
+
+    /* value the account */
+    let valuation = Valuation::new(cash, &positions, &quotes, &companies);
+
+    /* build message to be send */
+    let message = payload_builder(
+        MessageType::ServerReturn,
+        1,
+        &ValuationResponse { valuation },
+    );
+    tls_connection
+        .send(message)
+        .await
+        .map_err(|_| ReturnFlags::ServerRetrieveValuationFailed)?;
+
+    Ok(())
+}
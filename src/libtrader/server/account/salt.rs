@@ -0,0 +1,64 @@
+use data_encoding::HEXUPPER;
+use ring::{digest, hmac};
+
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::config::server_config;
+use crate::server::db::cmd::get_user_salt::get_user_salt;
+
+/// Returns the client salt of an account, for the client to hash its credentials with.
+///
+/// Unknown usernames get a fake salt derived from the username and ```salt_secret```, which is
+/// the same on every request, so that the response does not reveal whether an account exists.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// username - The username of the account.
+/// is_email - Whether to return the email salt or the password salt.
+///
+/// Returns: the salt on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let salt = acc_client_salt(sql_conn, &request.username, true).await?;
+/// ```
+pub async fn acc_client_salt(
+    sql_conn: &tokio_postgres::Client,
+    username: &str,
+    is_email: bool,
+) -> Result<Vec<u8>, ReturnFlags> {
+    match get_user_salt(sql_conn, username, is_email, false).await {
+        Ok(salt) => HEXUPPER
+            .decode(salt.as_bytes())
+            .map_err(|_| ReturnFlags::ServerDbUserSaltNotFound),
+        Err(ReturnFlags::ServerDbUserSaltNotFound) => {
+            Ok(fake_salt(&server_config().salt_secret, username, is_email))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Derives the fake client salt of an unknown username.
+///
+/// Returns: a salt of the same length as real client salts.
+pub fn fake_salt(secret: &[u8], username: &str, is_email: bool) -> Vec<u8> {
+    let key = hmac::Key::new(hmac::HMAC_SHA512, secret);
+    let kind = if is_email { "email:" } else { "password:" };
+    let tag = hmac::sign(&key, [kind, username].concat().as_bytes());
+    tag.as_ref()[..digest::SHA512_OUTPUT_LEN].to_vec()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fake_salt() {
+        let salt = fake_salt(b"secret", "n1ckn8me", true);
+        assert_eq!(salt.len(), digest::SHA512_OUTPUT_LEN);
+        assert_eq!(salt, fake_salt(b"secret", "n1ckn8me", true));
+        assert_ne!(salt, fake_salt(b"secret", "n1ckn8me", false));
+        assert_ne!(salt, fake_salt(b"secret", "n2ckn8me", true));
+        assert_ne!(salt, fake_salt(b"other secret", "n1ckn8me", true));
+    }
+}
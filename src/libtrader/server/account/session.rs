@@ -0,0 +1,242 @@
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use data_encoding::HEXLOWER;
+use log::{info, warn};
+use ring::digest;
+use ring::rand::{self, SecureRandom};
+
+use crate::common::account::session::{RefreshToken, SessionID};
+use crate::common::message::response::LoginResponse;
+use crate::common::misc::return_flags::ReturnFlags;
+use crate::common::sessions::jwt_claim::JWTClaim;
+
+use crate::server::config::server_config;
+use crate::server::db::cmd::create_refresh_token::create_refresh_token;
+use crate::server::db::cmd::create_session::create_session;
+use crate::server::db::cmd::get_refresh_token::get_refresh_token;
+use crate::server::db::cmd::get_session::get_session;
+use crate::server::db::cmd::update_refresh_token::{
+    delete_expired_refresh_tokens, revoke_refresh_family, use_refresh_token,
+};
+use crate::server::db::cmd::update_session::{
+    delete_expired_sessions, revoke_family_sessions, revoke_session,
+};
+use crate::server::initializer::IP;
+use crate::server::network::jwt_wrapper::{create_jwt_token, verify_jwt_token};
+
+/// How often expired sessions and refresh tokens are deleted, in seconds.
+pub const CLEANUP_INTERVAL: i64 = 60 * 60;
+
+/// The last time expired sessions and refresh tokens were deleted.
+static LAST_CLEANUP: AtomicI64 = AtomicI64::new(0);
+
+/// Opens a session for an authorized user.
+///
+/// Issues a JWT token and a refresh token starting a new family, and records them, with the
+/// address of the connected client, so that they can be logged out before they expire.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// user_id - The DB entry id of the authorized user.
+///
+/// Returns: the JWT and refresh tokens on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let tokens = acc_create_session(sql_conn, user_id).await?;
+/// ```
+pub async fn acc_create_session(
+    sql_conn: &tokio_postgres::Client,
+    user_id: i64,
+) -> Result<LoginResponse, ReturnFlags> {
+    let family_id = random_hex()?;
+    issue_session(sql_conn, user_id, &family_id).await
+}
+
+/// Exchanges a refresh token for a new JWT token and refresh token.
+///
+/// The refresh token can be exchanged only once. Exchanging it again means it was stolen, so
+/// its whole family is revoked along with every session it issued, logging out both the thief
+/// and the user. The session issued with the exchanged token is logged out.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// refresh_token - The refresh token to exchange.
+///
+/// Returns: the new JWT and refresh tokens on success, ReturnFlags on error;
+/// ```ServerAccRefreshTokenReused``` when the token was already exchanged.
+///
+/// Example:
+/// ```rust
+///     let tokens = acc_refresh_session(sql_conn, &request.refresh_token).await?;
+/// ```
+pub async fn acc_refresh_session(
+    sql_conn: &tokio_postgres::Client,
+    refresh_token: &str,
+) -> Result<LoginResponse, ReturnFlags> {
+    let token_hash = hash_refresh_token(refresh_token);
+    let token = match use_refresh_token(sql_conn, &token_hash).await? {
+        Some(token) => token,
+        None => {
+            return match get_refresh_token(sql_conn, &token_hash).await? {
+                Some(token) if token.is_used => {
+                    warn!("REFRESH_TOKEN_REUSED: family {}", token.family_id);
+                    revoke_refresh_family(sql_conn, &token.family_id).await?;
+                    revoke_family_sessions(sql_conn, &token.family_id).await?;
+                    Err(ReturnFlags::ServerAccRefreshTokenReused)
+                }
+                Some(_) => Err(ReturnFlags::ServerAccSessionRevoked),
+                None => Err(ReturnFlags::ServerAccUnauthorized),
+            };
+        }
+    };
+    if token.expiry_date < now() {
+        return Err(ReturnFlags::ServerAccRefreshTokenExpired);
+    }
+
+    match revoke_session(sql_conn, token.user_id, &token.sess_id).await {
+        Ok(()) | Err(ReturnFlags::ServerDbSearchSessionNotFound) => {}
+        Err(err) => return Err(err),
+    }
+    issue_session(sql_conn, token.user_id, &token.family_id).await
+}
+
+/// Verifies a JWT token and the session it belongs to.
+///
+/// Unlike ```verify_jwt_token()```, tokens of sessions that were logged out or revoked are
+/// rejected.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// token - The JWT token to verify.
+///
+/// Returns: the JWTClaim on success, ReturnFlags on error; ```ServerAccSessionRevoked``` when the
+/// session is no longer active.
+///
+/// Example:
+/// ```rust
+///     let claim = verify_session(sql_conn, request.jwt).await?;
+/// ```
+pub async fn verify_session(
+    sql_conn: &tokio_postgres::Client,
+    token: String,
+) -> Result<JWTClaim, ReturnFlags> {
+    let claim = verify_jwt_token(token)?;
+    let session = match get_session(sql_conn, &claim.jti).await {
+        Ok(session) => session,
+        Err(ReturnFlags::ServerDbSearchSessionNotFound) => {
+            warn!("VERIFY_SESSION_UNKNOWN: {}", claim.jti);
+            return Err(ReturnFlags::ServerAccUnauthorized);
+        }
+        Err(err) => return Err(err),
+    };
+    if !session.is_active || session.user_id != claim.user_id {
+        warn!("VERIFY_SESSION_REVOKED: {}", session);
+        return Err(ReturnFlags::ServerAccSessionRevoked);
+    }
+    Ok(claim)
+}
+
+/// Issues and records a JWT token and a refresh token of the given family.
+async fn issue_session(
+    sql_conn: &tokio_postgres::Client,
+    user_id: i64,
+    family_id: &str,
+) -> Result<LoginResponse, ReturnFlags> {
+    if is_cleanup_due(&LAST_CLEANUP, now()) {
+        delete_expired(sql_conn, now()).await;
+    }
+
+    let (jwt, claim) = create_jwt_token(user_id)?;
+    let session = SessionID {
+        sess_id: claim.jti,
+        user_id,
+        client_ip: IP
+            .try_with(|addr| addr.ip())
+            .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+        issue_date: claim.iat as i64,
+        expiry_date: claim.exp as i64,
+        is_active: true,
+    };
+    create_session(sql_conn, &session).await?;
+
+    let refresh_token = random_hex()?;
+    create_refresh_token(
+        sql_conn,
+        &RefreshToken {
+            token_hash: hash_refresh_token(&refresh_token),
+            family_id: family_id.to_string(),
+            user_id,
+            sess_id: session.sess_id,
+            issue_date: session.issue_date,
+            expiry_date: session.issue_date + server_config().jwt_refresh_ttl as i64,
+            is_used: false,
+            is_revoked: false,
+        },
+    )
+    .await?;
+
+    Ok(LoginResponse { jwt, refresh_token })
+}
+
+/// Returns whether expired sessions and refresh tokens should be deleted at ```now```, and if so
+/// records ```now``` as the last cleanup, so that only one caller deletes them.
+fn is_cleanup_due(last_cleanup: &AtomicI64, now: i64) -> bool {
+    let last = last_cleanup.load(Ordering::Relaxed);
+    now >= last + CLEANUP_INTERVAL
+        && last_cleanup
+            .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+}
+
+/// Deletes the expired sessions and refresh tokens, failures are only logged.
+async fn delete_expired(sql_conn: &tokio_postgres::Client, now: i64) {
+    match delete_expired_sessions(sql_conn, now).await {
+        Ok(deleted) => info!("SESSIONS_EXPIRED_DELETED: {}", deleted),
+        Err(err) => warn!("SESSIONS_EXPIRED_DELETE_FAILED: {}", err),
+    }
+    match delete_expired_refresh_tokens(sql_conn, now).await {
+        Ok(deleted) => info!("REFRESH_TOKENS_EXPIRED_DELETED: {}", deleted),
+        Err(err) => warn!("REFRESH_TOKENS_EXPIRED_DELETE_FAILED: {}", err),
+    }
+}
+
+/// Hashes a refresh token for storage, the token itself is never stored.
+fn hash_refresh_token(refresh_token: &str) -> String {
+    HEXLOWER.encode(digest::digest(&digest::SHA256, refresh_token.as_bytes()).as_ref())
+}
+
+/// Returns 32 random bytes, in hex.
+fn random_hex() -> Result<String, ReturnFlags> {
+    let mut bytes = [0u8; 32];
+    rand::SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| ReturnFlags::ServerCreateJwtTokenFailed)?;
+    Ok(HEXLOWER.encode(&bytes))
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_cleanup_due() {
+        let last_cleanup = AtomicI64::new(0);
+        assert!(is_cleanup_due(&last_cleanup, 1_000_000));
+        assert!(!is_cleanup_due(&last_cleanup, 1_000_000));
+        assert!(!is_cleanup_due(
+            &last_cleanup,
+            1_000_000 + CLEANUP_INTERVAL - 1
+        ));
+        assert!(is_cleanup_due(&last_cleanup, 1_000_000 + CLEANUP_INTERVAL));
+    }
+}
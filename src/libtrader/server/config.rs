@@ -0,0 +1,700 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::misc::config_file::{read_config_file, ConfigValue};
+use crate::server::market::clock::ReplayClock;
+use crate::server::market::quote_source::QuoteSourceSetting;
+use crate::server::market::simulator::{SimulationModel, SimulatorConfig, MIN_PRICE};
+use crate::server::network::jwt_keys::{JwtAlgorithm, JwtKey, JwtKeySet};
+
+/// Every setting of the server, with its default value if it has one.
+///
+/// Each setting is read from the configuration file under its name, and from the environment
+/// variable of the same name in upper case.
+const SETTINGS: &[(&str, Option<&str>)] = &[
+    ("server_addr", Some("0.0.0.0:4000")),
+    ("tls_cert", Some("certs/certificate.crt")),
+    ("tls_key", Some("certs/private.key")),
+    ("db_host", Some("localhost")),
+    ("db_host_port", Some("5432")),
+    ("db_name", None),
+    ("db_user", None),
+    ("db_pass", None),
+    ("db_acc_user", None),
+    ("db_acc_pass", None),
+    ("db_portfolio_user", None),
+    ("db_portfolio_pass", None),
+    ("jwt_algorithm", Some("HS512")),
+    ("jwt_kid", Some("1")),
+    ("jwt_secret", Some("")),
+    ("jwt_key_file", Some("")),
+    ("jwt_verify_secrets", Some("")),
+    ("jwt_verify_key_files", Some("")),
+    ("jwt_ttl", Some("900")),
+    ("jwt_refresh_ttl", Some("2592000")),
+    ("salt_secret", None),
+    ("market_source", Some("")),
+    ("market_interval", Some("60")),
+    ("sim_model", Some("gbm")),
+    ("sim_drift", Some("0.05")),
+    ("sim_reversion", Some("2")),
+    ("sim_volatility", Some("0.3")),
+    ("sim_jump_intensity", Some("4")),
+    ("sim_jump_size", Some("0.05")),
+    ("sim_spread", Some("0.001")),
+    ("sim_volume", Some("1000")),
+    ("sim_initial_price", Some("100")),
+    ("sim_seed", Some("")),
+    ("replay_start", Some("0")),
+    ("replay_end", Some("0")),
+    ("replay_speed", Some("60")),
+    ("quote_cache_ttl", Some("5")),
+];
+
+/// The settings of the server configuration file, every one optional.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ServerConfigFile {
+    server_addr: Option<ConfigValue>,
+    tls_cert: Option<ConfigValue>,
+    tls_key: Option<ConfigValue>,
+    db_host: Option<ConfigValue>,
+    db_host_port: Option<ConfigValue>,
+    db_name: Option<ConfigValue>,
+    db_user: Option<ConfigValue>,
+    db_pass: Option<ConfigValue>,
+    db_acc_user: Option<ConfigValue>,
+    db_acc_pass: Option<ConfigValue>,
+    db_portfolio_user: Option<ConfigValue>,
+    db_portfolio_pass: Option<ConfigValue>,
+    jwt_algorithm: Option<ConfigValue>,
+    jwt_kid: Option<ConfigValue>,
+    jwt_secret: Option<ConfigValue>,
+    jwt_key_file: Option<ConfigValue>,
+    jwt_verify_secrets: Option<ConfigValue>,
+    jwt_verify_key_files: Option<ConfigValue>,
+    jwt_ttl: Option<ConfigValue>,
+    jwt_refresh_ttl: Option<ConfigValue>,
+    salt_secret: Option<ConfigValue>,
+    market_source: Option<ConfigValue>,
+    market_interval: Option<ConfigValue>,
+    sim_model: Option<ConfigValue>,
+    sim_drift: Option<ConfigValue>,
+    sim_reversion: Option<ConfigValue>,
+    sim_volatility: Option<ConfigValue>,
+    sim_jump_intensity: Option<ConfigValue>,
+    sim_jump_size: Option<ConfigValue>,
+    sim_spread: Option<ConfigValue>,
+    sim_volume: Option<ConfigValue>,
+    sim_initial_price: Option<ConfigValue>,
+    sim_seed: Option<ConfigValue>,
+    replay_start: Option<ConfigValue>,
+    replay_end: Option<ConfigValue>,
+    replay_speed: Option<ConfigValue>,
+    quote_cache_ttl: Option<ConfigValue>,
+}
+
+impl ServerConfigFile {
+    /// Returns the settings set in the file, as text.
+    fn into_settings(self) -> Vec<(String, String)> {
+        /* unset settings are left out of the table */
+        match toml::Value::try_from(self) {
+            Ok(toml::Value::Table(table)) => table
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    let value = value.try_into::<ConfigValue>().ok()?;
+                    Some((key, value.to_string()))
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// The shortest accepted JWT secret, in bytes.
+const JWT_SECRET_MIN_LEN: usize = 32;
+
+static SERVER_CONFIG: OnceLock<ServerConfig> = OnceLock::new();
+
+/// The credentials of a database user.
+#[derive(PartialEq, Clone)]
+pub struct DbCredentials {
+    pub user: String,
+    pub pass: String,
+}
+
+/// The server configuration, loaded and validated once at startup.
+///
+/// Members:
+/// server_addr - The address to accept clients on.
+/// tls_cert - Path to the TLS certificate chain.
+/// tls_key - Path to the TLS private key.
+/// db_host - The host of the postgres database.
+/// db_port - The port of the postgres database.
+/// db_name - The name of the postgres database.
+/// db_admin - The owner of the schemas, used to run migrations.
+/// db_accounts - The user of ```accounts_schema```.
+/// db_portfolio - The user of ```portfolio_schema```.
+/// jwt_keys - The key signing session tokens, and the retired keys still verifying them.
+/// jwt_ttl - How long session tokens are valid, in seconds.
+/// jwt_refresh_ttl - How long refresh tokens are valid, in seconds.
+/// salt_secret - The secret deriving the salts returned for unknown usernames.
+/// market_source - The source of market data to ingest, if any.
+/// market_interval - How often the market data source is polled, in seconds.
+/// market_simulator - The parameters of the market simulator.
+/// market_replay - The market time replayed by the replay source.
+/// quote_cache_ttl - How long quotes are served from memory before they are read again, in
+/// seconds.
+pub struct ServerConfig {
+    pub server_addr: String,
+    pub tls_cert: PathBuf,
+    pub tls_key: PathBuf,
+    pub db_host: String,
+    pub db_port: u16,
+    pub db_name: String,
+    pub db_admin: DbCredentials,
+    pub db_accounts: DbCredentials,
+    pub db_portfolio: DbCredentials,
+    pub jwt_keys: JwtKeySet,
+    pub jwt_ttl: u64,
+    pub jwt_refresh_ttl: u64,
+    pub salt_secret: Vec<u8>,
+    pub market_source: Option<QuoteSourceSetting>,
+    pub market_interval: u64,
+    pub market_simulator: SimulatorConfig,
+    pub market_replay: ReplayClock,
+    pub quote_cache_ttl: u64,
+}
+
+/// Every problem found while loading the server configuration.
+#[derive(PartialEq, Debug)]
+pub struct ConfigError {
+    pub problems: Vec<String>,
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid server configuration:")?;
+        for problem in &self.problems {
+            write!(f, "\n  - {}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl ServerConfig {
+    /// Loads and validates the server configuration.
+    ///
+    /// Settings are taken from, in increasing precedence: their defaults, the configuration
+    /// file, the environment, and ```overrides``` given on the command line.
+    ///
+    /// Arguments:
+    /// path - The configuration file, if any.
+    /// overrides - Settings given on the command line, by setting name.
+    /// serving - Whether the TLS files are needed.
+    ///
+    /// Returns: the configuration on success, and every problem found on error.
+    ///
+    /// Example:
+    /// ```rust
+    ///     let config = ServerConfig::load(Some(Path::new("server.toml")), &[], true)?;
+    /// ```
+    pub fn load(
+        path: Option<&Path>,
+        overrides: &[(&str, Option<String>)],
+        serving: bool,
+    ) -> Result<ServerConfig, ConfigError> {
+        let mut problems = Vec::new();
+        let mut settings: HashMap<String, String> = HashMap::new();
+
+        if let Some(path) = path {
+            match read_config_file::<ServerConfigFile>(path) {
+                Ok(file) => settings.extend(file.into_settings()),
+                Err(err) => problems.push(format!("can not read {}", err)),
+            }
+        }
+
+        for (name, _) in SETTINGS {
+            if let Ok(value) = std::env::var(name.to_uppercase()) {
+                settings.insert(name.to_string(), value);
+            }
+        }
+
+        for (name, value) in overrides {
+            if let Some(value) = value {
+                settings.insert(name.to_string(), value.clone());
+            }
+        }
+
+        let config = ServerConfig::from_settings(&settings, &mut problems);
+        if serving {
+            for path in &[&config.tls_cert, &config.tls_key] {
+                if !path.is_file() {
+                    problems.push(format!("TLS file {} does not exist", path.display()));
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(config)
+        } else {
+            Err(ConfigError { problems })
+        }
+    }
+
+    /// Builds the configuration from resolved settings, recording every problem found.
+    fn from_settings(settings: &HashMap<String, String>, problems: &mut Vec<String>) -> Self {
+        let value = |name: &str| -> String {
+            let default = SETTINGS
+                .iter()
+                .find(|(setting, _)| *setting == name)
+                .and_then(|(_, default)| *default);
+            settings
+                .get(name)
+                .map(String::as_str)
+                .or(default)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let mut get = |name: &str| -> String {
+            let value = value(name);
+            if value.is_empty() {
+                problems.push(format!(
+                    "missing setting `{}` (environment variable {})",
+                    name,
+                    name.to_uppercase()
+                ));
+            }
+            value
+        };
+
+        let server_addr = get("server_addr");
+        let tls_cert = PathBuf::from(get("tls_cert"));
+        let tls_key = PathBuf::from(get("tls_key"));
+        let db_host = get("db_host");
+        let db_port = get("db_host_port");
+        let db_name = get("db_name");
+        let db_admin = DbCredentials {
+            user: get("db_user"),
+            pass: get("db_pass"),
+        };
+        let db_accounts = DbCredentials {
+            user: get("db_acc_user"),
+            pass: get("db_acc_pass"),
+        };
+        let db_portfolio = DbCredentials {
+            user: get("db_portfolio_user"),
+            pass: get("db_portfolio_pass"),
+        };
+        let jwt_algorithm = get("jwt_algorithm");
+        let jwt_ttl = get("jwt_ttl");
+        let jwt_refresh_ttl = get("jwt_refresh_ttl");
+        let market_interval = get("market_interval");
+        let quote_cache_ttl = get("quote_cache_ttl");
+        let salt_secret = get("salt_secret");
+
+        let db_port = match db_port.parse::<u16>() {
+            Ok(port) => port,
+            Err(_) => {
+                if !db_port.is_empty() {
+                    problems.push(format!(
+                        "setting `db_host_port` is not a port number: {}",
+                        db_port
+                    ));
+                }
+                0
+            }
+        };
+        let jwt_keys = jwt_key_set(&jwt_algorithm, &value, problems);
+
+        /* the fake salts of unknown usernames must not change across restarts */
+        if !salt_secret.is_empty() && salt_secret.len() < JWT_SECRET_MIN_LEN {
+            problems.push(format!(
+                "setting `salt_secret` must be at least {} bytes long",
+                JWT_SECRET_MIN_LEN
+            ));
+        }
+        let salt_secret = salt_secret.into_bytes();
+        let jwt_ttl = parse_seconds("jwt_ttl", &jwt_ttl, problems);
+        let jwt_refresh_ttl = parse_seconds("jwt_refresh_ttl", &jwt_refresh_ttl, problems);
+        let market_source = match QuoteSourceSetting::parse(&value("market_source")) {
+            Ok(market_source) => market_source,
+            Err(err) => {
+                problems.push(format!("setting `market_source`: {}", err));
+                None
+            }
+        };
+        let market_interval = parse_seconds("market_interval", &market_interval, problems);
+        let quote_cache_ttl = parse_seconds("quote_cache_ttl", &quote_cache_ttl, problems);
+        let market_simulator = simulator_config(&value, problems);
+        let market_replay = ReplayClock {
+            start_epoch: parse_number("replay_start", &value("replay_start"), 0, problems),
+            end_epoch: parse_number("replay_end", &value("replay_end"), 0, problems),
+            speed: parse_number("replay_speed", &value("replay_speed"), 0.0, problems),
+        };
+        if market_source == Some(QuoteSourceSetting::Replay)
+            && market_replay.start_epoch >= market_replay.end_epoch
+        {
+            problems.push("setting `replay_end` must be after `replay_start`".to_string());
+        }
+
+        ServerConfig {
+            server_addr,
+            tls_cert,
+            tls_key,
+            db_host,
+            db_port,
+            db_name,
+            db_admin,
+            db_accounts,
+            db_portfolio,
+            jwt_keys,
+            jwt_ttl,
+            jwt_refresh_ttl,
+            salt_secret,
+            market_source,
+            market_interval,
+            market_simulator,
+            market_replay,
+            quote_cache_ttl,
+        }
+    }
+}
+
+/// Parses a duration setting, recording a problem if it is not a positive number of seconds.
+fn parse_seconds(name: &str, value: &str, problems: &mut Vec<String>) -> u64 {
+    match value.parse::<u64>() {
+        Ok(seconds) if seconds > 0 => seconds,
+        _ => {
+            if !value.is_empty() {
+                problems.push(format!(
+                    "setting `{}` is not a positive number of seconds: {}",
+                    name, value
+                ));
+            }
+            0
+        }
+    }
+}
+
+/// Parses a numeric setting, recording a problem if it is not a number of at least ```min```.
+fn parse_number<T: std::str::FromStr + PartialOrd + Default>(
+    name: &str,
+    value: &str,
+    min: T,
+    problems: &mut Vec<String>,
+) -> T {
+    match value.parse::<T>() {
+        Ok(number) if number >= min => number,
+        _ => {
+            problems.push(format!(
+                "setting `{}` is not a valid number: {}",
+                name, value
+            ));
+            T::default()
+        }
+    }
+}
+
+/// Builds the market simulator parameters from the ```sim_*``` settings, recording every
+/// problem found.
+fn simulator_config<F: Fn(&str) -> String>(
+    value: &F,
+    problems: &mut Vec<String>,
+) -> SimulatorConfig {
+    let number = |name: &str, min: f64, problems: &mut Vec<String>| -> f64 {
+        parse_number(name, &value(name), min, problems)
+    };
+    let drift = number("sim_drift", f64::MIN, problems);
+    let speed = number("sim_reversion", 0.0, problems);
+    let volatility = number("sim_volatility", 0.0, problems);
+    let model = match SimulationModel::from_name(&value("sim_model"), drift, speed, volatility) {
+        Some(model) => model,
+        None => {
+            problems.push(format!(
+                "setting `sim_model` must be gbm or mean_reversion: {}",
+                value("sim_model")
+            ));
+            SimulationModel::GeometricBrownian { drift, volatility }
+        }
+    };
+    let seed = match value("sim_seed") {
+        seed if seed.is_empty() => None,
+        seed => Some(parse_number("sim_seed", &seed, 0, problems)),
+    };
+
+    SimulatorConfig {
+        model,
+        jump_intensity: number("sim_jump_intensity", 0.0, problems),
+        jump_size: number("sim_jump_size", 0.0, problems),
+        spread: number("sim_spread", 0.0, problems),
+        volume: parse_number("sim_volume", &value("sim_volume"), 0, problems),
+        initial_price: number("sim_initial_price", MIN_PRICE, problems),
+        seed,
+    }
+}
+
+/// Builds the JWT key set from the ```jwt_*``` settings, recording every problem found.
+///
+/// HS512 signs with ```jwt_secret```, RS256 and EdDSA with the private key in
+/// ```jwt_key_file```, under the key id ```jwt_kid```. Retired keys, listed comma separated as
+/// ```kid=secret``` in ```jwt_verify_secrets``` and ```kid=path``` in
+/// ```jwt_verify_key_files```, keep verifying the tokens they signed until these expire.
+fn jwt_key_set<F: Fn(&str) -> String>(
+    algorithm: &str,
+    value: &F,
+    problems: &mut Vec<String>,
+) -> JwtKeySet {
+    let kid = value("jwt_kid");
+    let verify_secrets = value("jwt_verify_secrets");
+    let verify_secrets = retired_entries("jwt_verify_secrets", &verify_secrets, problems);
+    let verify_key_files = value("jwt_verify_key_files");
+    let verify_key_files = retired_entries("jwt_verify_key_files", &verify_key_files, problems);
+    let mut secret = |name: &str, kid: &str, secret: &str| -> JwtKey {
+        if secret.len() < JWT_SECRET_MIN_LEN {
+            problems.push(format!(
+                "setting `{}` must be at least {} bytes long",
+                name, JWT_SECRET_MIN_LEN
+            ));
+        }
+        JwtKey::from_secret(kid, secret.as_bytes())
+    };
+    let mut retired: Vec<JwtKey> = verify_secrets
+        .iter()
+        .map(|(kid, key)| secret("jwt_verify_secrets", kid, key))
+        .collect();
+
+    let signing = match JwtAlgorithm::from_name(algorithm) {
+        Some(JwtAlgorithm::HS512) => {
+            let jwt_secret = value("jwt_secret");
+            if jwt_secret.is_empty() {
+                problems.push(
+                    "missing setting `jwt_secret` (environment variable JWT_SECRET)".to_string(),
+                );
+                None
+            } else {
+                Some(secret("jwt_secret", &kid, &jwt_secret))
+            }
+        }
+        Some(algorithm) => {
+            let path = value("jwt_key_file");
+            if path.is_empty() {
+                problems.push(format!(
+                    "missing setting `jwt_key_file` (environment variable JWT_KEY_FILE), \
+                     required by {}",
+                    algorithm.name()
+                ));
+                None
+            } else {
+                match JwtKey::from_pem_file(&kid, Path::new(&path)) {
+                    Ok(key) if key.algorithm() != algorithm => {
+                        problems.push(format!(
+                            "setting `jwt_key_file` holds an {} key, not an {} key",
+                            key.algorithm().name(),
+                            algorithm.name()
+                        ));
+                        None
+                    }
+                    Ok(key) => Some(key),
+                    Err(err) => {
+                        problems.push(format!("setting `jwt_key_file`: {}", err));
+                        None
+                    }
+                }
+            }
+        }
+        None => {
+            if !algorithm.is_empty() {
+                problems.push(format!(
+                    "setting `jwt_algorithm` must be HS512, RS256 or EdDSA: {}",
+                    algorithm
+                ));
+            }
+            None
+        }
+    };
+
+    for (kid, path) in verify_key_files {
+        match JwtKey::from_pem_file(kid, Path::new(path)) {
+            Ok(key) => retired.push(key),
+            Err(err) => problems.push(format!("setting `jwt_verify_key_files`: {}", err)),
+        }
+    }
+
+    /* an unusable placeholder, the configuration is rejected with the problems found */
+    let placeholder = || JwtKey::from_secret(&kid, &[]);
+    let signing = signing.unwrap_or_else(placeholder);
+    match JwtKeySet::new(signing, retired) {
+        Ok(keys) => keys,
+        Err(err) => {
+            problems.push(format!("setting `jwt_kid`: {}", err));
+            JwtKeySet::new(placeholder(), Vec::new()).unwrap()
+        }
+    }
+}
+
+/// Splits a comma separated list of retired keys into ```(kid, key)``` pairs, recording the
+/// entries without a key id.
+fn retired_entries<'a>(
+    name: &str,
+    list: &'a str,
+    problems: &mut Vec<String>,
+) -> Vec<(&'a str, &'a str)> {
+    let mut entries = Vec::new();
+    for entry in list
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        match entry.split_once('=') {
+            Some((kid, key)) if !kid.trim().is_empty() => entries.push((kid.trim(), key.trim())),
+            _ => problems.push(format!(
+                "setting `{}` entries must be written kid=key",
+                name
+            )),
+        }
+    }
+    entries
+}
+
+/// Installs the configuration used by the rest of the server.
+///
+/// Must be called once at startup, before any connection is served. Later calls are ignored.
+pub fn set_server_config(config: ServerConfig) {
+    let _ = SERVER_CONFIG.set(config);
+}
+
+/// Returns the configuration installed at startup.
+///
+/// Panics if ```set_server_config()``` was not called.
+pub fn server_config() -> &'static ServerConfig {
+    SERVER_CONFIG
+        .get()
+        .expect("server configuration is not loaded")
+}
+
+/// Installs a complete configuration for tests.
+#[cfg(test)]
+pub fn set_test_server_config() {
+    let settings: HashMap<String, String> = SETTINGS
+        .iter()
+        .map(|(name, default)| (name.to_string(), default.unwrap_or("test").to_string()))
+        .chain(std::iter::once((
+            "jwt_secret".to_string(),
+            "0123456789abcdef0123456789abcdef".to_string(),
+        )))
+        .collect();
+    set_server_config(ServerConfig::from_settings(&settings, &mut Vec::new()));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn settings(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_server_config_from_settings() {
+        let mut problems = Vec::new();
+        let config = ServerConfig::from_settings(
+            &settings(&[
+                ("db_name", "pt_db"),
+                ("db_user", "pt_usr"),
+                ("db_pass", "PASSWORD"),
+                ("db_acc_user", "accounts_schema_usr"),
+                ("db_acc_pass", "PASSWORD"),
+                ("db_portfolio_user", "portfolio_schema_usr"),
+                ("db_portfolio_pass", "PASSWORD"),
+                ("jwt_secret", "0123456789abcdef0123456789abcdef"),
+                ("salt_secret", "fedcba9876543210fedcba9876543210"),
+            ]),
+            &mut problems,
+        );
+        assert!(problems.is_empty(), "{:?}", problems);
+        assert_eq!(config.db_host, "localhost");
+        assert_eq!(config.db_port, 5432);
+        assert_eq!(config.db_accounts.user, "accounts_schema_usr");
+        assert_eq!(config.market_source, None);
+        assert_eq!(config.market_simulator.seed, None);
+        assert_eq!(
+            config.market_simulator.model,
+            SimulationModel::GeometricBrownian {
+                drift: 0.05,
+                volatility: 0.3
+            }
+        );
+    }
+
+    #[test]
+    fn test_server_config_file() {
+        let every_setting: String = SETTINGS
+            .iter()
+            .map(|(name, _)| format!("{} = \"\"\n", name))
+            .collect();
+        assert!(toml::from_str::<ServerConfigFile>(&every_setting).is_ok());
+
+        let file: ServerConfigFile =
+            toml::from_str("db_host_port = 5432 # postgres\ndb_name = \"pt_db\"\n").unwrap();
+        let mut pairs = file.into_settings();
+        pairs.sort();
+        assert_eq!(
+            pairs,
+            vec![
+                ("db_host_port".to_string(), "5432".to_string()),
+                ("db_name".to_string(), "pt_db".to_string()),
+            ]
+        );
+
+        let err = toml::from_str::<ServerConfigFile>("db_name = \"pt_db\"\njwt_secrets = \"\"\n")
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("line 2"), "{}", err);
+    }
+
+    #[test]
+    fn test_server_config_lists_every_problem() {
+        let mut problems = Vec::new();
+        ServerConfig::from_settings(
+            &settings(&[
+                ("db_host_port", "postgres"),
+                ("jwt_secret", "seecreet"),
+                ("jwt_verify_secrets", "0123456789abcdef0123456789abcdef"),
+                ("salt_secret", "seecreet"),
+                ("sim_model", "random_walk"),
+                ("sim_spread", "-1"),
+                ("market_source", "replay"),
+                ("replay_start", "1583020800"),
+            ]),
+            &mut problems,
+        );
+        assert_eq!(
+            problems,
+            vec![
+                "missing setting `db_name` (environment variable DB_NAME)",
+                "missing setting `db_user` (environment variable DB_USER)",
+                "missing setting `db_pass` (environment variable DB_PASS)",
+                "missing setting `db_acc_user` (environment variable DB_ACC_USER)",
+                "missing setting `db_acc_pass` (environment variable DB_ACC_PASS)",
+                "missing setting `db_portfolio_user` (environment variable DB_PORTFOLIO_USER)",
+                "missing setting `db_portfolio_pass` (environment variable DB_PORTFOLIO_PASS)",
+                "setting `db_host_port` is not a port number: postgres",
+                "setting `jwt_verify_secrets` entries must be written kid=key",
+                "setting `jwt_secret` must be at least 32 bytes long",
+                "setting `salt_secret` must be at least 32 bytes long",
+                "setting `sim_model` must be gbm or mean_reversion: random_walk",
+                "setting `sim_spread` is not a valid number: -1",
+                "setting `replay_end` must be after `replay_start`",
+            ]
+        );
+    }
+}
@@ -3,23 +3,25 @@ use crate::common::misc::return_flags::ReturnFlags;
 
 /// Creates a company on the postgres SQL database.
 ///
-/// Takes in a company and writes an entry in public.companies.
+/// Takes in a company and writes an entry in public.companies. The id of the company is assigned
+/// by the database.
 ///
 /// Arguments:
+/// sql_conn - The SQL connection to use.
 /// company - The company to create.
 ///
-/// Returns: the company, a string containing reason of failure on error.
+/// Returns: the company with its new id, ReturnFlags on error.
 ///
 /// Example:
 /// ```rust
-///    match create_company(company) {
-///        Ok(()) => info!("created company"),
+///    match create_company(&sql_conn, company).await {
+///        Ok(company) => info!("created company {}", company.id),
 ///        Err(err) => error!("Failed to create company with error: {}", err),
 ///    }
 /// ```
 pub async fn create_company(
-    sql_conn: &mut tokio_postgres::Client,
-    company: Company,
+    sql_conn: &tokio_postgres::Client,
+    mut company: Company,
 ) -> Result<Company, ReturnFlags> {
     /*
      * Creates a company entry in database in public.companies.
@@ -27,10 +29,12 @@ pub async fn create_company(
 
     // Insert argument company into public.companies database table.
     match sql_conn
-        .execute(
-            "INSERT INTO public.companies VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        .query_one(
+            "INSERT INTO public.companies \
+                (symbol, isin, company_name, primary_exchange, sector, industry, \
+                primary_sic_code, employees) \
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING id",
             &[
-                &company.id,
                 &company.symbol,
                 &company.isin,
                 &company.company_name,
@@ -43,7 +47,10 @@ pub async fn create_company(
         )
         .await
     {
-        Ok(_row) => Ok(company),
+        Ok(row) => {
+            company.id = row.get(0);
+            Ok(company)
+        }
         Err(_) => Err(ReturnFlags::ServerDbCreateCompanyFailed),
     }
 }
@@ -0,0 +1,68 @@
+use crate::common::account::ledger::LedgerEntry;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::market::clock::market_now;
+
+/// Creates a cash ledger entry on the postgre SQL database
+///
+/// Applies the signed amount to the user's cash balance and records the movement in
+/// accounts_schema.ledger.
+///
+/// Arguments:
+/// user_id - ID of the user whose cash moves.
+/// transaction_id - The transaction causing the movement, if any.
+/// amount - The signed amount, positive credits and negative debits.
+///
+/// Returns: the created ledger entry on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     match create_ledger_entry(&sql_transaction, user_id, Some(transaction_id), -1234.5).await {
+///         Ok(entry) => println!("new balance {}", entry.balance),
+///         Err(err) => panic!("TEST_CMD_CREATE_LEDGER_ENTRY_FAILED: {}", err)
+///     }
+/// ```
+pub async fn create_ledger_entry(
+    sql_conn: &tokio_postgres::Transaction<'_>,
+    user_id: i64,
+    transaction_id: Option<i64>,
+    amount: f64,
+) -> Result<LedgerEntry, ReturnFlags> {
+    /*
+     * Updates the cash balance in accounts_schema.accounts and creates the
+     * matching entry in accounts_schema.ledger.
+     * */
+    let time_epoch = market_now();
+
+    /* update balance */
+    let balance: f64 = match sql_conn
+        .query_one(
+            "UPDATE accounts_schema.accounts SET cash_balance = cash_balance + $2 
+                         WHERE id = $1 RETURNING cash_balance",
+            &[&user_id, &amount],
+        )
+        .await
+    {
+        Ok(row) => row.get(0),
+        Err(_) => return Err(ReturnFlags::ServerDbCreateLedgerEntryFailed),
+    };
+
+    /* insert ledger entry */
+    match sql_conn
+        .execute(
+            "INSERT INTO accounts_schema.ledger 
+                         (user_id, transaction_id, amount, balance, time_epoch) 
+                         VALUES ($1, $2, $3, $4, $5)",
+            &[&user_id, &transaction_id, &amount, &balance, &time_epoch],
+        )
+        .await
+    {
+        Ok(_rows) => Ok(LedgerEntry {
+            transaction_id,
+            amount,
+            balance,
+            time_epoch,
+        }),
+        Err(_) => Err(ReturnFlags::ServerDbCreateLedgerEntryFailed),
+    }
+}
@@ -0,0 +1,55 @@
+use crate::common::account::order::Order;
+use crate::common::misc::return_flags::ReturnFlags;
+
+/// Creates an order on the postgre SQL database
+///
+/// Takes in the order to insert to the database, the id of the order is ignored.
+///
+/// Arguments:
+/// user_id - ID of the user placing the order.
+/// order - The order to use.
+///
+/// Returns: the id of the created order on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     match create_order(&sql_conn, user_id, &order).await {
+///         Ok(id) => order.id = id,
+///         Err(err) => panic!("TEST_CMD_CREATE_ORDER_FAILED: {}", err)
+///     }
+/// ```
+pub async fn create_order(
+    sql_conn: &tokio_postgres::Client,
+    user_id: i64,
+    order: &Order,
+) -> Result<i64, ReturnFlags> {
+    /*
+     * Creates an order entry in database in portfolio_schema.orders.
+     * */
+    match sql_conn
+        .query_one(
+            "INSERT INTO portfolio_schema.orders 
+                         (user_id, stock_symbol, order_type, is_buy, stock_price, stop_price, stock_amount,
+                         stock_filled, is_triggered, is_filled, is_cancelled, create_epoch)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) RETURNING id",
+            &[
+                &user_id,
+                &order.stock_symbol,
+                &(order.order_type as i16),
+                &order.is_buy,
+                &order.stock_price,
+                &order.stop_price,
+                &order.stock_amount,
+                &order.stock_filled,
+                &order.is_triggered,
+                &order.is_filled,
+                &order.is_cancelled,
+                &order.create_epoch,
+            ],
+        )
+        .await
+    {
+        Ok(row) => Ok(row.get(0)),
+        Err(_) => Err(ReturnFlags::ServerDbCreateOrderFailed),
+    }
+}
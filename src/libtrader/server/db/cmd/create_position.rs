@@ -9,6 +9,8 @@ use crate::common::misc::return_flags::ReturnFlags;
 /// user_id - ID to create position for.
 /// position - The position to use.
 ///
+/// Returns: the id of the created position on success, ReturnFlags on error.
+///
 /// Example:
 /// ```rust
 ///     match create_position(Position::default()) {
@@ -17,23 +19,23 @@ use crate::common::misc::return_flags::ReturnFlags;
 ///     }
 /// ```
 pub async fn create_position(
-    sql_conn: &mut tokio_postgres::Client,
+    sql_conn: &tokio_postgres::Transaction<'_>,
     user_id: i64,
-    position: Position,
-) -> Result<(), ReturnFlags> {
+    position: &Position,
+) -> Result<i64, ReturnFlags> {
     /*
      * Creates a position entry in database in portfolio_schema.positions.
      * */
 
     /* insert position */
-    match sql_conn.execute("INSERT INTO portfolio_schema.positions 
+    match sql_conn.query_one("INSERT INTO portfolio_schema.positions 
                          (user_id, stock_symbol, stock_open_amount, stock_open_price, stock_open_cost,
-                         stock_close_amount, stock_close_price, open_epoch, close_epoch, is_buy, is_open)
-                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+                         stock_close_amount, stock_close_price, stock_close_cost, open_epoch, close_epoch, is_buy, is_open)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) RETURNING id",
                          &[&user_id, &position.stock_symbol, &position.stock_open_amount, &position.stock_open_price,
-                         &position.stock_open_cost, &position.stock_close_amount, &position.stock_close_price,
+                         &position.stock_open_cost, &position.stock_close_amount, &position.stock_close_price, &position.stock_close_cost,
                          &position.open_epoch, &position.close_epoch, &position.is_buy, &position.is_open]).await {
-        Ok(_rows) => Ok(()),
+        Ok(row) => Ok(row.get(0)),
         Err(_) => Err(ReturnFlags::ServerDbCreatePositionFailed),
     }
 }
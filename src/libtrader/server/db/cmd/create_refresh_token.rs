@@ -0,0 +1,50 @@
+use crate::common::account::session::RefreshToken;
+use crate::common::misc::return_flags::ReturnFlags;
+
+/// Records a refresh token on the postgre SQL database
+///
+/// Refresh tokens of the same user that have expired are removed at the same time.
+///
+/// Arguments:
+/// token - The refresh token to record.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     create_refresh_token(&sql_conn, &token).await?;
+/// ```
+pub async fn create_refresh_token(
+    sql_conn: &tokio_postgres::Client,
+    token: &RefreshToken,
+) -> Result<(), ReturnFlags> {
+    sql_conn
+        .execute(
+            "DELETE FROM sessions_schema.refresh_tokens WHERE user_id = $1 AND expiry_date < $2",
+            &[&token.user_id, &token.issue_date],
+        )
+        .await
+        .map_err(|_| ReturnFlags::ServerDbCreateRefreshTokenFailed)?;
+
+    match sql_conn
+        .execute(
+            "INSERT INTO sessions_schema.refresh_tokens 
+                         (token_hash, family_id, user_id, sess_id, issue_date, expiry_date, is_used, is_revoked)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            &[
+                &token.token_hash,
+                &token.family_id,
+                &token.user_id,
+                &token.sess_id,
+                &token.issue_date,
+                &token.expiry_date,
+                &token.is_used,
+                &token.is_revoked,
+            ],
+        )
+        .await
+    {
+        Ok(1) => Ok(()),
+        _ => Err(ReturnFlags::ServerDbCreateRefreshTokenFailed),
+    }
+}
@@ -0,0 +1,48 @@
+use crate::common::account::session::SessionID;
+use crate::common::misc::return_flags::ReturnFlags;
+
+/// Records a session issued at login on the postgre SQL database
+///
+/// Sessions of the same user that have expired are removed at the same time.
+///
+/// Arguments:
+/// session - The session to record.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     create_session(&sql_conn, &session).await?;
+/// ```
+pub async fn create_session(
+    sql_conn: &tokio_postgres::Client,
+    session: &SessionID,
+) -> Result<(), ReturnFlags> {
+    sql_conn
+        .execute(
+            "DELETE FROM sessions_schema.sessions WHERE user_id = $1 AND expiry_date < $2",
+            &[&session.user_id, &session.issue_date],
+        )
+        .await
+        .map_err(|_| ReturnFlags::ServerDbCreateSessionFailed)?;
+
+    match sql_conn
+        .execute(
+            "INSERT INTO sessions_schema.sessions 
+                         (sess_id, user_id, client_ip, issue_date, expiry_date, is_active)
+                         VALUES ($1, $2, $3, $4, $5, $6)",
+            &[
+                &session.sess_id,
+                &session.user_id,
+                &session.client_ip.to_string(),
+                &session.issue_date,
+                &session.expiry_date,
+                &session.is_active,
+            ],
+        )
+        .await
+    {
+        Ok(1) => Ok(()),
+        _ => Err(ReturnFlags::ServerDbCreateSessionFailed),
+    }
+}
@@ -1,45 +1,45 @@
+use crate::common::generic::stock_val::StockVal;
 use crate::common::misc::return_flags::ReturnFlags;
 
-/// Creates a stock on the postgres SQL database.
+/// Creates a stock quote on the postgres SQL database.
 ///
-/// Takes in a stock name and creates a table in the ```asset_schema``` schema
+/// Takes in a stock value and inserts it into the ```asset_schema.quotes``` table. A quote for an
+/// already stored symbol and time epoch is left untouched.
 ///
 /// Arguments:
-/// stock_name - The name of the stock to create.
+/// sql_conn - The SQL connection to use.
+/// stock_val - The quote to store, keyed by its symbol and time epoch.
 ///
-/// Returns: nothing on success, a string containing the reason of failure on error.
+/// Returns: nothing on success, ReturnFlags on error.
 ///
 /// Example:
 /// ```rust
-///    match create_stock("AAPL") {
-///        Ok(()) => info!("created stock table"),
-///        Err(err) => error!("failed to create stock table {}", err),
+///    match create_stock_val(&sql_conn, &val).await {
+///        Ok(()) => info!("stored quote"),
+///        Err(err) => error!("failed to store quote {}", err),
 ///    }
 /// ```
-pub async fn create_stock(
-    sql_conn: &mut tokio_postgres::Client,
-    stock_name: &str,
+pub async fn create_stock_val(
+    sql_conn: &tokio_postgres::Client,
+    stock_val: &StockVal,
 ) -> Result<(), ReturnFlags> {
     /*
-     * Creates a stock table in database in assets schema.
+     * Inserts a quote into the quotes table in assets schema.
      */
-
-    // Create the table.
     match sql_conn
         .execute(
-            format!(
-                "CREATE TABLE asset_schema.{} ( \
-                        id                  BIGSERIAL PRIMARY KEY, \
-                        isin                TEXT NOT NULL, \
-                        time_epoch          BIGINT NOT NULL, \
-                        ask_price           DOUBLE PRECISION NOT NULL, \
-                        bid_price           DOUBLE PRECISION NOT NULL, \
-                        volume              BIGINT NOT NULL \
-                )",
-                stock_name
-            )
-            .as_str(),
-            &[],
+            "INSERT INTO asset_schema.quotes \
+                (symbol, isin, time_epoch, ask_price, bid_price, volume) \
+                VALUES ($1, $2, $3, $4, $5, $6) \
+                ON CONFLICT (symbol, time_epoch) DO NOTHING",
+            &[
+                &stock_val.symbol,
+                &stock_val.isin,
+                &stock_val.time_epoch,
+                &stock_val.ask_price,
+                &stock_val.bid_price,
+                &stock_val.volume,
+            ],
         )
         .await
     {
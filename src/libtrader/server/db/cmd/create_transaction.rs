@@ -9,6 +9,8 @@ use crate::common::misc::return_flags::ReturnFlags;
 /// user_id - ID to use for the new transaction.
 /// transaction - The transaction to use.
 ///
+/// Returns: the id of the created transaction on success, ReturnFlags on error.
+///
 /// Example:
 /// ```rust
 ///     match create_transaction(Position::default()) {
@@ -17,20 +19,20 @@ use crate::common::misc::return_flags::ReturnFlags;
 ///     }
 /// ```
 pub async fn create_transaction(
-    sql_conn: &mut tokio_postgres::Client,
+    sql_conn: &tokio_postgres::Transaction<'_>,
     user_id: i64,
     transaction: &Transaction,
-) -> Result<(), ReturnFlags> {
+) -> Result<i64, ReturnFlags> {
     /*
      * Creates a transaction entry in database in accounts_schema.transactions.
      * */
 
     /* insert position */
     match sql_conn
-        .execute(
+        .query_one(
             "INSERT INTO accounts_schema.transactions 
                          (user_id, stock_symbol, shares_size, shares_cost, is_buy) 
-                         VALUES ($1, $2, $3, $4, $5)",
+                         VALUES ($1, $2, $3, $4, $5) RETURNING id",
             &[
                 &user_id,
                 &transaction.stock_symbol,
@@ -41,7 +43,7 @@ pub async fn create_transaction(
         )
         .await
     {
-        Ok(_rows) => Ok(()),
+        Ok(row) => Ok(row.get(0)),
         Err(_) => Err(ReturnFlags::ServerDbCreateTransactionFailed),
     }
 }
@@ -0,0 +1,60 @@
+use crate::common::misc::return_flags::ReturnFlags;
+
+/// Deletes an account and everything it owns from the postgre SQL database
+///
+/// Removes the positions, orders, ledger entries, transactions, sessions and refresh tokens of
+/// the account, then the account itself, in a single transaction.
+///
+/// Arguments:
+/// sql_conn - A connection as ```accounts_schema_usr```.
+/// user_id - ID of the account to delete.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     delete_account(&mut sql_conn, user_id).await?;
+/// ```
+pub async fn delete_account(
+    sql_conn: &mut tokio_postgres::Client,
+    user_id: i64,
+) -> Result<(), ReturnFlags> {
+    let transaction = sql_conn
+        .transaction()
+        .await
+        .map_err(|_| ReturnFlags::ServerDeleteAccountFailed)?;
+
+    /* the ledger references the transactions, so it goes first */
+    for table in &[
+        "portfolio_schema.positions",
+        "portfolio_schema.orders",
+        "accounts_schema.ledger",
+        "accounts_schema.transactions",
+        "sessions_schema.refresh_tokens",
+        "sessions_schema.sessions",
+    ] {
+        transaction
+            .execute(
+                format!("DELETE FROM {} WHERE user_id = $1", table).as_str(),
+                &[&user_id],
+            )
+            .await
+            .map_err(|_| ReturnFlags::ServerDeleteAccountFailed)?;
+    }
+
+    match transaction
+        .execute(
+            "DELETE FROM accounts_schema.accounts WHERE id = $1",
+            &[&user_id],
+        )
+        .await
+    {
+        Ok(1) => {}
+        _ => return Err(ReturnFlags::ServerDeleteAccountFailed),
+    }
+
+    transaction
+        .commit()
+        .await
+        .map_err(|_| ReturnFlags::ServerDeleteAccountFailed)
+}
@@ -19,7 +19,7 @@ use crate::common::misc::return_flags::ReturnFlags;
 ///    }
 /// ```
 pub async fn get_company_from_db(
-    sql_conn: &mut tokio_postgres::Client,
+    sql_conn: &tokio_postgres::Client,
     searched_symbol: &str,
 ) -> Result<Company, ReturnFlags> {
     /*
@@ -33,20 +33,47 @@ pub async fn get_company_from_db(
         )
         .await
     {
-        Ok(row) => {
-            let mut found_company: Company = Company::default();
-            found_company.id = row[0].get(0);
-            found_company.symbol = row[0].get(1);
-            found_company.isin = row[0].get(2);
-            found_company.company_name = row[0].get(3);
-            found_company.primary_exchange = row[0].get(4);
-            found_company.sector = row[0].get(5);
-            found_company.industry = row[0].get(6);
-            found_company.primary_sic_code = row[0].get(7);
-            found_company.employees = row[0].get(8);
+        Ok(row) if !row.is_empty() => Ok(company_from_row(&row[0])),
+        _ => Err(ReturnFlags::ServerDbSearchCompanyNotFound),
+    }
+}
 
-            return Ok(found_company);
-        }
+/// Returns every company from the postgres SQL database.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+///
+/// Returns: the companies ordered by symbol on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///    for company in get_companies_from_db(&sql_conn).await? {
+///        info!("found {}", company.symbol);
+///    }
+/// ```
+pub async fn get_companies_from_db(
+    sql_conn: &tokio_postgres::Client,
+) -> Result<Vec<Company>, ReturnFlags> {
+    match sql_conn
+        .query("SELECT * FROM public.companies ORDER BY symbol", &[])
+        .await
+    {
+        Ok(rows) => Ok(rows.iter().map(company_from_row).collect()),
         Err(_) => Err(ReturnFlags::ServerDbSearchCompanyNotFound),
     }
 }
+
+/// Builds a Company from a row of ```public.companies```.
+fn company_from_row(row: &tokio_postgres::Row) -> Company {
+    Company {
+        id: row.get(0),
+        symbol: row.get(1),
+        isin: row.get(2),
+        company_name: row.get(3),
+        primary_exchange: row.get(4),
+        sector: row.get(5),
+        industry: row.get(6),
+        primary_sic_code: row.get(7),
+        employees: row.get(8),
+    }
+}
@@ -0,0 +1,123 @@
+use crate::common::account::order::{Order, OrderType};
+use crate::common::misc::return_flags::ReturnFlags;
+
+static ORDER_COLUMNS: &str = "id, stock_symbol, order_type, is_buy, stock_price, stop_price, \
+                              stock_amount, stock_filled, is_triggered, is_filled, is_cancelled, \
+                              create_epoch";
+
+/// Converts a portfolio_schema.orders row selected with ```ORDER_COLUMNS``` to an Order.
+pub fn order_from_row(row: &tokio_postgres::Row) -> Order {
+    Order {
+        id: row.get(0),
+        stock_symbol: row.get(1),
+        order_type: OrderType::from_i16(row.get(2)).unwrap_or_default(),
+        is_buy: row.get(3),
+        stock_price: row.get(4),
+        stop_price: row.get(5),
+        stock_amount: row.get(6),
+        stock_filled: row.get(7),
+        is_triggered: row.get(8),
+        is_filled: row.get(9),
+        is_cancelled: row.get(10),
+        create_epoch: row.get(11),
+    }
+}
+
+/// Returns all orders of a user from the postgres SQL database.
+///
+/// Arguments:
+/// user_id - ID of the user to get the orders of.
+///
+/// Returns: a Vec<Order> ordered by placement on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let orders = get_orders_from_db(&sql_conn, user_id).await?;
+/// ```
+pub async fn get_orders_from_db(
+    sql_conn: &tokio_postgres::Client,
+    user_id: i64,
+) -> Result<Vec<Order>, ReturnFlags> {
+    match sql_conn
+        .query(
+            format!(
+                "SELECT {} FROM portfolio_schema.orders WHERE user_id = $1 ORDER BY id",
+                ORDER_COLUMNS
+            )
+            .as_str(),
+            &[&user_id],
+        )
+        .await
+    {
+        Ok(all_rows) => Ok(all_rows.iter().map(order_from_row).collect()),
+        Err(_) => Err(ReturnFlags::ServerDbSearchOrderNotFound),
+    }
+}
+
+/// Returns all working orders from the postgres SQL database.
+///
+/// Working orders are neither filled nor cancelled. Each order is returned with the id of the
+/// user that placed it.
+///
+/// Returns: a Vec<(user_id, Order)> ordered by placement on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     for (user_id, order) in get_working_orders_from_db(&sql_conn).await? {
+///         /* match the order */
+///     }
+/// ```
+pub async fn get_working_orders_from_db(
+    sql_conn: &tokio_postgres::Client,
+) -> Result<Vec<(i64, Order)>, ReturnFlags> {
+    match sql_conn
+        .query(
+            format!(
+                "SELECT {}, user_id FROM portfolio_schema.orders 
+                 WHERE NOT is_filled AND NOT is_cancelled ORDER BY id",
+                ORDER_COLUMNS
+            )
+            .as_str(),
+            &[],
+        )
+        .await
+    {
+        Ok(all_rows) => Ok(all_rows
+            .iter()
+            .map(|row| (row.get(12), order_from_row(row)))
+            .collect()),
+        Err(_) => Err(ReturnFlags::ServerDbSearchOrderNotFound),
+    }
+}
+
+/// Returns a working order and locks it until the SQL transaction ends.
+///
+/// Arguments:
+/// order_id - ID of the order to lock.
+///
+/// Returns: the order if it is still working, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let order = get_working_order_for_update(&sql_transaction, order.id).await?;
+/// ```
+pub async fn get_working_order_for_update(
+    sql_conn: &tokio_postgres::Transaction<'_>,
+    order_id: i64,
+) -> Result<Order, ReturnFlags> {
+    match sql_conn
+        .query_opt(
+            format!(
+                "SELECT {} FROM portfolio_schema.orders 
+                 WHERE id = $1 AND NOT is_filled AND NOT is_cancelled FOR UPDATE",
+                ORDER_COLUMNS
+            )
+            .as_str(),
+            &[&order_id],
+        )
+        .await
+    {
+        Ok(Some(row)) => Ok(order_from_row(&row)),
+        _ => Err(ReturnFlags::ServerDbSearchOrderNotFound),
+    }
+}
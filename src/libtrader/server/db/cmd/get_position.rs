@@ -0,0 +1,107 @@
+use crate::common::account::position::Position;
+use crate::common::misc::return_flags::ReturnFlags;
+
+pub static POSITION_COLUMNS: &str = "id, stock_symbol, stock_open_amount, stock_open_price, \
+                                     stock_open_cost, stock_close_amount, stock_close_price, \
+                                     stock_close_cost, open_epoch, close_epoch, is_buy, is_open";
+
+/// Converts a portfolio_schema.positions row selected with ```POSITION_COLUMNS``` to a Position.
+pub fn position_from_row(row: &tokio_postgres::Row) -> Position {
+    Position {
+        id: row.get(0),
+        stock_symbol: row.get(1),
+        stock_open_amount: row.get(2),
+        stock_open_price: row.get(3),
+        stock_open_cost: row.get(4),
+        stock_close_amount: row.get(5),
+        stock_close_price: row.get(6),
+        stock_close_cost: row.get(7),
+        open_epoch: row.get(8),
+        close_epoch: row.get(9),
+        is_buy: row.get(10),
+        is_open: row.get(11),
+    }
+}
+
+/// Returns an open position of a user and locks it until the SQL transaction ends.
+///
+/// Arguments:
+/// user_id - ID of the user owning the position.
+/// position_id - ID of the position to lock.
+///
+/// Returns: the position if it is still open, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let position = get_open_position_for_update(&sql_transaction, user_id, position_id).await?;
+/// ```
+pub async fn get_open_position_for_update(
+    sql_conn: &tokio_postgres::Transaction<'_>,
+    user_id: i64,
+    position_id: i64,
+) -> Result<Position, ReturnFlags> {
+    match sql_conn
+        .query_opt(
+            format!(
+                "SELECT {} FROM portfolio_schema.positions 
+                 WHERE id = $1 AND user_id = $2 AND is_open FOR UPDATE",
+                POSITION_COLUMNS
+            )
+            .as_str(),
+            &[&position_id, &user_id],
+        )
+        .await
+    {
+        Ok(Some(row)) => Ok(position_from_row(&row)),
+        _ => Err(ReturnFlags::ServerDbSearchPositionNotFound),
+    }
+}
+
+/// The open positions of a user, valued at their open price.
+///
+/// Members:
+/// long - The cost of the shares still held long.
+/// short - The proceeds of the shares still sold short.
+#[derive(PartialEq, Debug, Default)]
+pub struct OpenExposure {
+    pub long: f64,
+    pub short: f64,
+}
+
+/// Returns the long and short exposure of the open positions of a user.
+///
+/// Partially closed positions only count the shares still open.
+///
+/// Arguments:
+/// user_id - ID of the user owning the positions.
+///
+/// Returns: the open exposure on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let exposure = get_open_exposure(&sql_transaction, user_id).await?;
+///     let equity = cash + exposure.long - exposure.short;
+/// ```
+pub async fn get_open_exposure(
+    sql_conn: &tokio_postgres::Transaction<'_>,
+    user_id: i64,
+) -> Result<OpenExposure, ReturnFlags> {
+    match sql_conn
+        .query_one(
+            "SELECT 
+                 COALESCE(SUM(stock_open_price * (stock_open_amount - stock_close_amount)) 
+                     FILTER (WHERE is_buy), 0), 
+                 COALESCE(SUM(stock_open_price * (stock_open_amount - stock_close_amount)) 
+                     FILTER (WHERE NOT is_buy), 0) 
+             FROM portfolio_schema.positions WHERE user_id = $1 AND is_open",
+            &[&user_id],
+        )
+        .await
+    {
+        Ok(row) => Ok(OpenExposure {
+            long: row.get(0),
+            short: row.get(1),
+        }),
+        Err(_) => Err(ReturnFlags::ServerDbSearchPositionNotFound),
+    }
+}
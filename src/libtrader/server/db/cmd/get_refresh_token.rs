@@ -0,0 +1,51 @@
+use crate::common::account::session::RefreshToken;
+use crate::common::misc::return_flags::ReturnFlags;
+
+/// The columns of ```sessions_schema.refresh_tokens```, in the order read by
+/// ```refresh_token_from_row()```.
+pub static REFRESH_TOKEN_COLUMNS: &str =
+    "token_hash, family_id, user_id, sess_id, issue_date, expiry_date, is_used, is_revoked";
+
+/// Builds a refresh token from a row selecting ```REFRESH_TOKEN_COLUMNS```.
+pub fn refresh_token_from_row(row: &tokio_postgres::Row) -> RefreshToken {
+    RefreshToken {
+        token_hash: row.get(0),
+        family_id: row.get(1),
+        user_id: row.get(2),
+        sess_id: row.get(3),
+        issue_date: row.get(4),
+        expiry_date: row.get(5),
+        is_used: row.get(6),
+        is_revoked: row.get(7),
+    }
+}
+
+/// Returns a refresh token from the postgre SQL database
+///
+/// Arguments:
+/// token_hash - The SHA-256 of the refresh token, in hex.
+///
+/// Returns: the refresh token on success, nothing if there is no such token, ReturnFlags on
+/// error.
+///
+/// Example:
+/// ```rust
+///     let token = get_refresh_token(&sql_conn, &token_hash).await?;
+/// ```
+pub async fn get_refresh_token(
+    sql_conn: &tokio_postgres::Client,
+    token_hash: &str,
+) -> Result<Option<RefreshToken>, ReturnFlags> {
+    sql_conn
+        .query_opt(
+            format!(
+                "SELECT {} FROM sessions_schema.refresh_tokens WHERE token_hash = $1",
+                REFRESH_TOKEN_COLUMNS
+            )
+            .as_str(),
+            &[&token_hash],
+        )
+        .await
+        .map(|row| row.as_ref().map(refresh_token_from_row))
+        .map_err(|_| ReturnFlags::ServerDbGetRefreshTokenFailed)
+}
@@ -0,0 +1,41 @@
+use crate::common::account::session::SessionID;
+use crate::common::misc::return_flags::ReturnFlags;
+
+/// Returns a session from the postgre SQL database
+///
+/// Arguments:
+/// sess_id - The id of the session, the ```jti``` of its token.
+///
+/// Returns: the session on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let session = get_session(&sql_conn, &claim.jti).await?;
+///     assert_eq!(session.is_active, true);
+/// ```
+pub async fn get_session(
+    sql_conn: &tokio_postgres::Client,
+    sess_id: &str,
+) -> Result<SessionID, ReturnFlags> {
+    let row = sql_conn
+        .query_opt(
+            "SELECT sess_id, user_id, client_ip, issue_date, expiry_date, is_active 
+                         FROM sessions_schema.sessions WHERE sess_id = $1",
+            &[&sess_id],
+        )
+        .await
+        .map_err(|_| ReturnFlags::ServerDbGetSessionFailed)?
+        .ok_or(ReturnFlags::ServerDbSearchSessionNotFound)?;
+
+    let client_ip: String = row.get(2);
+    Ok(SessionID {
+        sess_id: row.get(0),
+        user_id: row.get(1),
+        client_ip: client_ip
+            .parse()
+            .map_err(|_| ReturnFlags::ServerDbGetSessionFailed)?,
+        issue_date: row.get(3),
+        expiry_date: row.get(4),
+        is_active: row.get(5),
+    })
+}
@@ -1,18 +1,34 @@
 use crate::common::generic::stock_val::StockVal;
 use crate::common::misc::return_flags::ReturnFlags;
 
+/// The columns of ```asset_schema.quotes``` in the order read by [`stock_val_from_row`].
+pub static STOCK_VAL_COLUMNS: &str = "id, symbol, isin, time_epoch, ask_price, bid_price, volume";
+
+/// Builds a StockVal from a row selected with [`STOCK_VAL_COLUMNS`].
+pub fn stock_val_from_row(row: &tokio_postgres::Row) -> StockVal {
+    StockVal {
+        id: row.get(0),
+        symbol: row.get(1),
+        isin: row.get(2),
+        time_epoch: row.get(3),
+        ask_price: row.get(4),
+        bid_price: row.get(5),
+        volume: row.get(6),
+    }
+}
+
 /// Returns the whole stock data from the postgres SQL database.
 ///
 /// Takes in a stock symbol and returns the whole data entries of the searched stock.
 ///
 /// Arguments:
-/// searched_symbol - The name of the stock table.
+/// searched_symbol - The symbol of the stock.
 ///
 /// Returns: a Vec<StockVal> on success, and a string containing the reason of failure on error.
 ///
 /// Example:
 /// ```rust
-///    match get_stock_from_db("AAPL".into()) {
+///    match get_stock_from_db(&sql_conn, "AAPL").await {
 ///         Ok(vals) => {
 ///             /* do something with the values */
 ///         },
@@ -20,35 +36,24 @@ use crate::common::misc::return_flags::ReturnFlags;
 ///   };
 /// ```
 pub async fn get_stock_from_db(
-    sql_conn: &mut tokio_postgres::Client,
+    sql_conn: &tokio_postgres::Client,
     searched_symbol: &str,
 ) -> Result<Vec<StockVal>, ReturnFlags> {
     /*
      * Returns all stock values from database.
      */
-
-    // Query database for table.
-    let mut stocks: Vec<StockVal> = Vec::new();
     match sql_conn
         .query(
-            format!("SELECT * FROM asset_schema.{}", searched_symbol).as_str(),
-            &[],
+            format!(
+                "SELECT {} FROM asset_schema.quotes WHERE symbol = $1 ORDER BY time_epoch",
+                STOCK_VAL_COLUMNS
+            )
+            .as_str(),
+            &[&searched_symbol],
         )
         .await
     {
-        Ok(all_rows) => {
-            for row in all_rows {
-                let mut val: StockVal = StockVal::default();
-                val.id = row.get(0);
-                val.isin = row.get(1);
-                val.time_epoch = row.get(2);
-                val.ask_price = row.get(3);
-                val.bid_price = row.get(4);
-                val.volume = row.get(5);
-                stocks.push(val);
-            }
-            Ok(stocks)
-        }
+        Ok(all_rows) => Ok(all_rows.iter().map(stock_val_from_row).collect()),
         Err(_) => Err(ReturnFlags::ServerDbSearchStockNotFound),
     }
 }
@@ -58,14 +63,14 @@ pub async fn get_stock_from_db(
 /// Takes in a stock symbol and returns the data entries after a specified epoch of the searched stock.
 ///
 /// Arguments:
-/// searched_symbol - The name of the stock table.
+/// searched_symbol - The symbol of the stock.
 /// time_epoch - The time from which the stock data retrieved.
 ///
 /// Returns: a Vec<StockVal> on success, and a string containing the reason of failure on error.
 ///
 /// Example:
 /// ```rust
-///     match get_stock_from_db_since_epoch("AAPL".into(), 123456) {
+///     match get_stock_from_db_since_epoch(&sql_conn, "AAPL", 123456).await {
 ///         Ok(vals) => {
 ///             /* do something with the filtered values */
 ///         },
@@ -73,40 +78,26 @@ pub async fn get_stock_from_db(
 ///     };
 /// ```
 pub async fn get_stock_from_db_since_epoch(
-    sql_conn: &mut tokio_postgres::Client,
+    sql_conn: &tokio_postgres::Client,
     searched_symbol: &str,
     time_epoch: i64,
 ) -> Result<Vec<StockVal>, ReturnFlags> {
     /*
      * Returns all stock values from database since a time epoch.
      */
-
-    // Query database for table.
-    let mut stocks: Vec<StockVal> = Vec::new();
     match sql_conn
         .query(
             format!(
-                "SELECT * FROM asset_schema.{} WHERE time_epoch >= {}",
-                searched_symbol, time_epoch
+                "SELECT {} FROM asset_schema.quotes \
+                    WHERE symbol = $1 AND time_epoch >= $2 ORDER BY time_epoch",
+                STOCK_VAL_COLUMNS
             )
             .as_str(),
-            &[],
+            &[&searched_symbol, &time_epoch],
         )
         .await
     {
-        Ok(all_rows) => {
-            for row in all_rows {
-                let mut val: StockVal = StockVal::default();
-                val.id = row.get(0);
-                val.isin = row.get(1);
-                val.time_epoch = row.get(2);
-                val.ask_price = row.get(3);
-                val.bid_price = row.get(4);
-                val.volume = row.get(5);
-                stocks.push(val);
-            }
-            Ok(stocks)
-        }
+        Ok(all_rows) => Ok(all_rows.iter().map(stock_val_from_row).collect()),
         Err(_) => Err(ReturnFlags::ServerDbSearchStockNotFound),
     }
 }
@@ -117,15 +108,16 @@ pub async fn get_stock_from_db_since_epoch(
 /// stock.
 ///
 /// Arguments:
-/// searched_symbol - The name of the stock table.
+/// searched_symbol - The symbol of the stock.
 /// first_time_epoch - The time from which the stock data is first retrieved.
 /// second_time_epoch - The time from which the stock data ends.
+/// max_rows - The most entries returned, the oldest first, or ```None``` for every entry.
 ///
 /// Returns: a Vec<StockVal> on success, and a string containing the reason of failure on error.
 ///
 /// Example:
 /// ```rust
-///    match get_stock_from_db_between_epochs("AAPL".into(), 123456, 123459) {
+///    match get_stock_from_db_between_epochs(&sql_conn, "AAPL", 123456, 123459, None).await {
 ///         Ok(vals) => {
 ///             /* do something with the filtered values */
 ///         },
@@ -133,41 +125,126 @@ pub async fn get_stock_from_db_since_epoch(
 ///   };
 /// ```
 pub async fn get_stock_from_db_between_epochs(
-    sql_conn: &mut tokio_postgres::Client,
+    sql_conn: &tokio_postgres::Client,
     searched_symbol: &str,
     first_time_epoch: i64,
     second_time_epoch: i64,
+    max_rows: Option<i64>,
 ) -> Result<Vec<StockVal>, ReturnFlags> {
     /*
-     * Returns all stock values from database between two time epochs.
+     * Returns all stock values from database between two time epochs, LIMIT NULL is no limit.
      */
+    match sql_conn
+        .query(
+            format!(
+                "SELECT {} FROM asset_schema.quotes \
+                    WHERE symbol = $1 AND time_epoch >= $2 AND time_epoch <= $3 \
+                    ORDER BY time_epoch, id LIMIT $4",
+                STOCK_VAL_COLUMNS
+            )
+            .as_str(),
+            &[
+                &searched_symbol,
+                &first_time_epoch,
+                &second_time_epoch,
+                &max_rows,
+            ],
+        )
+        .await
+    {
+        Ok(all_rows) => Ok(all_rows.iter().map(stock_val_from_row).collect()),
+        Err(_) => Err(ReturnFlags::ServerDbSearchStockNotFound),
+    }
+}
 
-    // Query database for table.
-    let mut stocks: Vec<StockVal> = Vec::new();
+/// Returns the latest stock value from the postgres SQL database.
+///
+/// Takes in a stock symbol and returns the data entry with the highest time epoch of the searched
+/// stock, ignoring entries after ```time_epoch```.
+///
+/// Arguments:
+/// searched_symbol - The symbol of the stock.
+/// time_epoch - The time at which the stock is valued, usually ```market_now()```.
+///
+/// Returns: a StockVal on success, and ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///    match get_stock_from_db_latest(&sql_conn, "AAPL", market_now()).await {
+///         Ok(val) => {
+///             /* price something with the quote */
+///         },
+///         Err(err) => panic!("failed to get the latest stock value, reason: {}", err)
+///   };
+/// ```
+pub async fn get_stock_from_db_latest(
+    sql_conn: &tokio_postgres::Client,
+    searched_symbol: &str,
+    time_epoch: i64,
+) -> Result<StockVal, ReturnFlags> {
+    /*
+     * Returns the most recent stock value from database.
+     */
+    match sql_conn
+        .query_opt(
+            format!(
+                "SELECT {} FROM asset_schema.quotes \
+                    WHERE symbol = $1 AND time_epoch <= $2 ORDER BY time_epoch DESC LIMIT 1",
+                STOCK_VAL_COLUMNS
+            )
+            .as_str(),
+            &[&searched_symbol, &time_epoch],
+        )
+        .await
+    {
+        Ok(Some(row)) => Ok(stock_val_from_row(&row)),
+        _ => Err(ReturnFlags::ServerDbSearchStockNotFound),
+    }
+}
+
+/// Returns the latest stock value of several stocks from the postgres SQL database.
+///
+/// Takes in a list of stock symbols and returns the data entry with the highest time epoch of each
+/// searched stock, ignoring entries after ```time_epoch```. Symbols without any stored quote are
+/// left out.
+///
+/// Arguments:
+/// searched_symbols - The symbols of the stocks.
+/// time_epoch - The time at which the stocks are valued, usually ```market_now()```.
+///
+/// Returns: a Vec<StockVal> ordered by symbol on success, and ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///    match get_stocks_from_db_latest(&sql_conn, &["AAPL".into()], market_now()).await {
+///         Ok(vals) => {
+///             /* price something with the quotes */
+///         },
+///         Err(err) => panic!("failed to get the latest stock values, reason: {}", err)
+///   };
+/// ```
+pub async fn get_stocks_from_db_latest(
+    sql_conn: &tokio_postgres::Client,
+    searched_symbols: &[String],
+    time_epoch: i64,
+) -> Result<Vec<StockVal>, ReturnFlags> {
+    /*
+     * Returns the most recent stock value of every symbol from database.
+     */
     match sql_conn
         .query(
             format!(
-                "SELECT * FROM asset_schema.{} WHERE time_epoch >= {} AND time_epoch <= {}",
-                searched_symbol, first_time_epoch, second_time_epoch
+                "SELECT DISTINCT ON (symbol) {} FROM asset_schema.quotes \
+                    WHERE symbol = ANY($1) AND time_epoch <= $2 \
+                    ORDER BY symbol, time_epoch DESC",
+                STOCK_VAL_COLUMNS
             )
             .as_str(),
-            &[],
+            &[&searched_symbols, &time_epoch],
         )
         .await
     {
-        Ok(all_rows) => {
-            for row in all_rows {
-                let mut val: StockVal = StockVal::default();
-                val.id = row.get(0);
-                val.isin = row.get(1);
-                val.time_epoch = row.get(2);
-                val.ask_price = row.get(3);
-                val.bid_price = row.get(4);
-                val.volume = row.get(5);
-                stocks.push(val);
-            }
-            Ok(stocks)
-        }
+        Ok(all_rows) => Ok(all_rows.iter().map(stock_val_from_row).collect()),
         Err(_) => Err(ReturnFlags::ServerDbSearchStockNotFound),
     }
 }
@@ -0,0 +1,28 @@
+use crate::common::misc::return_flags::ReturnFlags;
+
+/// Returns the cash balance of a user, locking it until the SQL transaction ends.
+///
+/// Arguments:
+/// user_id - ID of the user to get the cash balance for.
+///
+/// Returns: the cash balance on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let buying_power = get_user_cash(&sql_transaction, user_id).await?;
+/// ```
+pub async fn get_user_cash(
+    sql_conn: &tokio_postgres::Transaction<'_>,
+    user_id: i64,
+) -> Result<f64, ReturnFlags> {
+    match sql_conn
+        .query_one(
+            "SELECT cash_balance FROM accounts_schema.accounts WHERE id = $1 FOR UPDATE",
+            &[&user_id],
+        )
+        .await
+    {
+        Ok(row) => Ok(row.get(0)),
+        Err(_) => Err(ReturnFlags::ServerGetUserIdNotFound),
+    }
+}
@@ -1,45 +1,42 @@
 use crate::common::misc::return_flags::ReturnFlags;
 
-use crate::server::db::cmd::user_exists::user_exists;
-
+/// Returns one of the salts of an account.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// username - The username of the account.
+/// is_email - Whether to return the email salt or the password salt.
+/// is_server - Whether to return the server salt or the client salt.
+///
+/// Returns: the hex encoded salt on success, ```ServerDbUserSaltNotFound``` if the account does
+/// not exist, ```ServerDbConnectFailed``` if the database could not be queried.
+///
+/// Example:
+/// ```rust
+///     let salt = get_user_salt(sql_conn, "n1ckn8me", true, false).await?;
+/// ```
 pub async fn get_user_salt(
     sql_conn: &tokio_postgres::Client,
     username: &str,
     is_email: bool,
     is_server: bool,
 ) -> Result<String, ReturnFlags> {
-    /* check that user exists*/
-    if user_exists(sql_conn, username).await {
-        if is_server {
-            if is_email {
-                for row in
-                    &sql_conn.query("SELECT username, server_email_salt FROM accounts_schema.accounts WHERE username LIKE $1", 
-                                  &[&username]).await.unwrap() {
-                        return Ok(row.get(1));
-                    }
-            } else {
-                for row in
-                    &sql_conn.query("SELECT username, server_pass_salt FROM accounts_schema.accounts WHERE username LIKE $1", 
-                                  &[&username]).await.unwrap() {
-                        return Ok(row.get(1));
-                    }
-            }
-        } else {
-            if is_email {
-                for row in
-                    &sql_conn.query("SELECT username, client_email_salt FROM accounts_schema.accounts WHERE username LIKE $1", 
-                                  &[&username]).await.unwrap() {
-                        return Ok(row.get(1));
-                    }
-            } else {
-                for row in
-                    &sql_conn.query("SELECT username, client_pass_salt FROM accounts_schema.accounts WHERE username LIKE $1", 
-                                  &[&username]).await.unwrap() {
-                        return Ok(row.get(1));
-                    }
-            }
-        }
-    }
+    let column = match (is_server, is_email) {
+        (true, true) => "server_email_salt",
+        (true, false) => "server_pass_salt",
+        (false, true) => "client_email_salt",
+        (false, false) => "client_pass_salt",
+    };
+    let query = format!(
+        "SELECT {} FROM accounts_schema.accounts WHERE username LIKE $1",
+        column
+    );
 
-    Err(ReturnFlags::ServerDbUserSaltNotFound)
+    match sql_conn.query(query.as_str(), &[&username]).await {
+        Ok(rows) => rows
+            .first()
+            .map(|row| row.get(0))
+            .ok_or(ReturnFlags::ServerDbUserSaltNotFound),
+        Err(_) => Err(ReturnFlags::ServerDbConnectFailed),
+    }
 }
@@ -4,10 +4,26 @@ pub mod get_company;
 pub mod create_stock;
 pub mod get_stock;
 
+pub mod create_ledger_entry;
+pub mod create_order;
 pub mod create_position;
 pub mod create_transaction;
+pub mod get_orders;
+pub mod get_position;
+pub mod update_order;
+pub mod update_position;
 
+pub mod create_refresh_token;
+pub mod create_session;
+pub mod get_refresh_token;
+pub mod get_session;
+pub mod update_refresh_token;
+pub mod update_session;
+
+pub mod delete_account;
+pub mod get_user_cash;
 pub mod get_user_hash;
 pub mod get_user_id;
 pub mod get_user_salt;
+pub mod update_account;
 pub mod user_exists;
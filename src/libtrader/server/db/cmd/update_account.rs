@@ -0,0 +1,69 @@
+use crate::common::misc::return_flags::ReturnFlags;
+
+/// Replaces the password hash and salts of an account on the postgre SQL database
+///
+/// Arguments:
+/// user_id - ID of the account.
+/// pass_hash - The server hash of the new password, in hex.
+/// server_pass_salt - The new server salt, in hex.
+/// client_pass_salt - The new client salt, in hex.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     update_password(&sql_conn, user_id, &pass_hash, &server_salt, &client_salt).await?;
+/// ```
+pub async fn update_password(
+    sql_conn: &tokio_postgres::Client,
+    user_id: i64,
+    pass_hash: &str,
+    server_pass_salt: &str,
+    client_pass_salt: &str,
+) -> Result<(), ReturnFlags> {
+    match sql_conn
+        .execute(
+            "UPDATE accounts_schema.accounts 
+                         SET pass_hash = $2, server_pass_salt = $3, client_pass_salt = $4 WHERE id = $1",
+            &[&user_id, &pass_hash, &server_pass_salt, &client_pass_salt],
+        )
+        .await
+    {
+        Ok(1) => Ok(()),
+        _ => Err(ReturnFlags::ServerChangePasswordFailed),
+    }
+}
+
+/// Replaces the email hash and salts of an account on the postgre SQL database
+///
+/// Arguments:
+/// user_id - ID of the account.
+/// email_hash - The server hash of the new email, in hex.
+/// server_email_salt - The new server salt, in hex.
+/// client_email_salt - The new client salt, in hex.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     update_email(&sql_conn, user_id, &email_hash, &server_salt, &client_salt).await?;
+/// ```
+pub async fn update_email(
+    sql_conn: &tokio_postgres::Client,
+    user_id: i64,
+    email_hash: &str,
+    server_email_salt: &str,
+    client_email_salt: &str,
+) -> Result<(), ReturnFlags> {
+    match sql_conn
+        .execute(
+            "UPDATE accounts_schema.accounts 
+                         SET email_hash = $2, server_email_salt = $3, client_email_salt = $4 WHERE id = $1",
+            &[&user_id, &email_hash, &server_email_salt, &client_email_salt],
+        )
+        .await
+    {
+        Ok(1) => Ok(()),
+        _ => Err(ReturnFlags::ServerChangeEmailFailed),
+    }
+}
@@ -0,0 +1,72 @@
+use crate::common::account::order::Order;
+use crate::common::misc::return_flags::ReturnFlags;
+
+/// Updates the fill state of an order on the postgre SQL database
+///
+/// Writes ```stock_filled```, ```is_triggered``` and ```is_filled``` of the order.
+///
+/// Arguments:
+/// order - The order with the updated fill state.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     order.stock_filled += fill_amount;
+///     update_order_fill(&sql_transaction, &order).await?;
+/// ```
+pub async fn update_order_fill(
+    sql_conn: &tokio_postgres::Transaction<'_>,
+    order: &Order,
+) -> Result<(), ReturnFlags> {
+    match sql_conn
+        .execute(
+            "UPDATE portfolio_schema.orders 
+                         SET stock_filled = $2, is_triggered = $3, is_filled = $4 WHERE id = $1",
+            &[
+                &order.id,
+                &order.stock_filled,
+                &order.is_triggered,
+                &order.is_filled,
+            ],
+        )
+        .await
+    {
+        Ok(1) => Ok(()),
+        _ => Err(ReturnFlags::ServerDbUpdateOrderFailed),
+    }
+}
+
+/// Cancels a working order on the postgre SQL database
+///
+/// Only the user that placed the order can cancel it, and only while it is neither filled nor
+/// cancelled.
+///
+/// Arguments:
+/// user_id - ID of the user cancelling the order.
+/// order_id - ID of the order to cancel.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     cancel_order(&sql_conn, user_id, order_id).await?;
+/// ```
+pub async fn cancel_order(
+    sql_conn: &tokio_postgres::Client,
+    user_id: i64,
+    order_id: i64,
+) -> Result<(), ReturnFlags> {
+    match sql_conn
+        .execute(
+            "UPDATE portfolio_schema.orders SET is_cancelled = TRUE 
+                         WHERE id = $1 AND user_id = $2 AND NOT is_filled AND NOT is_cancelled",
+            &[&order_id, &user_id],
+        )
+        .await
+    {
+        Ok(1) => Ok(()),
+        Ok(_) => Err(ReturnFlags::ServerDbSearchOrderNotFound),
+        Err(_) => Err(ReturnFlags::ServerDbUpdateOrderFailed),
+    }
+}
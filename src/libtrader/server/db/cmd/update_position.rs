@@ -0,0 +1,40 @@
+use crate::common::account::position::Position;
+use crate::common::misc::return_flags::ReturnFlags;
+
+/// Updates the close state of a position on the postgre SQL database
+///
+/// Writes the close amount, price, cost, epoch and ```is_open``` of the position.
+///
+/// Arguments:
+/// position - The position with the updated close state.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     position.close(shares_amount, quote.bid_price, now);
+///     update_position_close(&sql_transaction, &position).await?;
+/// ```
+pub async fn update_position_close(
+    sql_conn: &tokio_postgres::Transaction<'_>,
+    position: &Position,
+) -> Result<(), ReturnFlags> {
+    match sql_conn
+        .execute(
+            "UPDATE portfolio_schema.positions SET stock_close_amount = $2, stock_close_price = $3, 
+                         stock_close_cost = $4, close_epoch = $5, is_open = $6 WHERE id = $1",
+            &[
+                &position.id,
+                &position.stock_close_amount,
+                &position.stock_close_price,
+                &position.stock_close_cost,
+                &position.close_epoch,
+                &position.is_open,
+            ],
+        )
+        .await
+    {
+        Ok(1) => Ok(()),
+        _ => Err(ReturnFlags::ServerDbUpdatePositionFailed),
+    }
+}
@@ -0,0 +1,135 @@
+use crate::common::account::session::RefreshToken;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::db::cmd::get_refresh_token::{refresh_token_from_row, REFRESH_TOKEN_COLUMNS};
+
+/// Marks a refresh token as used on the postgre SQL database
+///
+/// Only a token that is neither used nor revoked is marked, so that a token can only be
+/// exchanged once even by concurrent requests.
+///
+/// Arguments:
+/// token_hash - The SHA-256 of the refresh token, in hex.
+///
+/// Returns: the token on success, nothing if it was already used, revoked, or does not exist,
+/// ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     match use_refresh_token(&sql_conn, &token_hash).await? {
+///         Some(token) => issue_session(&sql_conn, token.user_id, &token.family_id).await,
+///         None => Err(ReturnFlags::ServerAccUnauthorized),
+///     }
+/// ```
+pub async fn use_refresh_token(
+    sql_conn: &tokio_postgres::Client,
+    token_hash: &str,
+) -> Result<Option<RefreshToken>, ReturnFlags> {
+    sql_conn
+        .query_opt(
+            format!(
+                "UPDATE sessions_schema.refresh_tokens SET is_used = TRUE 
+                         WHERE token_hash = $1 AND NOT is_used AND NOT is_revoked RETURNING {}",
+                REFRESH_TOKEN_COLUMNS
+            )
+            .as_str(),
+            &[&token_hash],
+        )
+        .await
+        .map(|row| row.as_ref().map(refresh_token_from_row))
+        .map_err(|_| ReturnFlags::ServerDbUpdateRefreshTokenFailed)
+}
+
+/// Revokes a family of refresh tokens on the postgre SQL database
+///
+/// Arguments:
+/// family_id - The family to revoke.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+pub async fn revoke_refresh_family(
+    sql_conn: &tokio_postgres::Client,
+    family_id: &str,
+) -> Result<(), ReturnFlags> {
+    sql_conn
+        .execute(
+            "UPDATE sessions_schema.refresh_tokens SET is_revoked = TRUE WHERE family_id = $1",
+            &[&family_id],
+        )
+        .await
+        .map(|_| ())
+        .map_err(|_| ReturnFlags::ServerDbUpdateRefreshTokenFailed)
+}
+
+/// Revokes the family of refresh tokens a session was issued with on the postgre SQL database
+///
+/// Arguments:
+/// user_id - ID of the user owning the session.
+/// sess_id - The session whose family to revoke.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+pub async fn revoke_session_refresh_family(
+    sql_conn: &tokio_postgres::Client,
+    user_id: i64,
+    sess_id: &str,
+) -> Result<(), ReturnFlags> {
+    sql_conn
+        .execute(
+            "UPDATE sessions_schema.refresh_tokens SET is_revoked = TRUE 
+                         WHERE family_id IN (SELECT family_id FROM sessions_schema.refresh_tokens 
+                         WHERE sess_id = $1 AND user_id = $2)",
+            &[&sess_id, &user_id],
+        )
+        .await
+        .map(|_| ())
+        .map_err(|_| ReturnFlags::ServerDbUpdateRefreshTokenFailed)
+}
+
+/// Revokes every refresh token of a user on the postgre SQL database
+///
+/// Arguments:
+/// user_id - ID of the user to log out of every device.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+pub async fn revoke_user_refresh_tokens(
+    sql_conn: &tokio_postgres::Client,
+    user_id: i64,
+) -> Result<(), ReturnFlags> {
+    sql_conn
+        .execute(
+            "UPDATE sessions_schema.refresh_tokens SET is_revoked = TRUE 
+                         WHERE user_id = $1 AND NOT is_revoked",
+            &[&user_id],
+        )
+        .await
+        .map(|_| ())
+        .map_err(|_| ReturnFlags::ServerDbUpdateRefreshTokenFailed)
+}
+
+/// Deletes the refresh token families that expired on the postgre SQL database
+///
+/// Tokens are only deleted once every token of their family expired, so that a used token
+/// exchanged again still revokes its family while the family lives.
+///
+/// Arguments:
+/// now - The current unix epoch.
+///
+/// Returns: the number of refresh tokens deleted on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let deleted = delete_expired_refresh_tokens(&sql_conn, now()).await?;
+/// ```
+pub async fn delete_expired_refresh_tokens(
+    sql_conn: &tokio_postgres::Client,
+    now: i64,
+) -> Result<u64, ReturnFlags> {
+    sql_conn
+        .execute(
+            "DELETE FROM sessions_schema.refresh_tokens WHERE family_id IN \
+                (SELECT family_id FROM sessions_schema.refresh_tokens \
+                GROUP BY family_id HAVING MAX(expiry_date) < $1)",
+            &[&now],
+        )
+        .await
+        .map_err(|_| ReturnFlags::ServerDbUpdateRefreshTokenFailed)
+}
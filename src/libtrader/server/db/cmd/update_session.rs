@@ -0,0 +1,108 @@
+use crate::common::misc::return_flags::ReturnFlags;
+
+/// Revokes a session of a user on the postgre SQL database
+///
+/// Arguments:
+/// user_id - ID of the user owning the session.
+/// sess_id - The id of the session to revoke.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     revoke_session(&sql_conn, claim.user_id, &claim.jti).await?;
+/// ```
+pub async fn revoke_session(
+    sql_conn: &tokio_postgres::Client,
+    user_id: i64,
+    sess_id: &str,
+) -> Result<(), ReturnFlags> {
+    match sql_conn
+        .execute(
+            "UPDATE sessions_schema.sessions SET is_active = FALSE 
+                         WHERE sess_id = $1 AND user_id = $2",
+            &[&sess_id, &user_id],
+        )
+        .await
+    {
+        Ok(1) => Ok(()),
+        Ok(_) => Err(ReturnFlags::ServerDbSearchSessionNotFound),
+        Err(_) => Err(ReturnFlags::ServerDbUpdateSessionFailed),
+    }
+}
+
+/// Revokes every session of a user on the postgre SQL database
+///
+/// Arguments:
+/// user_id - ID of the user to log out of every device.
+///
+/// Returns: the number of sessions revoked on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let revoked = revoke_user_sessions(&sql_conn, claim.user_id).await?;
+/// ```
+pub async fn revoke_user_sessions(
+    sql_conn: &tokio_postgres::Client,
+    user_id: i64,
+) -> Result<u64, ReturnFlags> {
+    sql_conn
+        .execute(
+            "UPDATE sessions_schema.sessions SET is_active = FALSE 
+                         WHERE user_id = $1 AND is_active",
+            &[&user_id],
+        )
+        .await
+        .map_err(|_| ReturnFlags::ServerDbUpdateSessionFailed)
+}
+
+/// Revokes every session issued with a family of refresh tokens on the postgre SQL database
+///
+/// Arguments:
+/// family_id - The family of refresh tokens whose sessions to revoke.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     revoke_family_sessions(&sql_conn, &token.family_id).await?;
+/// ```
+pub async fn revoke_family_sessions(
+    sql_conn: &tokio_postgres::Client,
+    family_id: &str,
+) -> Result<(), ReturnFlags> {
+    sql_conn
+        .execute(
+            "UPDATE sessions_schema.sessions SET is_active = FALSE 
+                         WHERE sess_id IN (SELECT sess_id FROM sessions_schema.refresh_tokens 
+                         WHERE family_id = $1)",
+            &[&family_id],
+        )
+        .await
+        .map(|_| ())
+        .map_err(|_| ReturnFlags::ServerDbUpdateSessionFailed)
+}
+
+/// Deletes the expired sessions on the postgre SQL database
+///
+/// Arguments:
+/// now - The current unix epoch.
+///
+/// Returns: the number of sessions deleted on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let deleted = delete_expired_sessions(&sql_conn, now()).await?;
+/// ```
+pub async fn delete_expired_sessions(
+    sql_conn: &tokio_postgres::Client,
+    now: i64,
+) -> Result<u64, ReturnFlags> {
+    sql_conn
+        .execute(
+            "DELETE FROM sessions_schema.sessions WHERE expiry_date < $1",
+            &[&now],
+        )
+        .await
+        .map_err(|_| ReturnFlags::ServerDbUpdateSessionFailed)
+}
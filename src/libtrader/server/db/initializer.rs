@@ -1,30 +1,27 @@
+use crate::server::config::{server_config, DbCredentials};
+
 /// Establishes a postgresql connection to the SQL database.
 ///
 /// Creates a postgresql connection.
 ///
 /// Arguments:
-/// user - The name of the user to connect to the database with.
-/// pass - The password of the user to connect to the database with.
+/// credentials - The database user to connect with, from the server configuration.
 ///
 /// Returns: ```postgres::Client``` on success, and a string containing the
 /// reason of failure on error.
 ///
 /// Example:
 /// ```rust
-/// let mut client = db_connect(DB_USER, DB_PASS)?;
+/// let mut client = db_connect(&server_config().db_portfolio).await?;
 /// ```
 pub async fn db_connect(
-    user: String,
-    pass: String,
+    credentials: &DbCredentials,
 ) -> Result<tokio_postgres::Client, tokio_postgres::Error> {
+    let config = server_config();
     /* Generate the requested string */
     let db_connect_str = format!(
         "host={} port={} dbname={} user={} password={}",
-        std::env::var("DB_HOST").unwrap(),
-        std::env::var("DB_HOST_PORT").unwrap(),
-        std::env::var("DB_NAME").unwrap(),
-        user,
-        pass
+        config.db_host, config.db_port, config.db_name, credentials.user, credentials.pass
     );
     let (client, connection) =
         tokio_postgres::connect(db_connect_str.as_str(), tokio_postgres::NoTls).await?;
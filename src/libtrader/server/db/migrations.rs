@@ -0,0 +1,255 @@
+use log::{error, info};
+
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::creation::STARTING_CASH_BALANCE;
+
+/// A versioned SQL migration embedded into the server binary.
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+macro_rules! migration {
+    ($version:expr, $name:expr) => {
+        Migration {
+            version: $version,
+            name: $name,
+            sql: include_str!(concat!("sql/", $name, ".sql")),
+        }
+    };
+}
+
+/// All migrations known to this binary, ordered by version.
+pub static MIGRATIONS: &[Migration] = &[
+    migration!(0, "000_table_companies"),
+    migration!(1, "001_schema_sessions"),
+    migration!(2, "002_table_sessions"),
+    migration!(3, "003_schema_asset"),
+    migration!(4, "004_schema_accounts"),
+    migration!(5, "005_table_transaction"),
+    migration!(6, "006_table_accounts"),
+    migration!(7, "007_schema_portfolio"),
+    migration!(8, "008_table_positions"),
+    migration!(9, "009_grant_trading"),
+    migration!(10, "010_cash_ledger"),
+    migration!(11, "011_table_orders"),
+    migration!(12, "012_position_close_cost"),
+    migration!(13, "013_grant_market_data"),
+    migration!(14, "014_table_quotes"),
+    migration!(15, "015_session_store"),
+    migration!(16, "016_refresh_tokens"),
+    migration!(17, "017_account_deletion"),
+    migration!(18, "018_session_expiry"),
+];
+
+/// Key of the advisory lock held while migrating, so that only one server migrates at a time.
+static MIGRATIONS_LOCK_KEY: i64 = 0x5054_4d49_4752;
+
+/// Returns the version of the newest migration embedded into the binary.
+pub fn latest_version() -> i64 {
+    MIGRATIONS
+        .last()
+        .map(|migration| migration.version)
+        .unwrap_or(-1)
+}
+
+/// Creates the ```schema_migrations``` table if it does not exist yet.
+async fn init_schema_migrations(sql_conn: &tokio_postgres::Client) -> Result<(), ReturnFlags> {
+    sql_conn
+        .batch_execute(
+            "CREATE TABLE IF NOT EXISTS public.schema_migrations ( \
+                version         BIGINT PRIMARY KEY, \
+                name            TEXT NOT NULL, \
+                applied_epoch   BIGINT NOT NULL \
+            )",
+        )
+        .await
+        .map_err(|_| ReturnFlags::ServerDbMigrationFailed)
+}
+
+/// Returns the highest applied migration version of the database.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+///
+/// Returns: the version, or -1 when no migration is applied yet, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let version = get_schema_version(&sql_conn).await?;
+/// ```
+pub async fn get_schema_version(sql_conn: &tokio_postgres::Client) -> Result<i64, ReturnFlags> {
+    init_schema_migrations(sql_conn).await?;
+    let row = sql_conn
+        .query_one(
+            "SELECT COALESCE(MAX(version), -1) FROM public.schema_migrations",
+            &[],
+        )
+        .await
+        .map_err(|_| ReturnFlags::ServerDbMigrationFailed)?;
+    Ok(row.get(0))
+}
+
+/// Applies all pending migrations to the database.
+///
+/// Every migration is run in its own transaction together with its ```schema_migrations```
+/// record. Refuses to touch a database whose schema is newer than this binary.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use, it must be allowed to create roles and schemas.
+///
+/// Returns: the versions that were applied on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     match migrate(&mut sql_conn).await {
+///         Ok(applied) => info!("applied {} migrations", applied.len()),
+///         Err(err) => error!("failed migrating database: {}", err),
+///     }
+/// ```
+pub async fn migrate(sql_conn: &mut tokio_postgres::Client) -> Result<Vec<i64>, ReturnFlags> {
+    sql_conn
+        .execute("SELECT pg_advisory_lock($1)", &[&MIGRATIONS_LOCK_KEY])
+        .await
+        .map_err(|_| ReturnFlags::ServerDbMigrationFailed)?;
+    let result = apply_pending(sql_conn).await;
+    sql_conn
+        .execute("SELECT pg_advisory_unlock($1)", &[&MIGRATIONS_LOCK_KEY])
+        .await
+        .map_err(|_| ReturnFlags::ServerDbMigrationFailed)?;
+    result
+}
+
+async fn apply_pending(sql_conn: &mut tokio_postgres::Client) -> Result<Vec<i64>, ReturnFlags> {
+    let version = get_schema_version(sql_conn).await?;
+    if version > latest_version() {
+        return Err(ReturnFlags::ServerDbSchemaAhead);
+    }
+
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.version > version) {
+        let transaction = sql_conn
+            .transaction()
+            .await
+            .map_err(|_| ReturnFlags::ServerDbMigrationFailed)?;
+        set_migration_settings(&transaction).await?;
+        transaction
+            .batch_execute(migration.sql)
+            .await
+            .map_err(|err| {
+                error!("MIGRATION_{}_FAILED: {}", migration.name, err);
+                ReturnFlags::ServerDbMigrationFailed
+            })?;
+        record_migration(&transaction, migration).await?;
+        transaction
+            .commit()
+            .await
+            .map_err(|_| ReturnFlags::ServerDbMigrationFailed)?;
+        info!("MIGRATION_APPLIED: {}", migration.name);
+        applied.push(migration.version);
+    }
+    Ok(applied)
+}
+
+/// Sets the ```papertrader.*``` settings read by migrations, until the end of the transaction.
+///
+/// Values the server also uses, such as ```STARTING_CASH_BALANCE```, are set here so that
+/// migrations do not hardcode copies of them.
+async fn set_migration_settings(
+    sql_transaction: &tokio_postgres::Transaction<'_>,
+) -> Result<(), ReturnFlags> {
+    sql_transaction
+        .execute(
+            "SELECT set_config('papertrader.starting_cash_balance', $1, true)",
+            &[&STARTING_CASH_BALANCE.to_string()],
+        )
+        .await
+        .map(|_| ())
+        .map_err(|_| ReturnFlags::ServerDbMigrationFailed)
+}
+
+/// Records migrations up to a version as applied without running them.
+///
+/// Used for databases that were created before migrations were tracked, e.g. by the docker
+/// init scripts.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+/// version - The last migration version already present in the database.
+///
+/// Returns: nothing on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     baseline(&sql_conn, 8).await?;
+/// ```
+pub async fn baseline(
+    sql_conn: &mut tokio_postgres::Client,
+    version: i64,
+) -> Result<(), ReturnFlags> {
+    if version > latest_version() {
+        return Err(ReturnFlags::ServerDbSchemaAhead);
+    }
+    init_schema_migrations(sql_conn).await?;
+
+    let transaction = sql_conn
+        .transaction()
+        .await
+        .map_err(|_| ReturnFlags::ServerDbMigrationFailed)?;
+    for migration in MIGRATIONS.iter().filter(|m| m.version <= version) {
+        record_migration(&transaction, migration).await?;
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(|_| ReturnFlags::ServerDbMigrationFailed)
+}
+
+async fn record_migration(
+    sql_transaction: &tokio_postgres::Transaction<'_>,
+    migration: &Migration,
+) -> Result<(), ReturnFlags> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let applied_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    sql_transaction
+        .execute(
+            "INSERT INTO public.schema_migrations (version, name, applied_epoch) \
+                VALUES ($1, $2, $3) ON CONFLICT (version) DO NOTHING",
+            &[&migration.version, &migration.name, &applied_epoch],
+        )
+        .await
+        .map(|_| ())
+        .map_err(|_| ReturnFlags::ServerDbMigrationFailed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_migrations_cover_sql_dir() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/src/libtrader/server/db/sql");
+        let mut files: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .filter(|name| name.ends_with(".sql"))
+            .collect();
+        files.sort();
+
+        let names: Vec<String> = MIGRATIONS
+            .iter()
+            .map(|migration| format!("{}.sql", migration.name))
+            .collect();
+        assert_eq!(names, files);
+
+        for (idx, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version, idx as i64);
+            assert!(migration.name.starts_with(&format!("{:03}_", idx)));
+        }
+    }
+}
@@ -0,0 +1,9 @@
+GRANT USAGE ON SCHEMA accounts_schema TO portfolio_schema_usr;
+GRANT SELECT, INSERT ON accounts_schema.transactions TO portfolio_schema_usr;
+GRANT USAGE ON SEQUENCE accounts_schema.transactions_id_seq TO portfolio_schema_usr;
+
+GRANT USAGE ON SCHEMA asset_schema TO portfolio_schema_usr;
+GRANT SELECT ON ALL TABLES IN SCHEMA asset_schema TO portfolio_schema_usr;
+ALTER DEFAULT PRIVILEGES IN SCHEMA asset_schema GRANT SELECT ON TABLES TO portfolio_schema_usr;
+
+GRANT SELECT ON public.companies TO portfolio_schema_usr;
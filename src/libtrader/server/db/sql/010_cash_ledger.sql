@@ -0,0 +1,21 @@
+ALTER TABLE accounts_schema.accounts ADD COLUMN cash_balance DOUBLE PRECISION NOT NULL DEFAULT 0;
+
+CREATE TABLE accounts_schema.ledger (
+	id				BIGSERIAL PRIMARY KEY,
+	user_id			BIGINT NOT NULL,
+	transaction_id	BIGINT REFERENCES accounts_schema.transactions(id),
+	amount			DOUBLE PRECISION NOT NULL,
+	balance			DOUBLE PRECISION NOT NULL,
+	time_epoch		BIGINT NOT NULL
+);
+
+-- existing accounts start with STARTING_CASH_BALANCE, set by the migration runner, and its
+-- opening ledger entry
+UPDATE accounts_schema.accounts
+	SET cash_balance = current_setting('papertrader.starting_cash_balance')::DOUBLE PRECISION;
+INSERT INTO accounts_schema.ledger (user_id, amount, balance, time_epoch)
+	SELECT id, cash_balance, cash_balance, EXTRACT(EPOCH FROM NOW())::BIGINT FROM accounts_schema.accounts;
+
+GRANT SELECT (id, cash_balance), UPDATE (cash_balance) ON accounts_schema.accounts TO portfolio_schema_usr;
+GRANT SELECT, INSERT ON accounts_schema.ledger TO portfolio_schema_usr;
+GRANT USAGE ON SEQUENCE accounts_schema.ledger_id_seq TO portfolio_schema_usr;
@@ -0,0 +1,18 @@
+CREATE TABLE portfolio_schema.orders (
+	id					BIGSERIAL PRIMARY KEY,
+	user_id				BIGINT NOT NULL,
+	stock_symbol		TEXT NOT NULL,
+	order_type			SMALLINT NOT NULL,
+	is_buy				BOOLEAN NOT NULL,
+	stock_price			DOUBLE PRECISION NOT NULL,
+	stop_price			DOUBLE PRECISION NOT NULL,
+	stock_amount		BIGINT NOT NULL,
+	stock_filled		BIGINT NOT NULL,
+	is_triggered		BOOLEAN NOT NULL,
+	is_filled			BOOLEAN NOT NULL,
+	is_cancelled		BOOLEAN NOT NULL,
+	create_epoch		BIGINT NOT NULL
+);
+
+CREATE INDEX orders_working_idx ON portfolio_schema.orders (stock_symbol, id)
+	WHERE NOT is_filled AND NOT is_cancelled;
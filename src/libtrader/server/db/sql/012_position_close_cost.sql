@@ -0,0 +1 @@
+ALTER TABLE portfolio_schema.positions ADD COLUMN stock_close_cost DOUBLE PRECISION NOT NULL DEFAULT 0
@@ -0,0 +1,5 @@
+GRANT USAGE ON SCHEMA asset_schema TO accounts_schema_usr;
+GRANT SELECT ON ALL TABLES IN SCHEMA asset_schema TO accounts_schema_usr;
+ALTER DEFAULT PRIVILEGES IN SCHEMA asset_schema GRANT SELECT ON TABLES TO accounts_schema_usr;
+
+GRANT SELECT ON public.companies TO accounts_schema_usr;
@@ -0,0 +1,37 @@
+CREATE TABLE asset_schema.quotes (
+	id					BIGSERIAL,
+	symbol				TEXT NOT NULL,
+	isin				TEXT NOT NULL,
+	time_epoch			BIGINT NOT NULL,
+	ask_price			DOUBLE PRECISION NOT NULL,
+	bid_price			DOUBLE PRECISION NOT NULL,
+	volume				BIGINT NOT NULL,
+	PRIMARY KEY (symbol, time_epoch)
+) PARTITION BY HASH (symbol);
+
+CREATE TABLE asset_schema.quotes_p0 PARTITION OF asset_schema.quotes FOR VALUES WITH (MODULUS 4, REMAINDER 0);
+CREATE TABLE asset_schema.quotes_p1 PARTITION OF asset_schema.quotes FOR VALUES WITH (MODULUS 4, REMAINDER 1);
+CREATE TABLE asset_schema.quotes_p2 PARTITION OF asset_schema.quotes FOR VALUES WITH (MODULUS 4, REMAINDER 2);
+CREATE TABLE asset_schema.quotes_p3 PARTITION OF asset_schema.quotes FOR VALUES WITH (MODULUS 4, REMAINDER 3);
+
+CREATE INDEX quotes_time_epoch_idx ON asset_schema.quotes (time_epoch);
+
+/* move the old per-symbol tables into the quotes table */
+DO $$
+DECLARE
+	stock_table TEXT;
+BEGIN
+	FOR stock_table IN
+		SELECT table_name FROM information_schema.tables
+		WHERE table_schema = 'asset_schema' AND table_name NOT LIKE 'quotes%'
+	LOOP
+		EXECUTE format(
+			'INSERT INTO asset_schema.quotes (symbol, isin, time_epoch, ask_price, bid_price, volume) '
+			'SELECT upper(%L), isin, time_epoch, ask_price, bid_price, volume FROM asset_schema.%I '
+			'ON CONFLICT DO NOTHING',
+			stock_table, stock_table);
+		EXECUTE format('DROP TABLE asset_schema.%I', stock_table);
+	END LOOP;
+END $$;
+
+GRANT SELECT ON ALL TABLES IN SCHEMA asset_schema TO portfolio_schema_usr, accounts_schema_usr;
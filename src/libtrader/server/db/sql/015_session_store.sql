@@ -0,0 +1,14 @@
+DROP TABLE sessions_schema.sessions;
+CREATE TABLE sessions_schema.sessions (
+	sess_id					TEXT PRIMARY KEY,
+	user_id					BIGINT NOT NULL,
+	client_ip				TEXT NOT NULL,
+	issue_date				BIGINT NOT NULL,
+	expiry_date				BIGINT NOT NULL,
+	is_active				BOOL NOT NULL
+);
+
+CREATE INDEX sessions_user_id_idx ON sessions_schema.sessions (user_id);
+
+GRANT USAGE ON SCHEMA sessions_schema TO accounts_schema_usr;
+GRANT SELECT, INSERT, UPDATE, DELETE ON sessions_schema.sessions TO accounts_schema_usr;
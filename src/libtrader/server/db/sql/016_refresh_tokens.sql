@@ -0,0 +1,15 @@
+CREATE TABLE sessions_schema.refresh_tokens (
+	token_hash				TEXT PRIMARY KEY,
+	family_id				TEXT NOT NULL,
+	user_id					BIGINT NOT NULL,
+	sess_id					TEXT NOT NULL,
+	issue_date				BIGINT NOT NULL,
+	expiry_date				BIGINT NOT NULL,
+	is_used					BOOL NOT NULL,
+	is_revoked				BOOL NOT NULL
+);
+
+CREATE INDEX refresh_tokens_family_id_idx ON sessions_schema.refresh_tokens (family_id);
+CREATE INDEX refresh_tokens_user_id_idx ON sessions_schema.refresh_tokens (user_id);
+
+GRANT SELECT, INSERT, UPDATE, DELETE ON sessions_schema.refresh_tokens TO accounts_schema_usr;
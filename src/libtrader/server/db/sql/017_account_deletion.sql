@@ -0,0 +1,2 @@
+GRANT USAGE ON SCHEMA portfolio_schema TO accounts_schema_usr;
+GRANT SELECT, DELETE ON portfolio_schema.positions, portfolio_schema.orders TO accounts_schema_usr;
@@ -0,0 +1 @@
+CREATE INDEX sessions_expiry_date_idx ON sessions_schema.sessions (expiry_date);
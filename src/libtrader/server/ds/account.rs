@@ -14,6 +14,7 @@ pub struct Account {
     pub client_pass_salt: String,
 
     pub is_pass: bool,
+    pub cash_balance: f64,
     pub portfolio: Portfolio,
     pub transactions: Vec<Transaction>,
 }
@@ -22,11 +23,12 @@ impl std::fmt::Display for Account {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "({}, {}, {}, {}, {}, {:#?})",
+            "({}, {}, {}, {}, {}, {}, {:#?})",
             self.username,
             self.email_hash,
             self.is_pass,
             self.pass_hash,
+            self.cash_balance,
             self.portfolio,
             self.transactions
         )
@@ -1,15 +1,114 @@
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
 use crate::common::generic::company::Company;
 use crate::common::generic::stock_val::StockVal;
 
-#[derive(PartialEq, Debug)]
+use crate::server::ds::quote_stream::QuoteStream;
+
+/// The number of cache lookups served from the global state, and those that were not.
+#[derive(PartialEq, Debug, Default, Clone, Copy)]
+pub struct CacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Debug, Default)]
 pub struct GlobalState {
     pub companies: HashMap<String, Company>,   // symbol, company
     pub stock_vals: HashMap<String, StockVal>, // symbol, stockval
+    refreshed: HashMap<String, Instant>,       // symbol, last refresh of the stockval
+    pub quote_stream: QuoteStream,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+impl GlobalState {
+    /// Records a quote as the latest value of its symbol, unless a newer one is already known.
+    ///
+    /// Recorded quotes are published on the quote stream. Either way the latest value of the
+    /// symbol counts as refreshed.
+    ///
+    /// Returns: true if the quote was recorded.
+    pub fn update_stock_val(&mut self, stock_val: &StockVal) -> bool {
+        self.refreshed
+            .insert(stock_val.symbol.clone(), Instant::now());
+        match self.stock_vals.get(&stock_val.symbol) {
+            Some(latest) if latest.time_epoch >= stock_val.time_epoch => false,
+            _ => {
+                self.stock_vals
+                    .insert(stock_val.symbol.clone(), stock_val.clone());
+                self.quote_stream.publish(stock_val);
+                true
+            }
+        }
+    }
+
+    /// Returns the latest value of a symbol if it was refreshed within ```max_age```.
+    ///
+    /// Every lookup is counted as a hit or a miss, see ```cache_metrics()```.
+    pub fn cached_stock_val(&self, symbol: &str, max_age: Duration) -> Option<StockVal> {
+        let is_fresh = self
+            .refreshed
+            .get(symbol)
+            .is_some_and(|refreshed| refreshed.elapsed() <= max_age);
+        match (is_fresh, self.stock_vals.get(symbol)) {
+            (true, Some(stock_val)) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(stock_val.clone())
+            }
+            _ => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Returns the number of cache hits and misses so far.
+    pub fn cache_metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
 }
 impl std::fmt::Display for GlobalState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({:#?}, {:#?})", self.companies, self.stock_vals)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_global_state_cache() {
+        let mut state = GlobalState::default();
+        let mut quotes = state.quote_stream.subscribe();
+        let max_age = Duration::from_secs(60);
+        let mut stock_val = StockVal {
+            symbol: "AAPL".into(),
+            time_epoch: 1000,
+            ..StockVal::default()
+        };
+
+        assert_eq!(state.cached_stock_val("AAPL", max_age), None);
+        assert!(state.update_stock_val(&stock_val));
+        assert_eq!(
+            state.cached_stock_val("AAPL", max_age),
+            Some(stock_val.clone())
+        );
+        assert_eq!(state.cached_stock_val("MSFT", max_age), None);
+
+        /* older quotes do not replace newer ones */
+        stock_val.time_epoch = 999;
+        assert!(!state.update_stock_val(&stock_val));
+        assert_eq!(state.stock_vals["AAPL"].time_epoch, 1000);
+
+        assert_eq!(quotes.try_recv().unwrap().stock_val.time_epoch, 1000);
+        assert!(quotes.try_recv().is_err());
+
+        assert_eq!(state.cache_metrics(), CacheMetrics { hits: 1, misses: 2 });
+    }
+}
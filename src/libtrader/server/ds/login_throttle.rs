@@ -0,0 +1,281 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::common::misc::return_flags::ReturnFlags;
+
+/// Failed logins allowed before any delay is imposed.
+pub const FREE_FAILURES: u32 = 3;
+/// The delay imposed after the first failure past ```FREE_FAILURES```, doubled on every further
+/// failure, in seconds.
+pub const BACKOFF_BASE: u64 = 1;
+/// The longest delay imposed between two attempts, in seconds.
+pub const BACKOFF_MAX: u64 = 5 * 60;
+/// Failed logins after which the key is locked out.
+pub const LOCKOUT_FAILURES: u32 = 10;
+/// How long a lockout lasts, in seconds.
+pub const LOCKOUT_DURATION: u64 = 15 * 60;
+/// How long failures are remembered without a new attempt, in seconds.
+pub const FORGET_AFTER: u64 = 60 * 60;
+/// How often keys past ```FORGET_AFTER``` are forgotten, in seconds.
+pub const FORGET_INTERVAL: u64 = 60;
+/// The most keys remembered, the least recently attempted key is forgotten first.
+pub const MAX_KEYS: usize = 65_536;
+
+/// The failed logins of a single key.
+#[derive(PartialEq, Debug, Default, Clone, Copy)]
+struct Failures {
+    count: u32,
+    last_attempt: u64,
+    blocked_until: u64,
+    in_flight: u32,
+}
+
+/// Throttles failed logins per key, such as a client address or a username.
+///
+/// Each key gets ```FREE_FAILURES``` failed attempts, after which every attempt must wait for an
+/// exponentially growing delay. After ```LOCKOUT_FAILURES``` failures the key is locked out for
+/// ```LOCKOUT_DURATION```. A successful login clears the failures of its key. At most
+/// ```MAX_KEYS``` keys are remembered, for ```FORGET_AFTER``` after their last attempt.
+///
+/// Attempts count as failed until they are verified, so parallel attempts can not get past the
+/// delay: once the free failures are used up, only one attempt per key is verified at a time.
+///
+/// Example:
+/// ```rust
+///     throttle.begin("user:n1ckn8me", now)?;
+///     match verify(...) {
+///         Ok(_) => throttle.success("user:n1ckn8me"),
+///         Err(_) => throttle.failure("user:n1ckn8me", now),
+///     }
+/// ```
+#[derive(PartialEq, Debug, Default)]
+pub struct LoginThrottle {
+    failures: HashMap<String, Failures>,
+    by_last_attempt: BTreeSet<(u64, String)>,
+    last_forget: u64,
+}
+
+impl LoginThrottle {
+    /// Starts a login attempt of a key at ```now```, if it is allowed.
+    ///
+    /// Every allowed attempt must be ended with ```success```, ```failure``` or ```release```.
+    ///
+    /// Returns: nothing if allowed, ```ServerAccLocked``` if the key is locked out,
+    /// ```ServerAccLoginThrottled``` if it must wait.
+    pub fn begin(&mut self, key: &str, now: u64) -> Result<(), ReturnFlags> {
+        if now >= self.last_forget + FORGET_INTERVAL {
+            self.forget(now);
+            self.last_forget = now;
+        }
+
+        let failures = self.attempt(key, now);
+        if now < failures.blocked_until {
+            if failures.count >= LOCKOUT_FAILURES {
+                return Err(ReturnFlags::ServerAccLocked);
+            }
+            return Err(ReturnFlags::ServerAccLoginThrottled);
+        }
+        if failures.count >= LOCKOUT_FAILURES {
+            /* the lockout is over, later failures are delayed again */
+            failures.count = FREE_FAILURES;
+        }
+        if failures.in_flight > 0 && failures.count + failures.in_flight >= FREE_FAILURES {
+            /* wait for the attempts in flight, they might fail */
+            return Err(ReturnFlags::ServerAccLoginThrottled);
+        }
+        failures.in_flight += 1;
+        Ok(())
+    }
+
+    /// Ends a login attempt of a key that failed at ```now```.
+    pub fn failure(&mut self, key: &str, now: u64) {
+        let failures = self.attempt(key, now);
+        failures.in_flight = failures.in_flight.saturating_sub(1);
+        failures.count += 1;
+        failures.blocked_until = if failures.count >= LOCKOUT_FAILURES {
+            now + LOCKOUT_DURATION
+        } else if failures.count > FREE_FAILURES {
+            let doublings = (failures.count - FREE_FAILURES - 1).min(16);
+            now + (BACKOFF_BASE << doublings).min(BACKOFF_MAX)
+        } else {
+            now
+        };
+    }
+
+    /// Ends a login attempt of a key that succeeded, clearing its failures.
+    pub fn success(&mut self, key: &str) {
+        if let Some(failures) = self.failures.get_mut(key) {
+            *failures = Failures {
+                last_attempt: failures.last_attempt,
+                in_flight: failures.in_flight.saturating_sub(1),
+                ..Failures::default()
+            };
+        }
+        self.remove_idle(key);
+    }
+
+    /// Ends a login attempt of a key that could not be verified, without counting it.
+    pub fn release(&mut self, key: &str) {
+        if let Some(failures) = self.failures.get_mut(key) {
+            failures.in_flight = failures.in_flight.saturating_sub(1);
+        }
+        self.remove_idle(key);
+    }
+
+    /// Returns the failures of a key, recording an attempt at ```now```.
+    ///
+    /// A new key replaces the least recently attempted key once ```MAX_KEYS``` are remembered.
+    fn attempt(&mut self, key: &str, now: u64) -> &mut Failures {
+        match self.failures.get(key) {
+            Some(failures) => {
+                self.by_last_attempt
+                    .remove(&(failures.last_attempt, key.to_string()));
+            }
+            None if self.failures.len() >= MAX_KEYS => {
+                if let Some((_, oldest)) = self.by_last_attempt.pop_first() {
+                    self.failures.remove(&oldest);
+                }
+            }
+            None => {}
+        }
+        self.by_last_attempt.insert((now, key.to_string()));
+
+        let failures = self.failures.entry(key.to_string()).or_default();
+        failures.last_attempt = now;
+        failures
+    }
+
+    /// Removes a key without failures or attempts in flight.
+    fn remove_idle(&mut self, key: &str) {
+        if let Some(failures) = self.failures.get(key) {
+            if failures.count == 0 && failures.in_flight == 0 {
+                self.by_last_attempt
+                    .remove(&(failures.last_attempt, key.to_string()));
+                self.failures.remove(key);
+            }
+        }
+    }
+
+    /// Forgets the keys that were not attempted for ```FORGET_AFTER```.
+    ///
+    /// Lockouts and delays end before ```FORGET_AFTER```, so no blocked key is forgotten.
+    fn forget(&mut self, now: u64) {
+        while let Some((last_attempt, _)) = self.by_last_attempt.first() {
+            if last_attempt + FORGET_AFTER > now {
+                break;
+            }
+            let (_, key) = self.by_last_attempt.pop_first().unwrap();
+            self.failures.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_login_throttle_backoff() {
+        let mut throttle = LoginThrottle::default();
+        for _ in 0..FREE_FAILURES {
+            assert_eq!(throttle.begin("user:test", 100), Ok(()));
+            throttle.failure("user:test", 100);
+        }
+
+        /* past the free failures, every failure doubles the delay */
+        assert_eq!(throttle.begin("user:test", 100), Ok(()));
+        throttle.failure("user:test", 100);
+        assert_eq!(
+            throttle.begin("user:test", 100),
+            Err(ReturnFlags::ServerAccLoginThrottled)
+        );
+        assert_eq!(throttle.begin("user:test", 101), Ok(()));
+        throttle.failure("user:test", 101);
+        assert_eq!(
+            throttle.begin("user:test", 102),
+            Err(ReturnFlags::ServerAccLoginThrottled)
+        );
+        assert_eq!(throttle.begin("user:test", 103), Ok(()));
+
+        /* other keys are not affected, and success clears the failures */
+        assert_eq!(throttle.begin("ip:127.0.0.1", 100), Ok(()));
+        throttle.release("ip:127.0.0.1");
+        throttle.success("user:test");
+        assert!(throttle.failures.is_empty() && throttle.by_last_attempt.is_empty());
+        assert_eq!(throttle.begin("user:test", 103), Ok(()));
+        throttle.failure("user:test", 103);
+        assert_eq!(throttle.begin("user:test", 103), Ok(()));
+    }
+
+    #[test]
+    fn test_login_throttle_in_flight() {
+        let mut throttle = LoginThrottle::default();
+
+        /* parallel attempts count as failed until they are verified */
+        for _ in 0..FREE_FAILURES {
+            assert_eq!(throttle.begin("user:test", 100), Ok(()));
+        }
+        assert_eq!(
+            throttle.begin("user:test", 100),
+            Err(ReturnFlags::ServerAccLoginThrottled)
+        );
+        throttle.release("user:test");
+        assert_eq!(throttle.begin("user:test", 100), Ok(()));
+        for _ in 0..FREE_FAILURES {
+            throttle.failure("user:test", 100);
+        }
+
+        /* past the free failures, one attempt at a time */
+        assert_eq!(throttle.begin("user:test", 100), Ok(()));
+        assert_eq!(
+            throttle.begin("user:test", 100),
+            Err(ReturnFlags::ServerAccLoginThrottled)
+        );
+    }
+
+    #[test]
+    fn test_login_throttle_lockout() {
+        let mut throttle = LoginThrottle::default();
+        let mut now = 0;
+        for _ in 0..LOCKOUT_FAILURES {
+            while throttle.begin("user:test", now).is_err() {
+                now += 1;
+            }
+            throttle.failure("user:test", now);
+        }
+        assert_eq!(
+            throttle.begin("user:test", now + LOCKOUT_DURATION - 1),
+            Err(ReturnFlags::ServerAccLocked)
+        );
+        assert_eq!(throttle.begin("user:test", now + LOCKOUT_DURATION), Ok(()));
+
+        /* after the lockout, the next failure is delayed again */
+        throttle.failure("user:test", now + LOCKOUT_DURATION);
+        assert_eq!(
+            throttle.begin("user:test", now + LOCKOUT_DURATION),
+            Err(ReturnFlags::ServerAccLoginThrottled)
+        );
+    }
+
+    #[test]
+    fn test_login_throttle_forget() {
+        let mut throttle = LoginThrottle::default();
+        for i in 0..MAX_KEYS as u64 + 1 {
+            let key = format!("user:{}", i);
+            assert_eq!(throttle.begin(&key, i / 32), Ok(()));
+            throttle.failure(&key, i / 32);
+        }
+
+        /* the least recently attempted key makes room */
+        assert_eq!(throttle.failures.len(), MAX_KEYS);
+        assert!(!throttle.failures.contains_key("user:0"));
+        assert_eq!(throttle.by_last_attempt.len(), MAX_KEYS);
+
+        /* keys not attempted for an hour are forgotten */
+        let now = MAX_KEYS as u64 / 32 - 1 + FORGET_AFTER;
+        assert_eq!(throttle.begin("user:test", now), Ok(()));
+        assert_eq!(throttle.failures.len(), 2);
+        assert!(throttle
+            .failures
+            .contains_key(&format!("user:{}", MAX_KEYS)));
+    }
+}
@@ -1,2 +1,4 @@
 pub mod account;
 pub mod global_state;
+pub mod login_throttle;
+pub mod quote_stream;
@@ -0,0 +1,54 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tokio::sync::broadcast;
+
+use crate::common::generic::stock_val::StockVal;
+
+/// The number of ticks buffered for each subscriber. A subscriber falling further behind loses
+/// its oldest ticks.
+pub static QUOTE_STREAM_CAPACITY: usize = 1024;
+
+/// A quote published on the quote stream.
+#[derive(PartialEq, Debug, Clone)]
+pub struct QuoteTick {
+    pub sequence: u64,
+    pub stock_val: StockVal,
+}
+
+/// Fans every new latest quote out to the subscribed connections.
+///
+/// Publishing never waits for subscribers. Each subscriber has a buffer of
+/// ```QUOTE_STREAM_CAPACITY``` ticks, and a subscriber that does not keep up skips the ticks that
+/// fell out of its buffer.
+#[derive(Debug)]
+pub struct QuoteStream {
+    sender: broadcast::Sender<QuoteTick>,
+    sequence: AtomicU64,
+}
+impl Default for QuoteStream {
+    fn default() -> Self {
+        QuoteStream {
+            sender: broadcast::channel(QUOTE_STREAM_CAPACITY).0,
+            sequence: AtomicU64::new(0),
+        }
+    }
+}
+impl QuoteStream {
+    /// Publishes a quote to every subscriber.
+    ///
+    /// Returns: the sequence number of the tick.
+    pub fn publish(&self, stock_val: &StockVal) -> u64 {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed) + 1;
+        /* nobody listening is fine */
+        let _ = self.sender.send(QuoteTick {
+            sequence,
+            stock_val: stock_val.clone(),
+        });
+        sequence
+    }
+
+    /// Subscribes to the ticks published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<QuoteTick> {
+        self.sender.subscribe()
+    }
+}
@@ -0,0 +1,213 @@
+use crate::common::account::order::{Order, OrderType};
+use crate::common::generic::stock_val::StockVal;
+
+/// The outcome of matching a working order against a quote.
+///
+/// Members:
+/// is_triggered - The new trigger state of the order.
+/// fill_amount - The number of shares filled, can be zero when only the trigger state changed.
+/// fill_price - The price per share of the fill.
+#[derive(PartialEq, Debug)]
+pub struct OrderMatch {
+    pub is_triggered: bool,
+    pub fill_amount: i64,
+    pub fill_price: f64,
+}
+
+/// Matches a working order against a quote.
+///
+/// Buys trade against the ask price and sells against the bid price. Stop orders trigger once
+/// the quote reaches the stop price, and after that a stop fills at the quote like a market
+/// order while a stop-limit fills like a limit order. The fill is limited by the shares left on
+/// the order and by the liquidity left on the quote.
+///
+/// Arguments:
+/// order - The working order to match.
+/// quote - The quote to match against.
+/// liquidity - The number of shares still available on the quote.
+///
+/// Returns: an OrderMatch when the order changed, nothing otherwise.
+///
+/// Example:
+/// ```rust
+///     if let Some(order_match) = match_order(&order, &quote, quote.volume) {
+///         /* write the fill */
+///     }
+/// ```
+pub fn match_order(order: &Order, quote: &StockVal, liquidity: i64) -> Option<OrderMatch> {
+    let price = if order.is_buy {
+        quote.ask_price
+    } else {
+        quote.bid_price
+    };
+
+    /* check whether the stop was reached */
+    let is_triggered = order.is_triggered
+        || match order.order_type {
+            OrderType::Limit => true,
+            OrderType::Stop | OrderType::StopLimit => {
+                if order.is_buy {
+                    price >= order.stop_price
+                } else {
+                    price <= order.stop_price
+                }
+            }
+        };
+    if !is_triggered {
+        return None;
+    }
+
+    /* check whether the quote is within the limit */
+    let is_marketable = match order.order_type {
+        OrderType::Stop => true,
+        OrderType::Limit | OrderType::StopLimit => {
+            if order.is_buy {
+                price <= order.stock_price
+            } else {
+                price >= order.stock_price
+            }
+        }
+    };
+    let fill_amount = if is_marketable {
+        (order.stock_amount - order.stock_filled).min(liquidity.max(0))
+    } else {
+        0
+    };
+
+    if fill_amount == 0 && is_triggered == order.is_triggered {
+        None
+    } else {
+        Some(OrderMatch {
+            is_triggered,
+            fill_amount,
+            fill_price: price,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn quote(ask_price: f64, bid_price: f64, volume: i64) -> StockVal {
+        StockVal {
+            ask_price,
+            bid_price,
+            volume,
+            ..StockVal::default()
+        }
+    }
+
+    #[test]
+    fn test_match_order_limit() {
+        let order = Order {
+            order_type: OrderType::Limit,
+            is_buy: true,
+            stock_price: 100.0,
+            stock_amount: 10,
+            is_triggered: true,
+            ..Order::default()
+        };
+
+        /* ask above the limit does not fill */
+        assert_eq!(match_order(&order, &quote(101.0, 100.5, 50), 50), None);
+
+        /* ask at or below the limit fills at the ask */
+        assert_eq!(
+            match_order(&order, &quote(99.0, 98.5, 50), 50),
+            Some(OrderMatch {
+                is_triggered: true,
+                fill_amount: 10,
+                fill_price: 99.0
+            })
+        );
+
+        /* fills are limited by the liquidity left */
+        assert_eq!(
+            match_order(&order, &quote(99.0, 98.5, 4), 4).unwrap().fill_amount,
+            4
+        );
+
+        /* sells fill at the bid once it reaches the limit */
+        let order = Order {
+            is_buy: false,
+            stock_filled: 6,
+            ..order
+        };
+        assert_eq!(match_order(&order, &quote(100.5, 99.5, 50), 50), None);
+        assert_eq!(
+            match_order(&order, &quote(101.0, 100.0, 50), 50),
+            Some(OrderMatch {
+                is_triggered: true,
+                fill_amount: 4,
+                fill_price: 100.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_match_order_stop() {
+        let order = Order {
+            order_type: OrderType::Stop,
+            is_buy: false,
+            stop_price: 90.0,
+            stock_amount: 10,
+            ..Order::default()
+        };
+
+        /* bid above the stop does not trigger */
+        assert_eq!(match_order(&order, &quote(95.5, 95.0, 50), 50), None);
+
+        /* bid at the stop triggers and fills at the bid */
+        assert_eq!(
+            match_order(&order, &quote(90.5, 89.0, 50), 50),
+            Some(OrderMatch {
+                is_triggered: true,
+                fill_amount: 10,
+                fill_price: 89.0
+            })
+        );
+
+        /* a trigger without liquidity is still reported */
+        assert_eq!(
+            match_order(&order, &quote(90.5, 89.0, 0), 0),
+            Some(OrderMatch {
+                is_triggered: true,
+                fill_amount: 0,
+                fill_price: 89.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_match_order_stop_limit() {
+        let order = Order {
+            order_type: OrderType::StopLimit,
+            is_buy: true,
+            stop_price: 110.0,
+            stock_price: 112.0,
+            stock_amount: 10,
+            ..Order::default()
+        };
+
+        /* the stop triggers but the ask is beyond the limit */
+        let order_match = match_order(&order, &quote(113.0, 112.5, 50), 50).unwrap();
+        assert!(order_match.is_triggered);
+        assert_eq!(order_match.fill_amount, 0);
+
+        /* once triggered it fills like a limit order */
+        let order = Order {
+            is_triggered: true,
+            ..order
+        };
+        assert_eq!(match_order(&order, &quote(113.0, 112.5, 50), 50), None);
+        assert_eq!(
+            match_order(&order, &quote(111.0, 110.5, 50), 50),
+            Some(OrderMatch {
+                is_triggered: true,
+                fill_amount: 10,
+                fill_price: 111.0
+            })
+        );
+    }
+}
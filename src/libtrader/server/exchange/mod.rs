@@ -0,0 +1,2 @@
+pub mod match_order;
+pub mod order_matcher;
@@ -0,0 +1,450 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use log::{info, warn};
+
+use crate::common::account::order::Order;
+use crate::common::generic::stock_val::StockVal;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::execute_fill::execute_fill;
+use crate::server::config::server_config;
+use crate::server::db::cmd::get_orders::{
+    get_working_order_for_update, get_working_orders_from_db,
+};
+use crate::server::db::cmd::get_stock::get_stock_from_db_between_epochs;
+use crate::server::db::cmd::update_order::{cancel_order, update_order_fill};
+use crate::server::db::initializer::db_connect;
+use crate::server::exchange::match_order::{match_order, OrderMatch};
+use crate::server::market::clock::market_now;
+
+/// The number of seconds between two checks for new ticks.
+pub static ORDER_MATCHER_INTERVAL: u64 = 1;
+
+/// Fills a matched order.
+///
+/// Locks the order, matches it again against the quote and writes the fill exactly as a market
+/// order does, together with the new fill state of the order, in one SQL transaction. Orders that
+/// can no longer be afforded are cancelled.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to write the fill with.
+/// user_id - The user that placed the order.
+/// order_id - The order to fill.
+/// quote - The quote to fill against.
+/// liquidity - The number of shares available to the order on the quote.
+///
+/// Returns: the order after the fill if it is still working, nothing if it was cancelled,
+/// ReturnFlags on error.
+async fn fill_order(
+    sql_conn: &mut tokio_postgres::Client,
+    user_id: i64,
+    order_id: i64,
+    quote: &StockVal,
+    liquidity: i64,
+) -> Result<Option<Order>, ReturnFlags> {
+    let sql_transaction = sql_conn
+        .transaction()
+        .await
+        .map_err(|_| ReturnFlags::ServerDbUpdateOrderFailed)?;
+
+    /* the order might have been cancelled since it was selected */
+    let mut order = match get_working_order_for_update(&sql_transaction, order_id).await {
+        Ok(order) => order,
+        Err(_) => return Ok(None),
+    };
+    let order_match = match match_order(&order, quote, liquidity) {
+        Some(order_match) => order_match,
+        None => return Ok(Some(order)),
+    };
+
+    if order_match.fill_amount > 0 {
+        match execute_fill(
+            &sql_transaction,
+            user_id,
+            &order.stock_symbol,
+            order_match.fill_amount,
+            order_match.fill_price,
+            order.is_buy,
+        )
+        .await
+        {
+            Ok(_) => {}
+            Err(ReturnFlags::ServerAccInsufficientFunds) => {
+                drop(sql_transaction);
+                warn!("ORDER_MATCHER_INSUFFICIENT_FUNDS: {}", order_id);
+                cancel_order(sql_conn, user_id, order_id).await?;
+                return Ok(None);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    apply_match(&mut order, &order_match);
+    update_order_fill(&sql_transaction, &order).await?;
+
+    sql_transaction
+        .commit()
+        .await
+        .map_err(|_| ReturnFlags::ServerDbUpdateOrderFailed)?;
+    Ok(Some(order))
+}
+
+/// Records a match in the fill state of an order.
+fn apply_match(order: &mut Order, order_match: &OrderMatch) {
+    order.stock_filled += order_match.fill_amount;
+    order.is_triggered = order_match.is_triggered;
+    order.is_filled = order.stock_filled >= order.stock_amount;
+}
+
+/// Matches the working orders of one symbol against a tick.
+///
+/// Orders are matched in the order they were placed, each fill uses up liquidity of the tick.
+/// Orders placed after the tick are skipped. The matched orders are updated as if their fills
+/// were written.
+///
+/// Arguments:
+/// orders - The working orders of the tick's symbol with the users that placed them.
+/// quote - The tick.
+///
+/// Returns: the index of every matched order together with its match.
+///
+/// Example:
+/// ```rust
+///     for (index, order_match) in match_tick(&mut orders, &quote) {
+///         /* write the fill of orders[index] */
+///     }
+/// ```
+pub fn match_tick(orders: &mut [(i64, Order)], quote: &StockVal) -> Vec<(usize, OrderMatch)> {
+    let mut matches = Vec::new();
+    let mut liquidity = quote.volume;
+    for (index, (_, order)) in orders.iter_mut().enumerate() {
+        if order.is_filled || order.create_epoch > quote.time_epoch {
+            continue;
+        }
+        if let Some(order_match) = match_order(order, quote, liquidity) {
+            liquidity -= order_match.fill_amount;
+            apply_match(order, &order_match);
+            matches.push((index, order_match));
+        }
+    }
+    matches
+}
+
+/// Matches the working orders of one symbol against new ticks, one tick after the other.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to write fills with.
+/// ticks - The new ticks of the symbol, oldest first.
+/// orders - The working orders of the symbol with the users that placed them.
+async fn match_ticks(
+    sql_conn: &mut tokio_postgres::Client,
+    ticks: &[StockVal],
+    mut orders: Vec<(i64, Order)>,
+) {
+    for quote in ticks {
+        for (index, order_match) in match_tick(&mut orders, quote) {
+            let (user_id, order) = &mut orders[index];
+            match fill_order(sql_conn, *user_id, order.id, quote, order_match.fill_amount).await {
+                Ok(Some(filled)) => *order = filled,
+                /* cancelled, stop matching it */
+                Ok(None) => order.is_filled = true,
+                Err(err) => {
+                    warn!("ORDER_MATCHER_FILL_FAILED: {}: {}", order.id, err);
+                    order.is_filled = true;
+                }
+            }
+        }
+        orders.retain(|(_, order)| !order.is_filled);
+        if orders.is_empty() {
+            break;
+        }
+    }
+}
+
+/// The last matched ticks of a symbol.
+///
+/// Members:
+/// time_epoch - The time epoch of the last matched tick.
+/// ids - The ids of the matched ticks at ```time_epoch```.
+#[derive(PartialEq, Debug, Default)]
+struct MatchedTicks {
+    time_epoch: i64,
+    ids: Vec<i64>,
+}
+
+impl MatchedTicks {
+    /// Records new matched ticks, oldest first.
+    fn record(&mut self, ticks: &[StockVal]) {
+        for tick in ticks {
+            if tick.time_epoch > self.time_epoch {
+                self.time_epoch = tick.time_epoch;
+                self.ids.clear();
+            }
+            if tick.time_epoch == self.time_epoch {
+                self.ids.push(tick.id);
+            }
+        }
+    }
+
+    /// Returns whether a tick loaded from ```next_tick_epoch()``` on was matched already.
+    fn is_matched(&self, tick: &StockVal) -> bool {
+        tick.time_epoch == self.time_epoch && self.ids.contains(&tick.id)
+    }
+}
+
+/// Returns the first time epoch to load new ticks of a symbol from.
+///
+/// Ticks before the last matched tick were matched already, and ticks before the first order
+/// was placed can not match any order. Ticks at the time of the last matched tick are loaded
+/// again, since more of them may have been stored since, and those matched already are then
+/// skipped with ```MatchedTicks::is_matched()```.
+///
+/// Ticks are tracked by time rather than by id, since the quotes replayed by the replay source
+/// were stored long before, in any order. Hence a tick stored after a later tick of the same
+/// symbol was matched is never matched.
+///
+/// Arguments:
+/// last_ticks - The last matched ticks of the symbol, if any.
+/// orders - The working orders of the symbol.
+///
+/// Returns: the first time epoch to load, nothing when there are no orders.
+fn next_tick_epoch(last_ticks: Option<&MatchedTicks>, orders: &[(i64, Order)]) -> Option<i64> {
+    let first_placed = orders.iter().map(|(_, order)| order.create_epoch).min()?;
+    Some(match last_ticks {
+        Some(last_ticks) => last_ticks.time_epoch.max(first_placed),
+        None => first_placed,
+    })
+}
+
+/// Background task matching working orders against new ticks.
+///
+/// Periodically loads every tick stored since the last check, up to the current market time,
+/// and matches the working orders of each symbol against them in time order. An order only
+/// matches ticks from the time it was placed on.
+/// This function should not return.
+///
+/// Returns: ReturnFlags when the SQL connection can not be established.
+///
+/// Example:
+/// ```rust
+///     tokio::spawn(order_matcher());
+/// ```
+pub async fn order_matcher() -> Result<(), ReturnFlags> {
+    /* connect to SQL database using user ```portfolio_schema_user``` */
+    let mut sql_conn = db_connect(&server_config().db_portfolio)
+        .await
+        .map_err(|_| ReturnFlags::ServerDbConnectFailed)?;
+    info!("ORDER_MATCHER_STARTED");
+
+    let mut last_ticks: HashMap<String, MatchedTicks> = HashMap::new();
+    let mut interval = tokio::time::interval(Duration::from_secs(ORDER_MATCHER_INTERVAL));
+    loop {
+        interval.tick().await;
+
+        /* group working orders by symbol */
+        let mut working_orders: BTreeMap<String, Vec<(i64, Order)>> = BTreeMap::new();
+        match get_working_orders_from_db(&sql_conn).await {
+            Ok(orders) => {
+                for (user_id, order) in orders {
+                    working_orders
+                        .entry(order.stock_symbol.clone())
+                        .or_default()
+                        .push((user_id, order));
+                }
+            }
+            Err(err) => {
+                warn!("ORDER_MATCHER_GET_ORDERS_FAILED: {}", err);
+                continue;
+            }
+        }
+
+        /* match every tick not seen before */
+        let now = market_now();
+        for (symbol, orders) in working_orders {
+            let since = match next_tick_epoch(last_ticks.get(&symbol), &orders) {
+                Some(since) => since,
+                None => continue,
+            };
+            let ticks = match get_stock_from_db_between_epochs(&sql_conn, &symbol, since, now, None)
+                .await
+            {
+                Ok(ticks) => ticks,
+                Err(err) => {
+                    warn!("ORDER_MATCHER_GET_TICKS_FAILED: {}: {}", symbol, err);
+                    continue;
+                }
+            };
+            let matched = last_ticks.entry(symbol).or_default();
+            let ticks: Vec<StockVal> = ticks
+                .into_iter()
+                .filter(|tick| !matched.is_matched(tick))
+                .collect();
+            if !ticks.is_empty() {
+                matched.record(&ticks);
+                match_ticks(&mut sql_conn, &ticks, orders).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::common::account::order::OrderType;
+    use crate::server::market::clock::ReplayClock;
+
+    fn tick(time_epoch: i64, ask_price: f64, bid_price: f64, volume: i64) -> StockVal {
+        StockVal {
+            symbol: "AAPL".into(),
+            time_epoch,
+            ask_price,
+            bid_price,
+            volume,
+            ..StockVal::default()
+        }
+    }
+
+    fn order(id: i64, order_type: OrderType, is_buy: bool, price: f64, create_epoch: i64) -> Order {
+        Order {
+            id,
+            order_type,
+            is_buy,
+            stock_symbol: "AAPL".into(),
+            stock_price: price,
+            stop_price: price,
+            stock_amount: 10,
+            is_triggered: order_type == OrderType::Limit,
+            create_epoch,
+            ..Order::default()
+        }
+    }
+
+    #[test]
+    fn test_match_tick() {
+        let mut orders = vec![
+            (1, order(1, OrderType::Limit, true, 100.0, 0)),
+            (2, order(2, OrderType::Limit, true, 100.0, 0)),
+            (3, order(3, OrderType::Limit, true, 100.0, 20)),
+        ];
+
+        /* the earlier order takes the liquidity first, the later order is not placed yet */
+        let matches = match_tick(&mut orders, &tick(10, 99.0, 98.0, 15));
+        assert_eq!(matches.len(), 2);
+        assert_eq!((matches[0].0, matches[0].1.fill_amount), (0, 10));
+        assert_eq!((matches[1].0, matches[1].1.fill_amount), (1, 5));
+        assert!(orders[0].1.is_filled);
+        assert_eq!(orders[1].1.stock_filled, 5);
+        assert_eq!(orders[2].1.stock_filled, 0);
+
+        /* filled orders are not matched again */
+        let matches = match_tick(&mut orders, &tick(20, 99.0, 98.0, 100));
+        assert_eq!(matches.len(), 2);
+        assert_eq!((matches[0].0, matches[0].1.fill_amount), (1, 5));
+        assert_eq!((matches[1].0, matches[1].1.fill_amount), (2, 10));
+    }
+
+    #[test]
+    fn test_match_tick_stop() {
+        let mut orders = vec![(1, order(1, OrderType::Stop, false, 95.0, 0))];
+
+        /* the stop triggers on one tick and stays triggered on the next */
+        assert!(match_tick(&mut orders, &tick(1, 97.0, 96.0, 100)).is_empty());
+        let matches = match_tick(&mut orders, &tick(2, 95.0, 94.0, 0));
+        assert_eq!(matches[0].1.fill_amount, 0);
+        assert!(orders[0].1.is_triggered);
+        let matches = match_tick(&mut orders, &tick(3, 97.0, 96.0, 100));
+        assert_eq!(matches[0].1.fill_amount, 10);
+        assert_eq!(matches[0].1.fill_price, 96.0);
+    }
+
+    #[test]
+    fn test_match_replay() {
+        let clock = ReplayClock {
+            start_epoch: 1_582_000_000,
+            end_epoch: 1_582_000_120,
+            speed: 60.0,
+        };
+        /* a tick every 5 market seconds, dipping below the limit between two checks */
+        let asks = [
+            103.0, 102.0, 101.0, 101.5, 100.5, 100.2, 100.1, 100.4, 100.3, 99.5, 99.0, 100.2,
+            101.0, 102.0, 101.0, 100.5, 100.7, 100.9, 101.2, 101.4, 101.1, 101.3, 101.6, 101.8,
+            102.0,
+        ];
+        let ticks: Vec<StockVal> = asks
+            .iter()
+            .enumerate()
+            .map(|(i, ask)| tick(clock.start_epoch + 5 * i as i64, *ask, ask - 0.1, 100))
+            .collect();
+        let mut orders = vec![(
+            1,
+            order(1, OrderType::Limit, true, 100.0, clock.start_epoch + 10),
+        )];
+
+        /* check once per real second, as the order matcher does */
+        let mut last_ticks = MatchedTicks::default();
+        let mut fills = Vec::new();
+        for elapsed in 1..=2 {
+            let now = clock.time_at(elapsed as f64);
+            let since = next_tick_epoch(Some(&last_ticks), &orders).unwrap();
+            let new_ticks: Vec<StockVal> = ticks
+                .iter()
+                .filter(|quote| {
+                    quote.time_epoch >= since
+                        && quote.time_epoch <= now
+                        && !last_ticks.is_matched(quote)
+                })
+                .cloned()
+                .collect();
+            last_ticks.record(&new_ticks);
+            for quote in &new_ticks {
+                for (_, order_match) in match_tick(&mut orders, quote) {
+                    fills.push((quote.time_epoch, order_match));
+                }
+            }
+        }
+
+        /* the order fills once, at the first tick crossing its limit */
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].0, clock.start_epoch + 45);
+        assert_eq!(fills[0].1.fill_price, 99.5);
+        assert_eq!(fills[0].1.fill_amount, 10);
+        assert!(orders[0].1.is_filled);
+    }
+
+    #[test]
+    fn test_matched_ticks() {
+        let orders = vec![(1, order(1, OrderType::Limit, true, 100.0, 5))];
+        let with_id = |id: i64, time_epoch: i64| StockVal {
+            id,
+            ..tick(time_epoch, 99.0, 98.0, 100)
+        };
+
+        /* ticks before the first order are not loaded */
+        assert_eq!(next_tick_epoch(None, &orders), Some(5));
+        assert_eq!(next_tick_epoch(None, &[]), None);
+
+        let mut last_ticks = MatchedTicks::default();
+        last_ticks.record(&[with_id(1, 10), with_id(2, 20), with_id(3, 20)]);
+        assert_eq!(next_tick_epoch(Some(&last_ticks), &orders), Some(20));
+
+        /* a tick stored later at the time of the last match is still new */
+        assert!(last_ticks.is_matched(&with_id(3, 20)));
+        assert!(!last_ticks.is_matched(&with_id(4, 20)));
+        last_ticks.record(&[with_id(4, 20)]);
+        assert_eq!(last_ticks.ids, vec![2, 3, 4]);
+
+        /* a tick stored later but older than the last match is not loaded again */
+        assert!(with_id(5, 15).time_epoch < next_tick_epoch(Some(&last_ticks), &orders).unwrap());
+
+        last_ticks.record(&[with_id(6, 30)]);
+        assert_eq!(
+            last_ticks,
+            MatchedTicks {
+                time_epoch: 30,
+                ids: vec![6]
+            }
+        );
+    }
+}
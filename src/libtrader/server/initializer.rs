@@ -1,34 +1,74 @@
 use std::io;
 use std::net::ToSocketAddrs;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use argh::FromArgs;
-use log::warn;
+use log::{info, warn};
 
-use tokio::io::AsyncReadExt;
+use futures::{SinkExt, StreamExt};
 use tokio::net::TcpListener;
 use tokio_rustls::TlsAcceptor;
+use tokio_util::codec::Framed;
 
+use crate::common::message::message_codec::MessageCodec;
 use crate::server::network::gen_tls_server_config::gen_tls_server_config;
 
+use crate::server::config::{server_config, set_server_config, ServerConfig};
 use crate::server::db::initializer::db_connect;
+use crate::server::db::migrations::{baseline as baseline_migrations, latest_version, migrate};
+use crate::server::exchange::order_matcher::order_matcher;
+use crate::server::market::clock::start_replay_clock;
+use crate::server::market::ingestion::market_ingestion;
+use crate::server::market::quote_cache::{load_global_state, log_cache_metrics};
+use crate::server::market::quote_source::QuoteSourceSetting;
 use crate::server::network::handle_data::handle_data;
+use crate::server::network::handshake::handshake;
+use crate::server::network::quote_subscription::QuoteSubscription;
 
 /// Server Options
 #[derive(FromArgs)]
 struct Options {
-    /// bind addr
+    /// configuration file, defaults to $SERVER_CONFIG
+    #[argh(option)]
+    config: Option<PathBuf>,
+
+    #[argh(subcommand)]
+    command: Command,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+enum Command {
+    Serve(ServeOptions),
+    Migrate(MigrateOptions),
+}
+
+/// Apply pending database migrations and run the server
+#[derive(FromArgs)]
+#[argh(subcommand, name = "serve")]
+struct ServeOptions {
+    /// bind addr, overrides the `server_addr` setting
     #[argh(positional)]
-    addr: String,
+    addr: Option<String>,
 
-    /// cert file
+    /// cert file, overrides the `tls_cert` setting
     #[argh(option, short = 'c')]
-    cert: PathBuf,
+    cert: Option<PathBuf>,
 
-    /// key file
+    /// key file, overrides the `tls_key` setting
     #[argh(option, short = 'k')]
-    key: PathBuf,
+    key: Option<PathBuf>,
+}
+
+/// Apply pending database migrations and exit
+#[derive(FromArgs)]
+#[argh(subcommand, name = "migrate")]
+struct MigrateOptions {
+    /// mark migrations up to this version as applied without running them, for databases
+    /// created before migrations were tracked
+    #[argh(option)]
+    baseline: Option<i64>,
 }
 
 tokio::task_local! {
@@ -109,8 +149,9 @@ fn libtrader_init_log() -> std::io::Result<()> {
 
 /// Server Initialization of the library.
 ///
-/// Public function that initializes the library, and starts the libtrader server.
-/// This function should not return.
+/// Public function that initializes the library, migrates the database, and starts the
+/// libtrader server. With the ```migrate``` subcommand it returns once the database is migrated.
+/// Otherwise, this function should not return.
 ///
 /// Example:
 /// ```rust
@@ -120,53 +161,191 @@ pub async fn libtrader_init_server() -> std::io::Result<()> {
     // Initialize log.
     libtrader_init_log()?;
 
-    // Initialize SQL connection
-    let sql_shared_conn = Arc::new(
-        db_connect(
-            std::env::var("DB_ACC_USER").unwrap(),
-            std::env::var("DB_ACC_PASS").unwrap(),
+    // Initialize arguments
+    let options: Options = argh::from_env();
+
+    // Load and validate the configuration
+    let config_path = options
+        .config
+        .or_else(|| std::env::var_os("SERVER_CONFIG").map(PathBuf::from));
+    let (overrides, serving) = match &options.command {
+        Command::Migrate(_) => (Vec::new(), false),
+        Command::Serve(serve_options) => (
+            vec![
+                ("server_addr", serve_options.addr.clone()),
+                ("tls_cert", path_setting(&serve_options.cert)),
+                ("tls_key", path_setting(&serve_options.key)),
+            ],
+            true,
+        ),
+    };
+    let config = ServerConfig::load(config_path.as_deref(), &overrides, serving)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err.to_string()))?;
+    set_server_config(config);
+
+    // Bring the database schema up to date
+    match options.command {
+        Command::Migrate(migrate_options) => {
+            libtrader_init_migrations(migrate_options.baseline).await
+        }
+        Command::Serve(_) => {
+            libtrader_init_migrations(None).await?;
+            libtrader_serve().await
+        }
+    }
+}
+
+/// Converts a path given on the command line to a setting value.
+fn path_setting(path: &Option<PathBuf>) -> Option<String> {
+    path.as_ref()
+        .map(|path| path.to_string_lossy().into_owned())
+}
+
+/// Applies the pending database migrations.
+///
+/// Private function used by libtrader_init_server() to migrate the database with the owner
+/// credentials ```db_user```. Fails if the database schema is newer than the binary.
+///
+/// Arguments:
+/// baseline - The migration version up to which to record migrations as applied first.
+///
+/// Returns: nothing on success, on error contains the reason of failure.
+async fn libtrader_init_migrations(baseline: Option<i64>) -> std::io::Result<()> {
+    let mut sql_conn = db_connect(&server_config().db_admin).await.map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::ConnectionAborted,
+            format!("SQL_CONNECTION_FAILED: {}", err),
         )
-        .await
-        .map_err(|err| {
+    })?;
+
+    let result = async {
+        if let Some(version) = baseline {
+            baseline_migrations(&mut sql_conn, version).await?;
+        }
+        migrate(&mut sql_conn).await
+    };
+    match result.await {
+        Ok(applied) => {
+            info!(
+                "SCHEMA_VERSION: {}, APPLIED_MIGRATIONS: {}",
+                latest_version(),
+                applied.len()
+            );
+            Ok(())
+        }
+        Err(err) => Err(io::Error::other(format!(
+            "LIBTRADER_INIT_MIGRATIONS_FAILED: {}",
+            err
+        ))),
+    }
+}
+
+/// Runs the libtrader server.
+///
+/// Private function used by libtrader_init_server() to accept client connections.
+/// This function should not return.
+async fn libtrader_serve() -> std::io::Result<()> {
+    let server_config = server_config();
+
+    // Initialize SQL connection
+    let sql_shared_conn = Arc::new(db_connect(&server_config.db_accounts).await.map_err(
+        |err| {
             io::Error::new(
                 io::ErrorKind::ConnectionAborted,
                 format!("SQL_CONNECTION_FAILED: {}", err),
             )
-        })?,
-    );
-
-    // Initialize arguments
-    let options: Options = argh::from_env();
+        },
+    )?);
 
-    let addr = options
-        .addr
+    let addr = server_config
+        .server_addr
         .to_socket_addrs()?
         .next()
         .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::AddrNotAvailable))?;
 
-    let config = gen_tls_server_config(&options.cert, &options.key)?;
+    let config = gen_tls_server_config(&server_config.tls_cert, &server_config.tls_key)?;
     let acceptor = TlsAcceptor::from(config);
 
     let listener = TcpListener::bind(&addr).await?;
 
+    // Replay market time before anything reads it
+    if server_config.market_source == Some(QuoteSourceSetting::Replay) {
+        start_replay_clock(server_config.market_replay);
+    }
+
+    // Load the global state
+    let global_state = Arc::new(RwLock::new(
+        load_global_state(&sql_shared_conn).await.map_err(|err| {
+            io::Error::other(format!("LIBTRADER_INIT_GLOBAL_STATE_FAILED: {}", err))
+        })?,
+    ));
+    tokio::spawn(IP.scope(addr, log_cache_metrics(global_state.clone())));
+
+    // Spawn the order matcher
+    tokio::spawn(IP.scope(addr, async move {
+        if let Err(err) = order_matcher().await {
+            warn!("ORDER_MATCHER_FAILED: {}", err);
+        }
+    }));
+
+    // Spawn the market data ingestion
+    if let Some(market_source) = &server_config.market_source {
+        let source = market_source.open(server_config);
+        let state = global_state.clone();
+        let interval = server_config.market_interval;
+        tokio::spawn(IP.scope(addr, async move {
+            if let Err(err) = market_ingestion(source, state, interval).await {
+                warn!("MARKET_INGESTION_FAILED: {}", err);
+            }
+        }));
+    }
+
     loop {
         let (socket, peer_addr) = listener.accept().await?; // socket, peer_addr
         let acceptor = acceptor.clone();
         let sql_conn = sql_shared_conn.clone();
+        let state = global_state.clone();
 
         // function to run in the thread
         let fut = async move {
-            let mut socket = acceptor.accept(socket).await?;
+            let mut socket = Framed::new(acceptor.accept(socket).await?, MessageCodec::new());
+            let hello = match handshake(&mut socket).await? {
+                Some(hello) => hello,
+                None => return Ok(()),
+            };
+            let mut subscription = QuoteSubscription::default();
             loop {
-                let mut buf = Vec::with_capacity(4096);
-                socket.read_buf(&mut buf).await?;
-                match handle_data(&sql_conn, &mut socket, buf.as_slice()).await {
-                    Ok(()) => {}
-                    Err(err) => {
-                        warn!("{}", format!("Failed running handle_data: {:#?}", err));
-                        break;
+                tokio::select! {
+                    client_msg = socket.next() => {
+                        let client_msg = match client_msg {
+                            Some(client_msg) => client_msg?,
+                            None => break,
+                        };
+                        match handle_data(
+                            &sql_conn,
+                            &state,
+                            &mut subscription,
+                            &hello.capabilities,
+                            &mut socket,
+                            client_msg,
+                        )
+                            .await
+                        {
+                            Ok(()) => {}
+                            Err(err) => {
+                                warn!("{}", format!("Failed running handle_data: {:#?}", err));
+                                break;
+                            }
+                        };
+                    }
+                    frames = subscription.next_frames() => {
+                        /* push the subscribed quotes */
+                        for frame in frames {
+                            socket.feed(frame).await?;
+                        }
+                        socket.flush().await?;
                     }
-                };
+                }
             }
 
             Ok(()) as std::io::Result<()>
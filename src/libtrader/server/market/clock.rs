@@ -0,0 +1,76 @@
+use std::sync::OnceLock;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// The replay clock, with the instant the replay started.
+static MARKET_CLOCK: OnceLock<(ReplayClock, Instant)> = OnceLock::new();
+
+/// A virtual market time running from ```start_epoch``` to ```end_epoch```, ```speed``` times as
+/// fast as the wall clock.
+///
+/// start_epoch - The market time the replay starts at.
+/// end_epoch - The market time the replay stops at.
+/// speed - The number of market seconds passing per real second.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct ReplayClock {
+    pub start_epoch: i64,
+    pub end_epoch: i64,
+    pub speed: f64,
+}
+
+impl ReplayClock {
+    /// Returns the market time after ```elapsed``` real seconds of replay.
+    pub fn time_at(&self, elapsed: f64) -> i64 {
+        let time_epoch = self.start_epoch as f64 + elapsed * self.speed;
+        (time_epoch as i64).min(self.end_epoch)
+    }
+}
+
+/// Starts replaying market time.
+///
+/// From then on ```market_now()``` follows the replay clock. The clock can only be started once.
+///
+/// Example:
+/// ```rust
+///     start_replay_clock(server_config.market_replay);
+/// ```
+pub fn start_replay_clock(clock: ReplayClock) {
+    let _ = MARKET_CLOCK.set((clock, Instant::now()));
+}
+
+/// Returns the current market time.
+///
+/// Market time is the wall clock, unless a replay clock was started. Quotes after the market time
+/// are ignored, and trades are recorded at the market time.
+///
+/// Returns: the market time as a unix epoch.
+///
+/// Example:
+/// ```rust
+///     let quote = get_stock_from_db_latest(&sql_conn, "AAPL", market_now()).await?;
+/// ```
+pub fn market_now() -> i64 {
+    match MARKET_CLOCK.get() {
+        Some((clock, started)) => clock.time_at(started.elapsed().as_secs_f64()),
+        None => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_replay_clock() {
+        let clock = ReplayClock {
+            start_epoch: 1_582_000_000,
+            end_epoch: 1_586_000_000,
+            speed: 3600.0,
+        };
+        assert_eq!(clock.time_at(0.0), 1_582_000_000);
+        assert_eq!(clock.time_at(2.5), 1_582_009_000);
+        assert_eq!(clock.time_at(1e9), 1_586_000_000);
+    }
+}
@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+
+use futures::future::BoxFuture;
+use log::warn;
+
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::market::quote_source::{
+    parse_csv_market_data, parse_json_market_data, MarketData, QuoteSource,
+};
+
+/// Reads market data from a CSV or JSON file.
+///
+/// Files ending in ```.csv``` are read as CSV quotes, any other file as JSON market data, see
+/// ```parse_csv_market_data``` and ```parse_json_market_data```. The file is read again on every
+/// fetch, so it can be appended to or replaced while the server runs.
+#[derive(PartialEq, Debug)]
+pub struct FileQuoteSource {
+    path: PathBuf,
+}
+
+impl FileQuoteSource {
+    pub fn new(path: PathBuf) -> FileQuoteSource {
+        FileQuoteSource { path }
+    }
+
+    async fn read(&self) -> Result<MarketData, String> {
+        let data = tokio::fs::read(&self.path)
+            .await
+            .map_err(|err| err.to_string())?;
+        let is_csv = self
+            .path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+        match is_csv {
+            true => parse_csv_market_data(&String::from_utf8_lossy(&data)),
+            false => parse_json_market_data(&data),
+        }
+    }
+}
+
+impl QuoteSource for FileQuoteSource {
+    fn name(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    fn fetch(&mut self) -> BoxFuture<'_, Result<MarketData, ReturnFlags>> {
+        Box::pin(async move {
+            self.read().await.map_err(|err| {
+                warn!("MARKET_SOURCE_FAILED: {}: {}", self.name(), err);
+                ReturnFlags::ServerMarketSourceFailed
+            })
+        })
+    }
+}
@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use log::warn;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::market::quote_source::{
+    parse_csv_market_data, parse_json_market_data, MarketData, QuoteSource,
+};
+
+/// The number of seconds to wait for an HTTP source to answer.
+pub static HTTP_SOURCE_TIMEOUT: u64 = 10;
+
+/// Reads market data from a plain HTTP endpoint.
+///
+/// Meant for a quote feed running next to the server, or a stand-in for one in tests. Every fetch
+/// sends a ```GET``` request and reads the body as CSV quotes if its content type is
+/// ```text/csv```, as JSON market data otherwise.
+#[derive(PartialEq, Debug)]
+pub struct HttpQuoteSource {
+    host: String,
+    path: String,
+}
+
+impl HttpQuoteSource {
+    /// Creates a source from an ```http://host[:port]/path``` URL.
+    ///
+    /// Returns: the source on success, the reason on error.
+    pub fn from_url(url: &str) -> Result<HttpQuoteSource, String> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or(format!("only http:// URLs are supported: {}", url))?;
+        let (host, path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, "/"),
+        };
+        if host.is_empty() {
+            return Err(format!("missing host in URL: {}", url));
+        }
+        let host = match host.contains(':') {
+            true => host.to_string(),
+            false => format!("{}:80", host),
+        };
+        Ok(HttpQuoteSource {
+            host,
+            path: path.to_string(),
+        })
+    }
+
+    async fn request(&self) -> Result<MarketData, String> {
+        let mut stream = TcpStream::connect(&self.host)
+            .await
+            .map_err(|err| err.to_string())?;
+        let request = format!(
+            "GET {} HTTP/1.0\r\nHost: {}\r\nAccept: application/json, text/csv\r\n\r\n",
+            self.path, self.host
+        );
+        stream
+            .write_all(request.as_bytes())
+            .await
+            .map_err(|err| err.to_string())?;
+        let mut response = Vec::new();
+        stream
+            .read_to_end(&mut response)
+            .await
+            .map_err(|err| err.to_string())?;
+        parse_http_response(&response)
+    }
+}
+
+impl QuoteSource for HttpQuoteSource {
+    fn name(&self) -> String {
+        format!("http://{}{}", self.host, self.path)
+    }
+
+    fn fetch(&mut self) -> BoxFuture<'_, Result<MarketData, ReturnFlags>> {
+        Box::pin(async move {
+            let timeout = Duration::from_secs(HTTP_SOURCE_TIMEOUT);
+            let result = match tokio::time::timeout(timeout, self.request()).await {
+                Ok(result) => result,
+                Err(_) => Err("timed out".to_string()),
+            };
+            result.map_err(|err| {
+                warn!("MARKET_SOURCE_FAILED: {}: {}", self.name(), err);
+                ReturnFlags::ServerMarketSourceFailed
+            })
+        })
+    }
+}
+
+/// Parses the market data out of an HTTP/1.0 response.
+fn parse_http_response(response: &[u8]) -> Result<MarketData, String> {
+    let header_end = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or("incomplete response")?;
+    let head = String::from_utf8_lossy(&response[..header_end]);
+    let body = &response[header_end + 4..];
+
+    let mut lines = head.lines();
+    let status = lines.next().unwrap_or_default();
+    if status.split_whitespace().nth(1) != Some("200") {
+        return Err(format!("unexpected status: {}", status));
+    }
+    let is_csv = lines.any(|line| {
+        let line = line.to_ascii_lowercase();
+        line.starts_with("content-type:") && line.contains("text/csv")
+    });
+    match is_csv {
+        true => parse_csv_market_data(&String::from_utf8_lossy(body)),
+        false => parse_json_market_data(body),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    /// Serves a single HTTP response on a local port, standing in for a quote feed.
+    async fn stand_in(content_type: &'static str, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await.unwrap();
+            let response = format!(
+                "HTTP/1.0 200 OK\r\nContent-Type: {}\r\n\r\n{}",
+                content_type, body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        format!("http://{}/quotes", addr)
+    }
+
+    #[tokio::test]
+    async fn test_http_quote_source() {
+        let url = stand_in(
+            "text/csv",
+            "symbol,isin,time_epoch,ask_price,bid_price,volume\nAAPL,US0378331005,1000,2,1,5\n",
+        )
+        .await;
+        let mut source = HttpQuoteSource::from_url(&url).unwrap();
+        let market_data = source.fetch().await.unwrap();
+        assert_eq!(market_data.quotes.len(), 1);
+        assert_eq!(market_data.quotes[0].symbol, "AAPL");
+
+        let url = stand_in("application/json", r#"{"companies": [{"symbol": "AAPL"}]}"#).await;
+        let mut source = HttpQuoteSource::from_url(&url).unwrap();
+        let market_data = source.fetch().await.unwrap();
+        assert_eq!(market_data.companies[0].symbol, "AAPL");
+
+        assert!(parse_http_response(b"HTTP/1.0 404 Not Found\r\n\r\n").is_err());
+        assert_eq!(
+            HttpQuoteSource::from_url("http://localhost").unwrap(),
+            HttpQuoteSource {
+                host: "localhost:80".into(),
+                path: "/".into()
+            }
+        );
+    }
+}
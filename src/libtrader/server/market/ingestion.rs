@@ -0,0 +1,154 @@
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use log::{info, warn};
+
+use crate::common::generic::stock_val::StockVal;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::config::server_config;
+use crate::server::db::cmd::create_company::create_company;
+use crate::server::db::cmd::create_stock::create_stock_val;
+use crate::server::db::cmd::get_company::get_company_from_db;
+use crate::server::db::initializer::db_connect;
+use crate::server::ds::global_state::GlobalState;
+use crate::server::market::quote_source::QuoteSource;
+
+/// Checks that a quote can be traded against.
+fn is_valid_quote(quote: &StockVal) -> bool {
+    !quote.symbol.is_empty()
+        && quote.ask_price.is_finite()
+        && quote.bid_price.is_finite()
+        && quote.ask_price > 0.0
+        && quote.bid_price > 0.0
+        && quote.volume >= 0
+}
+
+/// Ingests the current market data of a quote source.
+///
+/// Stores the companies not known yet and every quote newer than the latest known quote of its
+/// symbol, then records them in the global state. Symbols are stored in upper case. Invalid
+/// quotes are skipped.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use, with write access to ```asset_schema```.
+/// source - The quote source to read from.
+/// state - The global state to record the latest quotes in.
+///
+/// Returns: the number of stored quotes on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let stored = ingest(&sql_conn, source.as_mut(), &state).await?;
+/// ```
+pub async fn ingest(
+    sql_conn: &tokio_postgres::Client,
+    source: &mut dyn QuoteSource,
+    state: &RwLock<GlobalState>,
+) -> Result<usize, ReturnFlags> {
+    let market_data = source.fetch().await?;
+
+    for mut company in market_data.companies {
+        company.symbol = company.symbol.to_uppercase();
+        if state
+            .read()
+            .unwrap()
+            .companies
+            .contains_key(&company.symbol)
+        {
+            continue;
+        }
+        let company = match get_company_from_db(sql_conn, &company.symbol).await {
+            Ok(stored) => stored,
+            Err(_) => create_company(sql_conn, company).await?,
+        };
+        info!("MARKET_INGESTION_COMPANY: {}", company.symbol);
+        state
+            .write()
+            .unwrap()
+            .companies
+            .insert(company.symbol.clone(), company);
+    }
+
+    let mut quotes = market_data.quotes;
+    quotes.sort_by_key(|quote| quote.time_epoch);
+    let mut stored = 0;
+    for mut quote in quotes {
+        quote.symbol = quote.symbol.to_uppercase();
+        if !is_valid_quote(&quote) {
+            warn!("MARKET_INGESTION_INVALID_QUOTE: {}", quote);
+            continue;
+        }
+        let is_known = match state.read().unwrap().stock_vals.get(&quote.symbol) {
+            Some(latest) => latest.time_epoch >= quote.time_epoch,
+            None => false,
+        };
+        if is_known {
+            continue;
+        }
+        create_stock_val(sql_conn, &quote).await?;
+        state.write().unwrap().update_stock_val(&quote);
+        stored += 1;
+    }
+    Ok(stored)
+}
+
+/// Background task ingesting market data.
+///
+/// Polls the quote source every ```interval``` seconds and ingests its market data, see
+/// ```ingest```. Failures are logged and retried on the next poll.
+/// This function should not return.
+///
+/// Arguments:
+/// source - The quote source to poll.
+/// state - The global state to record the latest quotes in.
+/// interval - The number of seconds between two polls.
+///
+/// Returns: ReturnFlags when the SQL connection can not be established.
+///
+/// Example:
+/// ```rust
+///     tokio::spawn(market_ingestion(source, state.clone(), 60));
+/// ```
+pub async fn market_ingestion(
+    mut source: Box<dyn QuoteSource>,
+    state: Arc<RwLock<GlobalState>>,
+    interval: u64,
+) -> Result<(), ReturnFlags> {
+    /* connect to SQL database as the owner of ```asset_schema``` */
+    let sql_conn = db_connect(&server_config().db_admin)
+        .await
+        .map_err(|_| ReturnFlags::ServerDbConnectFailed)?;
+    info!("MARKET_INGESTION_STARTED: {}", source.name());
+
+    let mut interval = tokio::time::interval(Duration::from_secs(interval));
+    loop {
+        interval.tick().await;
+        match ingest(&sql_conn, source.as_mut(), &state).await {
+            Ok(0) => {}
+            Ok(stored) => info!("MARKET_INGESTION_STORED: {}", stored),
+            Err(err) => warn!("MARKET_INGESTION_FAILED: {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_quote() {
+        let mut quote = StockVal {
+            symbol: "AAPL".into(),
+            ask_price: 2.0,
+            bid_price: 1.0,
+            ..StockVal::default()
+        };
+        assert!(is_valid_quote(&quote));
+        quote.ask_price = f64::NAN;
+        assert!(!is_valid_quote(&quote));
+        quote.ask_price = 2.0;
+        quote.volume = -1;
+        assert!(!is_valid_quote(&quote));
+    }
+}
@@ -0,0 +1,8 @@
+pub mod clock;
+pub mod file_source;
+pub mod http_source;
+pub mod ingestion;
+pub mod quote_cache;
+pub mod quote_source;
+pub mod replay;
+pub mod simulator;
@@ -0,0 +1,105 @@
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use log::info;
+
+use crate::common::generic::stock_val::StockVal;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::config::server_config;
+use crate::server::db::cmd::get_company::get_companies_from_db;
+use crate::server::db::cmd::get_stock::{get_stock_from_db_latest, get_stocks_from_db_latest};
+use crate::server::ds::global_state::{CacheMetrics, GlobalState};
+use crate::server::market::clock::market_now;
+
+/// The number of seconds between two logs of the cache metrics.
+pub static CACHE_METRICS_INTERVAL: u64 = 60;
+
+/// Loads the global state from the postgres SQL database.
+///
+/// Reads every company and the latest quote of each of them.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use.
+///
+/// Returns: the global state on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let global_state = Arc::new(RwLock::new(load_global_state(&sql_conn).await?));
+/// ```
+pub async fn load_global_state(
+    sql_conn: &tokio_postgres::Client,
+) -> Result<GlobalState, ReturnFlags> {
+    let mut state = GlobalState::default();
+    for company in get_companies_from_db(sql_conn).await? {
+        state.companies.insert(company.symbol.clone(), company);
+    }
+    let symbols: Vec<String> = state.companies.keys().cloned().collect();
+    for stock_val in get_stocks_from_db_latest(sql_conn, &symbols, market_now()).await? {
+        state.update_stock_val(&stock_val);
+    }
+    Ok(state)
+}
+
+/// Returns the latest quote of a stock.
+///
+/// Serves the quote from the global state if it was refreshed within ```quote_cache_ttl```
+/// seconds, and reads it from the database into the global state otherwise. Ingested quotes are
+/// recorded in the global state as they arrive, the time to live only bounds how late quotes
+/// written by other processes are seen.
+///
+/// Arguments:
+/// sql_conn - The SQL connection to use on a miss.
+/// state - The global state.
+/// symbol - The symbol of the stock.
+///
+/// Returns: the quote on success, ReturnFlags on error.
+///
+/// Example:
+/// ```rust
+///     let quote = latest_stock_val(&sql_conn, &state, "AAPL").await?;
+/// ```
+pub async fn latest_stock_val(
+    sql_conn: &tokio_postgres::Client,
+    state: &RwLock<GlobalState>,
+    symbol: &str,
+) -> Result<StockVal, ReturnFlags> {
+    let max_age = Duration::from_secs(server_config().quote_cache_ttl);
+    if let Some(stock_val) = state.read().unwrap().cached_stock_val(symbol, max_age) {
+        return Ok(stock_val);
+    }
+
+    let stock_val = get_stock_from_db_latest(sql_conn, symbol, market_now()).await?;
+    state.write().unwrap().update_stock_val(&stock_val);
+    Ok(stock_val)
+}
+
+/// Background task logging the cache metrics of the global state.
+///
+/// Logs the hits and misses every ```CACHE_METRICS_INTERVAL``` seconds, when they changed.
+/// This function does not return.
+///
+/// Example:
+/// ```rust
+///     tokio::spawn(log_cache_metrics(global_state.clone()));
+/// ```
+pub async fn log_cache_metrics(state: Arc<RwLock<GlobalState>>) {
+    let mut logged = CacheMetrics::default();
+    let mut interval = tokio::time::interval(Duration::from_secs(CACHE_METRICS_INTERVAL));
+    loop {
+        interval.tick().await;
+        let metrics = state.read().unwrap().cache_metrics();
+        if metrics == logged {
+            continue;
+        }
+        let lookups = metrics.hits + metrics.misses;
+        info!(
+            "GLOBAL_STATE_CACHE: {} hits, {} misses, {:.1}% hit rate",
+            metrics.hits,
+            metrics.misses,
+            100.0 * metrics.hits as f64 / lookups as f64
+        );
+        logged = metrics;
+    }
+}
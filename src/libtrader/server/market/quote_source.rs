@@ -0,0 +1,250 @@
+use std::path::PathBuf;
+
+use futures::future::BoxFuture;
+use serde::Deserialize;
+
+use crate::common::generic::company::Company;
+use crate::common::generic::stock_val::StockVal;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::config::ServerConfig;
+use crate::server::market::file_source::FileQuoteSource;
+use crate::server::market::http_source::HttpQuoteSource;
+use crate::server::market::replay::ReplayQuoteSource;
+use crate::server::market::simulator::SimulatedQuoteSource;
+
+/// The companies and quotes read from a quote source at once.
+#[derive(PartialEq, Debug, Default)]
+pub struct MarketData {
+    pub companies: Vec<Company>,
+    pub quotes: Vec<StockVal>,
+}
+
+/// A source of market data, polled by the ingestion service.
+///
+/// Sources return everything they currently know on every fetch. Quotes that were already
+/// ingested are skipped when they are stored, so sources do not need to remember what they
+/// returned before.
+///
+/// Example:
+/// ```rust
+///     let mut source = FileQuoteSource::new(PathBuf::from("quotes.csv"));
+///     let market_data = source.fetch().await?;
+/// ```
+pub trait QuoteSource: Send {
+    /// Describes the source for the log.
+    fn name(&self) -> String;
+
+    /// Reads the current market data from the source.
+    ///
+    /// Returns: the market data on success, ```ServerMarketSourceFailed``` on error.
+    fn fetch(&mut self) -> BoxFuture<'_, Result<MarketData, ReturnFlags>>;
+}
+
+/// The quote source named by the ```market_source``` setting.
+#[derive(PartialEq, Debug, Clone)]
+pub enum QuoteSourceSetting {
+    File(PathBuf),
+    Http(String),
+    Simulator,
+    Replay,
+}
+
+impl QuoteSourceSetting {
+    /// Parses the ```market_source``` setting.
+    ///
+    /// ```simulator``` names the market simulator, ```replay``` the replay of stored quotes, an
+    /// ```http://``` URL an HTTP source, anything else a CSV or JSON file.
+    ///
+    /// Returns: the source, None if the setting is empty, the reason on error.
+    pub fn parse(setting: &str) -> Result<Option<QuoteSourceSetting>, String> {
+        let setting = setting.trim();
+        if setting.is_empty() {
+            return Ok(None);
+        }
+        if setting == "simulator" {
+            return Ok(Some(QuoteSourceSetting::Simulator));
+        }
+        if setting == "replay" {
+            return Ok(Some(QuoteSourceSetting::Replay));
+        }
+        if setting.contains("://") {
+            HttpQuoteSource::from_url(setting)?;
+            return Ok(Some(QuoteSourceSetting::Http(setting.to_string())));
+        }
+        Ok(Some(QuoteSourceSetting::File(PathBuf::from(setting))))
+    }
+
+    /// Opens the named quote source.
+    ///
+    /// Arguments:
+    /// config - The server configuration, holding the parameters of the source.
+    pub fn open(&self, config: &ServerConfig) -> Box<dyn QuoteSource> {
+        match self {
+            QuoteSourceSetting::File(path) => Box::new(FileQuoteSource::new(path.clone())),
+            /* the URL was checked by parse() */
+            QuoteSourceSetting::Http(url) => Box::new(HttpQuoteSource::from_url(url).unwrap()),
+            QuoteSourceSetting::Simulator => Box::new(SimulatedQuoteSource::new(
+                config.market_simulator.clone(),
+                config.market_interval,
+            )),
+            QuoteSourceSetting::Replay => Box::new(ReplayQuoteSource::new(config.market_replay)),
+        }
+    }
+}
+
+/// The JSON forms of market data: a list of quotes, or an object of companies and quotes.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MarketDataJson {
+    Quotes(Vec<StockVal>),
+    Full {
+        #[serde(default)]
+        companies: Vec<Company>,
+        #[serde(default)]
+        quotes: Vec<StockVal>,
+    },
+}
+
+/// Parses market data in JSON.
+///
+/// Accepts a list of quotes, or an object with ```companies``` and ```quotes``` lists. Ids are
+/// optional and ignored.
+///
+/// Returns: the market data on success, the reason on error.
+pub fn parse_json_market_data(data: &[u8]) -> Result<MarketData, String> {
+    match serde_json::from_slice(data) {
+        Ok(MarketDataJson::Quotes(quotes)) => Ok(MarketData {
+            companies: Vec::new(),
+            quotes,
+        }),
+        Ok(MarketDataJson::Full { companies, quotes }) => Ok(MarketData { companies, quotes }),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Parses quotes in CSV.
+///
+/// The first line names the columns, ```symbol```, ```isin```, ```time_epoch```,
+/// ```ask_price```, ```bid_price``` and ```volume``` in any order. Empty lines and lines
+/// starting with ```#``` are skipped.
+///
+/// Returns: the market data on success, the reason on error.
+pub fn parse_csv_market_data(data: &str) -> Result<MarketData, String> {
+    let mut lines = data
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let header: Vec<&str> = match lines.next() {
+        Some((_, header)) => header.split(',').map(|column| column.trim()).collect(),
+        None => return Ok(MarketData::default()),
+    };
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|column| *column == name)
+            .ok_or(format!("missing column `{}`", name))
+    };
+    let symbol = column("symbol")?;
+    let isin = column("isin")?;
+    let time_epoch = column("time_epoch")?;
+    let ask_price = column("ask_price")?;
+    let bid_price = column("bid_price")?;
+    let volume = column("volume")?;
+
+    let mut quotes = Vec::new();
+    for (line_number, line) in lines {
+        let fields: Vec<&str> = line.split(',').map(|field| field.trim()).collect();
+        if fields.len() != header.len() {
+            return Err(format!(
+                "line {}: expected {} fields, found {}",
+                line_number,
+                header.len(),
+                fields.len()
+            ));
+        }
+        let invalid = |name: &str| format!("line {}: invalid {}", line_number, name);
+        quotes.push(StockVal {
+            id: 0,
+            symbol: fields[symbol].to_string(),
+            isin: fields[isin].to_string(),
+            time_epoch: fields[time_epoch]
+                .parse()
+                .map_err(|_| invalid("time_epoch"))?,
+            ask_price: fields[ask_price]
+                .parse()
+                .map_err(|_| invalid("ask_price"))?,
+            bid_price: fields[bid_price]
+                .parse()
+                .map_err(|_| invalid("bid_price"))?,
+            volume: fields[volume].parse().map_err(|_| invalid("volume"))?,
+        });
+    }
+    Ok(MarketData {
+        companies: Vec::new(),
+        quotes,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_market_data() {
+        let csv = "# quotes\n\
+                   time_epoch,symbol,isin,ask_price,bid_price,volume\n\
+                   1000,AAPL,US0378331005,150.5,150.25,300\n\n";
+        let quote = StockVal {
+            id: 0,
+            symbol: "AAPL".into(),
+            isin: "US0378331005".into(),
+            time_epoch: 1000,
+            ask_price: 150.5,
+            bid_price: 150.25,
+            volume: 300,
+        };
+        assert_eq!(
+            parse_csv_market_data(csv).unwrap().quotes,
+            vec![quote.clone()]
+        );
+        assert!(parse_csv_market_data("symbol,isin\nAAPL,US0378331005").is_err());
+        assert!(parse_csv_market_data(
+            "symbol,isin,time_epoch,ask_price,bid_price,volume\nAAPL,X,now,1,1,1"
+        )
+        .is_err());
+
+        let json = br#"[{"symbol": "AAPL", "isin": "US0378331005", "time_epoch": 1000,
+                         "ask_price": 150.5, "bid_price": 150.25, "volume": 300}]"#;
+        assert_eq!(
+            parse_json_market_data(json).unwrap().quotes,
+            vec![quote.clone()]
+        );
+        let json = br#"{"companies": [{"symbol": "AAPL", "isin": "US0378331005",
+                                       "company_name": "Apple Inc."}]}"#;
+        let market_data = parse_json_market_data(json).unwrap();
+        assert_eq!(market_data.companies[0].company_name, "Apple Inc.");
+        assert!(market_data.quotes.is_empty());
+    }
+
+    #[test]
+    fn test_quote_source_setting() {
+        assert_eq!(QuoteSourceSetting::parse(" "), Ok(None));
+        assert_eq!(
+            QuoteSourceSetting::parse("data/quotes.csv"),
+            Ok(Some(QuoteSourceSetting::File(PathBuf::from(
+                "data/quotes.csv"
+            ))))
+        );
+        assert_eq!(
+            QuoteSourceSetting::parse("simulator"),
+            Ok(Some(QuoteSourceSetting::Simulator))
+        );
+        assert!(QuoteSourceSetting::parse("http://127.0.0.1:8080/quotes")
+            .unwrap()
+            .is_some());
+        assert!(QuoteSourceSetting::parse("https://example.com/quotes").is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use futures::future::BoxFuture;
+use log::{info, warn};
+
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::config::server_config;
+use crate::server::db::cmd::get_company::get_companies_from_db;
+use crate::server::db::cmd::get_stock::get_stock_from_db_between_epochs;
+use crate::server::db::initializer::db_connect;
+use crate::server::market::clock::{market_now, ReplayClock};
+use crate::server::market::quote_source::{MarketData, QuoteSource};
+
+/// Replays the stored quotes of every company in ```public.companies```.
+///
+/// Every fetch returns the quotes stored between the previous fetch and the current market time,
+/// so the quotes reach the server as if they were ticking live. The market time must follow the
+/// replay clock, see ```start_replay_clock```.
+pub struct ReplayQuoteSource {
+    clock: ReplayClock,
+    replayed_until: i64,
+    sql_conn: Option<tokio_postgres::Client>,
+}
+
+impl ReplayQuoteSource {
+    pub fn new(clock: ReplayClock) -> ReplayQuoteSource {
+        ReplayQuoteSource {
+            clock,
+            replayed_until: clock.start_epoch - 1,
+            sql_conn: None,
+        }
+    }
+
+    async fn replay(&mut self) -> Result<MarketData, ReturnFlags> {
+        let now = market_now();
+        if now <= self.replayed_until {
+            return Ok(MarketData::default());
+        }
+
+        if self.sql_conn.is_none() {
+            /* connect to SQL database using user ```accounts_schema_user``` */
+            let sql_conn = db_connect(&server_config().db_accounts)
+                .await
+                .map_err(|_| ReturnFlags::ServerDbConnectFailed)?;
+            self.sql_conn = Some(sql_conn);
+        }
+        let sql_conn = self.sql_conn.as_ref().unwrap();
+
+        let mut quotes = Vec::new();
+        for company in get_companies_from_db(sql_conn).await? {
+            quotes.append(
+                &mut get_stock_from_db_between_epochs(
+                    sql_conn,
+                    &company.symbol,
+                    self.replayed_until + 1,
+                    now,
+                    None,
+                )
+                .await?,
+            );
+        }
+
+        self.replayed_until = now;
+        if now >= self.clock.end_epoch {
+            info!("MARKET_REPLAY_FINISHED: {}", now);
+        }
+        Ok(MarketData {
+            companies: Vec::new(),
+            quotes,
+        })
+    }
+}
+
+impl QuoteSource for ReplayQuoteSource {
+    fn name(&self) -> String {
+        format!(
+            "replay of {} to {} at {}x",
+            self.clock.start_epoch, self.clock.end_epoch, self.clock.speed
+        )
+    }
+
+    fn fetch(&mut self) -> BoxFuture<'_, Result<MarketData, ReturnFlags>> {
+        Box::pin(async move {
+            self.replay().await.map_err(|err| {
+                /* reconnect on the next tick */
+                self.sql_conn = None;
+                warn!("MARKET_SOURCE_FAILED: {}: {}", self.name(), err);
+                ReturnFlags::ServerMarketSourceFailed
+            })
+        })
+    }
+}
@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+
+use futures::future::BoxFuture;
+use log::{info, warn};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::common::generic::company::Company;
+use crate::common::generic::stock_val::StockVal;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::config::server_config;
+use crate::server::db::cmd::get_company::get_companies_from_db;
+use crate::server::db::cmd::get_stock::get_stock_from_db_latest;
+use crate::server::db::initializer::db_connect;
+use crate::server::market::clock::market_now;
+use crate::server::market::quote_source::{MarketData, QuoteSource};
+
+/// The number of seconds in a year, the time unit of the model parameters.
+pub static SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 60.0 * 60.0;
+
+/// The lowest price the simulator quotes.
+pub static MIN_PRICE: f64 = 0.01;
+
+/// How the mid price of a simulated stock evolves between ticks.
+///
+/// Rates are per year, the time between two ticks is converted to years.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum SimulationModel {
+    /// Geometric Brownian motion: the price drifts by ```drift``` per year, with log returns of
+    /// standard deviation ```volatility``` over a year.
+    GeometricBrownian { drift: f64, volatility: f64 },
+    /// Mean reversion: the log price is pulled back to the log of the first price of the stock
+    /// at rate ```speed```, with noise of standard deviation ```volatility``` over a year.
+    MeanReversion { speed: f64, volatility: f64 },
+}
+
+impl SimulationModel {
+    /// Parses the name of a model, taking its parameters from the ```sim_*``` settings.
+    pub fn from_name(
+        name: &str,
+        drift: f64,
+        speed: f64,
+        volatility: f64,
+    ) -> Option<SimulationModel> {
+        match name {
+            "gbm" => Some(SimulationModel::GeometricBrownian { drift, volatility }),
+            "mean_reversion" => Some(SimulationModel::MeanReversion { speed, volatility }),
+            _ => None,
+        }
+    }
+}
+
+/// The parameters of the market simulator.
+///
+/// model - How the mid prices evolve.
+/// jump_intensity - The expected number of price jumps per stock and year.
+/// jump_size - The standard deviation of the log return of a jump.
+/// spread - The distance between the bid and the ask, relative to the mid price.
+/// volume - The average number of shares offered per tick.
+/// initial_price - The first mid price of stocks without any stored quote.
+/// seed - The seed of the random numbers, random if None.
+#[derive(PartialEq, Debug, Clone)]
+pub struct SimulatorConfig {
+    pub model: SimulationModel,
+    pub jump_intensity: f64,
+    pub jump_size: f64,
+    pub spread: f64,
+    pub volume: i64,
+    pub initial_price: f64,
+    pub seed: Option<u64>,
+}
+
+/// The simulated price of a stock.
+#[derive(PartialEq, Debug, Clone, Copy)]
+struct SimulatedPrice {
+    mid: f64,
+    mean: f64,
+}
+
+/// Generates quotes following a ```SimulationModel```.
+///
+/// Given the same seed, the same starting prices and the same sequence of ticks, the simulator
+/// generates the same quotes.
+///
+/// Example:
+/// ```rust
+///     let mut simulator = Simulator::new(config);
+///     let quote = simulator.tick(&company, now, 60.0);
+/// ```
+#[derive(Debug)]
+pub struct Simulator {
+    config: SimulatorConfig,
+    rng: StdRng,
+    prices: HashMap<String, SimulatedPrice>, // symbol, price
+}
+
+impl Simulator {
+    pub fn new(config: SimulatorConfig) -> Simulator {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Simulator {
+            config,
+            rng,
+            prices: HashMap::new(),
+        }
+    }
+
+    /// Checks whether a stock has a simulated price.
+    pub fn is_simulated(&self, symbol: &str) -> bool {
+        self.prices.contains_key(symbol)
+    }
+
+    /// Starts simulating a stock at a mid price.
+    pub fn start(&mut self, symbol: &str, mid: f64) {
+        let mid = mid.max(MIN_PRICE);
+        self.prices
+            .insert(symbol.to_string(), SimulatedPrice { mid, mean: mid });
+    }
+
+    /// Draws a number from the standard normal distribution.
+    fn standard_normal(&mut self) -> f64 {
+        /* Box-Muller transform, 1 - u keeps the logarithm finite */
+        let u: f64 = 1.0 - self.rng.gen::<f64>();
+        let v: f64 = self.rng.gen::<f64>();
+        (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+    }
+
+    /// Generates the next quote of a company.
+    ///
+    /// Stocks that are not simulated yet start at ```initial_price```.
+    ///
+    /// Arguments:
+    /// company - The company to quote.
+    /// time_epoch - The time of the quote.
+    /// elapsed - The number of seconds since the previous tick.
+    ///
+    /// Returns: the quote.
+    pub fn tick(&mut self, company: &Company, time_epoch: i64, elapsed: f64) -> StockVal {
+        if !self.is_simulated(&company.symbol) {
+            self.start(&company.symbol, self.config.initial_price);
+        }
+        let mut price = self.prices[&company.symbol];
+        let dt = elapsed / SECONDS_PER_YEAR;
+
+        /* the log return of the model */
+        let noise = self.standard_normal();
+        let mut log_return = match self.config.model {
+            SimulationModel::GeometricBrownian { drift, volatility } => {
+                (drift - volatility * volatility / 2.0) * dt + volatility * dt.sqrt() * noise
+            }
+            SimulationModel::MeanReversion { speed, volatility } => {
+                let reversion = 1.0 - (-speed * dt).exp();
+                (price.mean.ln() - price.mid.ln()) * reversion + volatility * dt.sqrt() * noise
+            }
+        };
+
+        /* a jump happens with the probability of a poisson event within dt */
+        let jump_probability = 1.0 - (-self.config.jump_intensity * dt).exp();
+        if self.rng.gen::<f64>() < jump_probability {
+            log_return += self.config.jump_size * self.standard_normal();
+        }
+
+        price.mid = (price.mid * log_return.exp()).max(MIN_PRICE);
+        self.prices.insert(company.symbol.clone(), price);
+
+        let half_spread = price.mid * self.config.spread / 2.0;
+        let volume = match self.config.volume {
+            volume if volume > 1 => self.rng.gen_range(volume / 2..=volume * 3 / 2),
+            volume => volume.max(0),
+        };
+        StockVal {
+            id: 0,
+            symbol: company.symbol.clone(),
+            isin: company.isin.clone(),
+            time_epoch,
+            ask_price: round_cents(price.mid + half_spread).max(MIN_PRICE),
+            bid_price: round_cents(price.mid - half_spread).max(MIN_PRICE),
+            volume,
+        }
+    }
+}
+
+/// Rounds a price to cents.
+fn round_cents(price: f64) -> f64 {
+    (price * 100.0).round() / 100.0
+}
+
+/// Simulates quotes for every company in ```public.companies```.
+///
+/// Companies added while the server runs are picked up on the next tick. Stocks with stored
+/// quotes continue from the mid price of their latest quote.
+pub struct SimulatedQuoteSource {
+    simulator: Simulator,
+    interval: u64,
+    sql_conn: Option<tokio_postgres::Client>,
+}
+
+impl SimulatedQuoteSource {
+    /// Creates a simulator ticking every ```interval``` seconds.
+    pub fn new(config: SimulatorConfig, interval: u64) -> SimulatedQuoteSource {
+        SimulatedQuoteSource {
+            simulator: Simulator::new(config),
+            interval,
+            sql_conn: None,
+        }
+    }
+
+    async fn simulate(&mut self) -> Result<MarketData, ReturnFlags> {
+        if self.sql_conn.is_none() {
+            /* connect to SQL database using user ```accounts_schema_user``` */
+            let sql_conn = db_connect(&server_config().db_accounts)
+                .await
+                .map_err(|_| ReturnFlags::ServerDbConnectFailed)?;
+            self.sql_conn = Some(sql_conn);
+        }
+        let sql_conn = self.sql_conn.as_ref().unwrap();
+
+        let companies = get_companies_from_db(sql_conn).await?;
+        let time_epoch = market_now();
+
+        let mut quotes = Vec::new();
+        for company in companies {
+            if !self.simulator.is_simulated(&company.symbol) {
+                if let Ok(latest) =
+                    get_stock_from_db_latest(sql_conn, &company.symbol, time_epoch).await
+                {
+                    self.simulator
+                        .start(&company.symbol, (latest.ask_price + latest.bid_price) / 2.0);
+                }
+                info!("MARKET_SIMULATOR_STARTED: {}", company.symbol);
+            }
+            quotes.push(
+                self.simulator
+                    .tick(&company, time_epoch, self.interval as f64),
+            );
+        }
+        Ok(MarketData {
+            companies: Vec::new(),
+            quotes,
+        })
+    }
+}
+
+impl QuoteSource for SimulatedQuoteSource {
+    fn name(&self) -> String {
+        "simulator".to_string()
+    }
+
+    fn fetch(&mut self) -> BoxFuture<'_, Result<MarketData, ReturnFlags>> {
+        Box::pin(async move {
+            self.simulate().await.map_err(|err| {
+                /* reconnect on the next tick */
+                self.sql_conn = None;
+                warn!("MARKET_SOURCE_FAILED: {}: {}", self.name(), err);
+                ReturnFlags::ServerMarketSourceFailed
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn config(model: SimulationModel, seed: u64) -> SimulatorConfig {
+        SimulatorConfig {
+            model,
+            jump_intensity: 0.0,
+            jump_size: 0.0,
+            spread: 0.01,
+            volume: 1000,
+            initial_price: 100.0,
+            seed: Some(seed),
+        }
+    }
+
+    fn run(config: SimulatorConfig, ticks: i64) -> Vec<StockVal> {
+        let company = Company {
+            symbol: "AAPL".into(),
+            ..Company::default()
+        };
+        let mut simulator = Simulator::new(config);
+        (0..ticks)
+            .map(|time_epoch| simulator.tick(&company, time_epoch, 86400.0))
+            .collect()
+    }
+
+    #[test]
+    fn test_simulator() {
+        let gbm = SimulationModel::GeometricBrownian {
+            drift: 0.05,
+            volatility: 0.3,
+        };
+
+        /* seeded sessions are reproducible */
+        let quotes = run(config(gbm, 7), 100);
+        assert_eq!(quotes, run(config(gbm, 7), 100));
+        assert_ne!(quotes, run(config(gbm, 8), 100));
+
+        for quote in &quotes {
+            assert!(quote.bid_price >= MIN_PRICE);
+            assert!(quote.ask_price > quote.bid_price);
+            assert!((500..=1500).contains(&quote.volume));
+        }
+
+        /* mean reversion keeps the price close to the first price */
+        let reverting = SimulationModel::MeanReversion {
+            speed: 1000.0,
+            volatility: 0.2,
+        };
+        for quote in run(config(reverting, 7), 200) {
+            assert!(quote.bid_price > 95.0 && quote.ask_price < 105.0);
+        }
+
+        /* jumps move the price without any volatility */
+        let mut jumping = config(
+            SimulationModel::GeometricBrownian {
+                drift: 0.0,
+                volatility: 0.0,
+            },
+            7,
+        );
+        jumping.jump_intensity = 365.25;
+        jumping.jump_size = 0.5;
+        let quotes = run(jumping, 10);
+        assert!(quotes
+            .iter()
+            .any(|quote| quote.bid_price != quotes[0].bid_price));
+    }
+}
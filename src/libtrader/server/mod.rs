@@ -1,5 +1,8 @@
 pub mod account;
+pub mod config;
 pub mod db;
 pub mod ds;
+pub mod exchange;
 pub mod initializer;
+pub mod market;
 pub mod network;
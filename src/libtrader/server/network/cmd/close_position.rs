@@ -0,0 +1,54 @@
+use std::sync::RwLock;
+
+use log::warn;
+
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::error_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::close_position::acc_close_position;
+use crate::server::ds::global_state::GlobalState;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+pub async fn close_position(
+    sql_conn: &tokio_postgres::Client,
+    state: &RwLock<GlobalState>,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> std::io::Result<()> {
+    /* assert recieved message */
+    if !assert_msg(
+        message,
+        MessageType::Command,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) || message.data.is_empty()
+    {
+        warn!("CLOSE_POSITION_INVALID_MESSAGE");
+        return tls_connection
+            .send(error_builder(ReturnFlags::ServerClosePositionInvMsg))
+            .await;
+    }
+
+    /* call acc_close_position() server version */
+    match acc_close_position(sql_conn, state, tls_connection, message).await {
+        Ok(_) => Ok(()),
+        Err(err) => {
+            warn!("CLOSE_POSITION_FAILED: {}", err);
+            tls_connection.send(error_builder(err)).await
+        }
+    }
+}
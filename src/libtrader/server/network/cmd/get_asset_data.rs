@@ -1,82 +1,121 @@
-//use std::io::Write;
-//
-//use crate::common::message::message::Message;
-//use crate::common::message::message_builder::message_builder;
-//use crate::common::message::message_type::MessageType;
-//use crate::common::misc::assert_msg::assert_msg;
-//
-//use crate::server::db::cmd::get_stock::get_stock_from_db_between_epochs;
-//use crate::server::network::tls_connection::TlsConnection;
-//
-//pub fn get_asset_data(tls_connection: &mut TlsConnection, message: &Message) {
-//    /* assert recieved message */
-//    if !assert_msg(
-//        message,
-//        MessageType::DataTransfer,
-//        true,
-//        3,
-//        false,
-//        0,
-//        false,
-//        0,
-//        false,
-//        0,
-//    ) {
-//        tls_connection.closing = true;
-//        warn!("GET_ASSET_DATA_MSG_ASSERT_FAILED");
-//        return;
-//    }
-//
-//    /*
-//     * Parse arguments
-//     * */
-//    /* get json data */
-//    let stringified_data = std::str::from_utf8(&message.data).unwrap();
-//    let data = json::parse(&stringified_data).unwrap();
-//    /* get symbol, start_epoch, and end_epoch */
-//    let symbol = data["symbol"].as_str().unwrap();
-//    let start_epoch = data["start_epoch"]
-//        .as_str()
-//        .unwrap()
-//        .to_string()
-//        .parse::<i64>()
-//        .unwrap();
-//    let end_epoch = data["end_epoch"]
-//        .as_str()
-//        .unwrap()
-//        .to_string()
-//        .parse::<i64>()
-//        .unwrap();
-//
-//    /* call get_stock_from_db_between_epochs() */
-//    match get_stock_from_db_between_epochs(symbol, start_epoch, end_epoch) {
-//        Ok(vals) => {
-//            /* send the data */
-//            let mut counter = 0;
-//            for val in &vals {
-//                let message = message_builder(
-//                    MessageType::DataTransfer,
-//                    1,
-//                    counter,
-//                    vals.len(),
-//                    0,
-//                    bincode::serialize(&val).unwrap(),
-//                );
-//                let _ = tls_connection.write(&bincode::serialize(&message).unwrap());
-//                counter = counter + 1;
-//            }
-//        }
-//        Err(err) => {
-//            /* handle error */
-//            let message = message_builder(
-//                MessageType::ServerReturn,
-//                0,
-//                0,
-//                0,
-//                0,
-//                bincode::serialize(&err).unwrap(),
-//            );
-//            let _ = tls_connection.write(&bincode::serialize(&message).unwrap());
-//        }
-//    }
-//}
+use std::sync::RwLock;
+
+use log::warn;
+
+use crate::common::message::inst::DataTransferInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::{error_builder, payload_builder};
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::{
+    AssetValueCurrentRequest, AssetValueRequest, MAX_ASSET_VALUE_ROWS,
+};
+use crate::common::message::response::{AssetValueCurrentResponse, AssetValueResponse};
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::db::cmd::get_stock::get_stock_from_db_between_epochs;
+use crate::server::ds::global_state::GlobalState;
+use crate::server::market::clock::market_now;
+use crate::server::market::quote_cache::latest_stock_val;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+pub async fn get_asset_data(
+    sql_conn: &tokio_postgres::Client,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> std::io::Result<()> {
+    /* assert recieved message */
+    if !assert_msg(
+        message,
+        MessageType::DataTransfer,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) {
+        warn!("GET_ASSET_DATA_MSG_ASSERT_FAILED");
+        return tls_connection
+            .send(error_builder(ReturnFlags::ServerGetAssetDataInvMsg))
+            .await;
+    }
+
+    /* call get_stock_from_db_between_epochs(), quotes after the market time are not out yet */
+    let vals = match message.payload::<AssetValueRequest>() {
+        Ok(request) if request.start_epoch <= request.end_epoch => {
+            get_stock_from_db_between_epochs(
+                sql_conn,
+                &request.symbol.to_uppercase(),
+                request.start_epoch,
+                request.end_epoch.min(market_now()),
+                Some(MAX_ASSET_VALUE_ROWS),
+            )
+            .await
+        }
+        _ => Err(ReturnFlags::ServerGetAssetDataInvMsg),
+    };
+    let message = match vals {
+        Ok(vals) => payload_builder(
+            MessageType::ServerReturn,
+            DataTransferInst::GetAssetValue as i64,
+            &AssetValueResponse { vals },
+        ),
+        Err(err) => {
+            warn!("GET_ASSET_DATA_FAILED: {}", err);
+            error_builder(err)
+        }
+    };
+    tls_connection.send(message).await
+}
+
+pub async fn get_asset_data_current(
+    sql_conn: &tokio_postgres::Client,
+    state: &RwLock<GlobalState>,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> std::io::Result<()> {
+    /* assert recieved message */
+    if !assert_msg(
+        message,
+        MessageType::DataTransfer,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) {
+        warn!("GET_ASSET_DATA_CURRENT_MSG_ASSERT_FAILED");
+        return tls_connection
+            .send(error_builder(ReturnFlags::ServerGetAssetDataInvMsg))
+            .await;
+    }
+
+    /* call latest_stock_val() */
+    let val = match message.payload::<AssetValueCurrentRequest>() {
+        Ok(request) => latest_stock_val(sql_conn, state, &request.symbol.to_uppercase()).await,
+        Err(_) => Err(ReturnFlags::ServerGetAssetDataInvMsg),
+    };
+    let message = match val {
+        Ok(val) => payload_builder(
+            MessageType::ServerReturn,
+            DataTransferInst::GetAssetValueCurrent as i64,
+            &AssetValueCurrentResponse { val },
+        ),
+        Err(err) => {
+            warn!("GET_ASSET_DATA_CURRENT_FAILED: {}", err);
+            error_builder(err)
+        }
+    };
+    tls_connection.send(message).await
+}
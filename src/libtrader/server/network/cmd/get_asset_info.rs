@@ -1,56 +1,61 @@
-//use std::io::Write;
-//
-//use crate::common::message::inst::DataTransferInst;
-//use crate::common::message::message::Message;
-//use crate::common::message::message_builder::message_builder;
-//use crate::common::message::message_type::MessageType;
-//use crate::common::misc::assert_msg::assert_msg;
-//
-//use crate::server::db::cmd::get_company::get_company_from_db;
-//use crate::server::network::tls_connection::TlsConnection;
-//
-//pub fn get_asset_info(tls_connection: &mut TlsConnection, message: &Message) {
-//    /* assert recieved message */
-//    if !assert_msg(
-//        message,
-//        MessageType::DataTransfer,
-//        true,
-//        1,
-//        false,
-//        0,
-//        false,
-//        0,
-//        false,
-//        0,
-//    ) {
-//        tls_connection.closing = true;
-//        warn!("GET_ASSET_INFO_INVALID_MESSAGE");
-//        return;
-//    }
-//
-//    /* call get_company_from_db() */
-//    match get_company_from_db(bincode::deserialize(&message.data).unwrap()) {
-//        Ok(company) => {
-//            let message = message_builder(
-//                MessageType::ServerReturn,
-//                DataTransferInst::GetAssetInfo as i64,
-//                0,
-//                0,
-//                1,
-//                bincode::serialize(&company).unwrap(),
-//            );
-//            let _ = tls_connection.write(&bincode::serialize(&message).unwrap());
-//        }
-//        Err(err) => {
-//            let message = message_builder(
-//                MessageType::ServerReturn,
-//                0,
-//                0,
-//                0,
-//                0,
-//                bincode::serialize(&err).unwrap(),
-//            );
-//            let _ = tls_connection.write(&bincode::serialize(&message).unwrap());
-//        }
-//    }
-//}
+use log::warn;
+
+use crate::common::message::inst::DataTransferInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::{error_builder, payload_builder};
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::AssetInfoRequest;
+use crate::common::message::response::AssetInfoResponse;
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::db::cmd::get_company::get_company_from_db;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+pub async fn get_asset_info(
+    sql_conn: &tokio_postgres::Client,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> std::io::Result<()> {
+    /* assert recieved message */
+    if !assert_msg(
+        message,
+        MessageType::DataTransfer,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) {
+        warn!("GET_ASSET_INFO_INVALID_MESSAGE");
+        return tls_connection
+            .send(error_builder(ReturnFlags::ServerGetAssetInfoInvMsg))
+            .await;
+    }
+
+    /* call get_company_from_db() */
+    let company = match message.payload::<AssetInfoRequest>() {
+        Ok(request) => get_company_from_db(sql_conn, &request.symbol).await,
+        Err(_) => Err(ReturnFlags::ServerGetAssetInfoInvMsg),
+    };
+    let message = match company {
+        Ok(company) => payload_builder(
+            MessageType::ServerReturn,
+            DataTransferInst::GetAssetInfo as i64,
+            &AssetInfoResponse { company },
+        ),
+        Err(err) => {
+            warn!("GET_ASSET_INFO_FAILED: {}", err);
+            error_builder(err)
+        }
+    };
+    tls_connection.send(message).await
+}
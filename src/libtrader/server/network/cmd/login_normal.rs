@@ -1,20 +1,22 @@
 use log::warn;
 
-use crate::common::message::inst::CommandInst;
 use crate::common::message::message::Message;
-use crate::common::message::message_builder::message_builder;
+use crate::common::message::message_builder::error_builder;
+use crate::common::message::message_codec::MessageCodec;
 use crate::common::message::message_type::MessageType;
 use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
 
 use crate::server::account::authorization::acc_auth;
 
-use tokio::io::AsyncWriteExt;
+use futures::SinkExt;
 use tokio::net::TcpStream;
 use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
 
 pub async fn login_normal(
     sql_conn: &tokio_postgres::Client,
-    tls_connection: &mut TlsStream<TcpStream>,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
     message: &Message,
 ) -> std::io::Result<()> {
     /* assert recieved message */
@@ -22,35 +24,24 @@ pub async fn login_normal(
         message,
         MessageType::Command,
         true,
-        3,
+        1,
         false,
         0,
         false,
         0,
         false,
         0,
-    ) && message.instruction == CommandInst::LoginMethod1 as i64
-        && message.data.len() != 0
+    ) || message.data.is_empty()
     {
         warn!("LOGIN_INVALID_MESSAGE");
-        return tls_connection.shutdown().await;
+        return tls_connection
+            .send(error_builder(ReturnFlags::ServerLoginInvMsg))
+            .await;
     }
 
     /* call acc_auth() server version */
     match acc_auth(sql_conn, tls_connection, message).await {
         Ok(_) => Ok(()),
-        Err(err) => {
-            let server_response = message_builder(
-                MessageType::ServerReturn,
-                0,
-                0,
-                0,
-                0,
-                bincode::serialize(&err).unwrap(),
-            );
-            tls_connection
-                .write_all(&bincode::serialize(&server_response).unwrap())
-                .await
-        }
+        Err(err) => tls_connection.send(error_builder(err)).await,
     }
 }
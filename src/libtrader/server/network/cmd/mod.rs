@@ -1,7 +1,19 @@
+pub mod cancel_order;
+pub mod change_email;
+pub mod change_password;
+pub mod close_position;
+pub mod delete_account;
 pub mod get_asset_data;
 pub mod get_asset_info;
 pub mod login_normal;
+pub mod logout;
+pub mod place_order;
 pub mod purchase_asset;
+pub mod refresh_token;
 pub mod register;
+pub mod retrieve_ledger;
+pub mod retrieve_orders;
 pub mod retrieve_portfolio;
 pub mod retrieve_transactions;
+pub mod retrieve_valuation;
+pub mod subscribe_quotes;
@@ -0,0 +1,50 @@
+use log::warn;
+
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::error_builder;
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::account::place_order::acc_place_order;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+pub async fn place_order(
+    sql_conn: &tokio_postgres::Client,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> std::io::Result<()> {
+    /* assert recieved message */
+    if !assert_msg(
+        message,
+        MessageType::Command,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) || message.data.is_empty()
+    {
+        warn!("PLACE_ORDER_INVALID_MESSAGE");
+        return tls_connection
+            .send(error_builder(ReturnFlags::ServerPlaceOrderInvMsg))
+            .await;
+    }
+
+    /* call acc_place_order() server version */
+    match acc_place_order(sql_conn, tls_connection, message).await {
+        Ok(_) => Ok(()),
+        Err(err) => {
+            warn!("PLACE_ORDER_FAILED: {}", err);
+            tls_connection.send(error_builder(err)).await
+        }
+    }
+}
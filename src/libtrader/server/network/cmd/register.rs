@@ -1,20 +1,22 @@
 use log::warn;
 
-use crate::common::message::inst::CommandInst;
 use crate::common::message::message::Message;
-use crate::common::message::message_builder::message_builder;
+use crate::common::message::message_builder::{error_builder, message_builder};
+use crate::common::message::message_codec::MessageCodec;
 use crate::common::message::message_type::MessageType;
 use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
 
 use crate::server::account::creation::acc_create;
 
-use tokio::io::AsyncWriteExt;
+use futures::SinkExt;
 use tokio::net::TcpStream;
 use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
 
 pub async fn register(
     sql_conn: &tokio_postgres::Client,
-    tls_connection: &mut TlsStream<TcpStream>,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
     message: &Message,
 ) -> std::io::Result<()> {
     /* assert recieved message */
@@ -22,18 +24,19 @@ pub async fn register(
         message,
         MessageType::Command,
         true,
-        5,
+        1,
         false,
         0,
         false,
         0,
         false,
         0,
-    ) && message.instruction == CommandInst::Register as i64
-        && message.data.len() != 0
+    ) || message.data.is_empty()
     {
         warn!("REGISTER_INVALID_MESSAGE");
-        return tls_connection.shutdown().await;
+        return tls_connection
+            .send(error_builder(ReturnFlags::ServerRegisterInvMsg))
+            .await;
     }
 
     /* call acc_create() server version */
@@ -41,13 +44,11 @@ pub async fn register(
         Ok(_) => {
             let server_response =
                 message_builder(MessageType::ServerReturn, 1, 0, 0, 0, Vec::new());
-            tls_connection
-                .write_all(&bincode::serialize(&server_response).unwrap())
-                .await
+            tls_connection.send(server_response).await
         }
         Err(err) => {
             warn!("REGISTER_FAILED: {}", err);
-            Ok(())
+            tls_connection.send(error_builder(err)).await
         }
     }
 }
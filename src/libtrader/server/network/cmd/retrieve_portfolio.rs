@@ -1,20 +1,23 @@
 use log::warn;
-use std::io;
 
 use crate::common::message::inst::DataTransferInst;
 use crate::common::message::message::Message;
+use crate::common::message::message_builder::error_builder;
+use crate::common::message::message_codec::MessageCodec;
 use crate::common::message::message_type::MessageType;
 use crate::common::misc::assert_msg::assert_msg;
 use crate::common::misc::return_flags::ReturnFlags;
 
 use crate::server::account::retrieval_portfolio::acc_retrieve_portfolio;
 
-use tokio::io::AsyncWriteExt;
+use futures::SinkExt;
 use tokio::net::TcpStream;
 use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
 
 pub async fn retrieve_portfolio(
-    tls_connection: &mut TlsStream<TcpStream>,
+    sql_conn: &tokio_postgres::Client,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
     message: &Message,
 ) -> std::io::Result<()> {
     /* assert recieved message */
@@ -33,18 +36,17 @@ pub async fn retrieve_portfolio(
         && message.data.len() != 0
     {
         warn!("RETRIEVE_PORTFOLIO_INVALID_MESSAGE");
-        return tls_connection.shutdown().await;
+        return tls_connection
+            .send(error_builder(ReturnFlags::ServerRetrievePortfolioInvMsg))
+            .await;
     }
 
     /* call acc_retrieve_portfolio() server version */
-    match acc_retrieve_portfolio(tls_connection, message).await {
+    match acc_retrieve_portfolio(sql_conn, tls_connection, message).await {
         Ok(_) => Ok(()),
         Err(err) => {
             warn!("RETRIEVE_PORTFOLIO_FAILED: {}", err);
-            Err(io::Error::new(
-                io::ErrorKind::Other,
-                format!("{}: {}", ReturnFlags::ServerRetrievePortfolioFailed, err),
-            ))
+            tls_connection.send(error_builder(err)).await
         }
     }
 }
@@ -1,21 +1,23 @@
 use log::warn;
-use std::io;
 
 use crate::common::message::inst::DataTransferInst;
 use crate::common::message::message::Message;
+use crate::common::message::message_builder::error_builder;
+use crate::common::message::message_codec::MessageCodec;
 use crate::common::message::message_type::MessageType;
 use crate::common::misc::assert_msg::assert_msg;
 use crate::common::misc::return_flags::ReturnFlags;
 
 use crate::server::account::retrieval_transaction::acc_retrieve_transaction;
 
-use tokio::io::AsyncWriteExt;
+use futures::SinkExt;
 use tokio::net::TcpStream;
 use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
 
 pub async fn retrieve_transactions(
     sql_conn: &tokio_postgres::Client,
-    tls_connection: &mut TlsStream<TcpStream>,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
     message: &Message,
 ) -> std::io::Result<()> {
     /* assert recieved message */
@@ -34,7 +36,9 @@ pub async fn retrieve_transactions(
         && message.data.len() != 0
     {
         warn!("RETRIEVE_TRANSACTION_INVALID_MESSAGE");
-        return tls_connection.shutdown().await;
+        return tls_connection
+            .send(error_builder(ReturnFlags::ServerRetrieveTransactionInvMsg))
+            .await;
     }
 
     /* call acc_retrieve_transaction() server version */
@@ -42,10 +46,7 @@ pub async fn retrieve_transactions(
         Ok(_) => Ok(()),
         Err(err) => {
             warn!("RETRIEVE_TRANSACTION_FAILED: {}", err);
-            Err(io::Error::new(
-                io::ErrorKind::Other,
-                format!("{}: {}", ReturnFlags::ServerRetrieveTransactionFailed, err),
-            ))
+            tls_connection.send(error_builder(err)).await
         }
     }
 }
@@ -0,0 +1,84 @@
+use std::sync::RwLock;
+
+use log::warn;
+
+use crate::common::message::inst::DataTransferInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::{error_builder, payload_builder};
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::QuoteSubscriptionRequest;
+use crate::common::message::response::QuoteSubscriptionResponse;
+use crate::common::misc::assert_msg::assert_msg;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::ds::global_state::GlobalState;
+use crate::server::network::quote_subscription::QuoteSubscription;
+
+use futures::SinkExt;
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Changes the quote subscription of the connection.
+///
+/// ```SubscribeQuotes``` adds the requested symbols, ```UnsubscribeQuotes``` removes them. The
+/// connection is answered with every symbol it is subscribed to after the change.
+///
+/// Arguments:
+/// state - The global state holding the quote stream.
+/// subscription - The quote subscription of the connection.
+/// tls_connection - The TLS connection to respond on.
+/// message - The subscription message, containing the symbols.
+///
+/// Returns: nothing on success, the I/O error of the connection on error.
+pub async fn subscribe_quotes(
+    state: &RwLock<GlobalState>,
+    subscription: &mut QuoteSubscription,
+    tls_connection: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    message: &Message,
+) -> std::io::Result<()> {
+    /* assert recieved message */
+    if !assert_msg(
+        message,
+        MessageType::DataTransfer,
+        true,
+        1,
+        false,
+        0,
+        false,
+        0,
+        false,
+        0,
+    ) {
+        warn!("SUBSCRIBE_QUOTES_MSG_ASSERT_FAILED");
+        return tls_connection
+            .send(error_builder(ReturnFlags::ServerSubscribeQuotesInvMsg))
+            .await;
+    }
+
+    let result = match message.payload::<QuoteSubscriptionRequest>() {
+        Ok(request) if message.instruction == DataTransferInst::SubscribeQuotes as i64 => {
+            subscription.subscribe(&state.read().unwrap().quote_stream, &request.symbols)
+        }
+        Ok(request) => {
+            subscription.unsubscribe(&request.symbols);
+            Ok(())
+        }
+        Err(_) => Err(ReturnFlags::ServerSubscribeQuotesInvMsg),
+    };
+    let message = match result {
+        Ok(()) => payload_builder(
+            MessageType::ServerReturn,
+            message.instruction,
+            &QuoteSubscriptionResponse {
+                symbols: subscription.symbols(),
+            },
+        ),
+        Err(err) => {
+            warn!("SUBSCRIBE_QUOTES_FAILED: {}", err);
+            error_builder(err)
+        }
+    };
+    tls_connection.send(message).await
+}
@@ -1,35 +1,67 @@
-use data_encoding::HEXUPPER;
+use std::sync::RwLock;
 
+use log::warn;
+
+use crate::common::message::handshake::required_capability;
 use crate::common::message::inst::{CommandInst, DataTransferInst};
 use crate::common::message::message::Message;
-use crate::common::message::message_builder::message_builder;
+use crate::common::message::message_builder::{error_builder, payload_builder};
+use crate::common::message::message_codec::MessageCodec;
 use crate::common::message::message_type::MessageType;
+use crate::common::message::request::SaltRequest;
+use crate::common::message::response::SaltResponse;
+use crate::common::misc::return_flags::ReturnFlags;
 
+use crate::server::account::salt::acc_client_salt;
+use crate::server::ds::global_state::GlobalState;
+use crate::server::network::cmd::cancel_order::cancel_order;
+use crate::server::network::cmd::change_email::change_email;
+use crate::server::network::cmd::change_password::change_password;
+use crate::server::network::cmd::close_position::close_position;
+use crate::server::network::cmd::delete_account::delete_account;
+use crate::server::network::cmd::get_asset_data::{get_asset_data, get_asset_data_current};
+use crate::server::network::cmd::get_asset_info::get_asset_info;
 use crate::server::network::cmd::login_normal::login_normal;
+use crate::server::network::cmd::logout::logout;
+use crate::server::network::cmd::place_order::place_order;
+use crate::server::network::cmd::purchase_asset::purchase_asset;
+use crate::server::network::cmd::refresh_token::refresh_token;
 use crate::server::network::cmd::register::register;
+use crate::server::network::cmd::retrieve_ledger::retrieve_ledger;
+use crate::server::network::cmd::retrieve_orders::retrieve_orders;
 use crate::server::network::cmd::retrieve_portfolio::retrieve_portfolio;
 use crate::server::network::cmd::retrieve_transactions::retrieve_transactions;
+use crate::server::network::cmd::retrieve_valuation::retrieve_valuation;
+use crate::server::network::cmd::subscribe_quotes::subscribe_quotes;
+use crate::server::network::quote_subscription::QuoteSubscription;
 
 //use tokio::net::TcpStream;
-use tokio::io::AsyncWriteExt;
+use futures::SinkExt;
 use tokio::net::TcpStream;
 use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
 
 pub async fn handle_data(
     sql_conn: &tokio_postgres::Client,
-    socket: &mut TlsStream<TcpStream>,
-    buf: &[u8],
+    state: &RwLock<GlobalState>,
+    subscription: &mut QuoteSubscription,
+    capabilities: &[String],
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+    client_msg: Message,
 ) -> std::io::Result<()> {
-    /* decode incoming message */
-    let client_msg: Message = bincode::deserialize(&buf).map_err(|err| {
-        std::io::Error::new(
-            std::io::ErrorKind::InvalidInput,
-            format!("HANDLE_DATA_RCVD_INVALID_MSG: {}", err),
-        )
-    })?;
-    //println!("This is a message: {}", client_msg);
+    /* refuse the features the connection did not negotiate */
+    if let Some(capability) = required_capability(client_msg.instruction) {
+        if !capabilities.iter().any(|cap| cap == capability) {
+            warn!("HANDLE_DATA_CAPABILITY_NOT_NEGOTIATED: {}", capability);
+            return socket
+                .send(error_builder(ReturnFlags::CommonCapabilityNotNegotiated))
+                .await;
+        }
+    }
 
-    /* handle individual client instructions */
+    /*
+     * handle individual client instructions
+     * */
     match client_msg.instruction {
         _ if client_msg.instruction == CommandInst::GenHashSalt as i64 => {
             use ring::rand::SecureRandom;
@@ -38,82 +70,32 @@ pub async fn handle_data(
             let mut salt = [0u8; digest::SHA512_OUTPUT_LEN / 2];
             rng.fill(&mut salt).unwrap();
 
-            let server_response: Message = message_builder(
+            let server_response = payload_builder(
                 MessageType::DataTransfer,
                 CommandInst::GenHashSalt as i64,
-                1,
-                0,
-                1,
-                salt.to_vec(),
+                &SaltResponse {
+                    salt: salt.to_vec(),
+                },
             );
-            socket
-                .write_all(bincode::serialize(&server_response).unwrap().as_slice())
-                .await
+            socket.send(server_response).await
         }
-        _ if client_msg.instruction == CommandInst::GetEmailSalt as i64 => {
-            use crate::server::db::cmd::get_user_salt::get_user_salt;
-            match get_user_salt(
-                sql_conn,
-                String::from_utf8(client_msg.data).unwrap().as_str(),
-                true,
-                false,
-            )
-            .await
-            {
-                Ok(salt) => {
-                    let server_response: Message = message_builder(
-                        MessageType::DataTransfer,
-                        CommandInst::GetEmailSalt as i64,
-                        1,
-                        0,
-                        1,
-                        HEXUPPER.decode(salt.as_bytes()).unwrap(),
-                    );
-                    socket
-                        .write_all(bincode::serialize(&server_response).unwrap().as_slice())
-                        .await
-                }
-                Err(_) => {
-                    let server_response =
-                        message_builder(MessageType::ServerReturn, 0, 0, 0, 0, Vec::new());
-                    socket
-                        .write_all(bincode::serialize(&server_response).unwrap().as_slice())
-                        .await
-                }
-            }
-        }
-        _ if client_msg.instruction == CommandInst::GetPasswordSalt as i64 => {
-            use crate::server::db::cmd::get_user_salt::get_user_salt;
-            match get_user_salt(
-                sql_conn,
-                String::from_utf8(client_msg.data).unwrap().as_str(),
-                false,
-                false,
-            )
-            .await
-            {
-                Ok(salt) => {
-                    let server_response: Message = message_builder(
-                        MessageType::DataTransfer,
-                        CommandInst::GetPasswordSalt as i64,
-                        1,
-                        0,
-                        1,
-                        HEXUPPER.decode(salt.as_bytes()).unwrap(),
-                    );
-                    socket
-                        .write_all(bincode::serialize(&server_response).unwrap().as_slice())
-                        .await
-                }
-                Err(_) => {
-                    let server_response =
-                        message_builder(MessageType::ServerReturn, 0, 0, 0, 0, Vec::new());
-
-                    socket
-                        .write_all(bincode::serialize(&server_response).unwrap().as_slice())
-                        .await
-                }
-            }
+        _ if client_msg.instruction == CommandInst::GetEmailSalt as i64
+            || client_msg.instruction == CommandInst::GetPasswordSalt as i64 =>
+        {
+            let is_email = client_msg.instruction == CommandInst::GetEmailSalt as i64;
+            let salt = match client_msg.payload::<SaltRequest>() {
+                Ok(request) => acc_client_salt(sql_conn, &request.username, is_email).await,
+                Err(_) => Err(ReturnFlags::ServerHandleDataRcvdInvMsg),
+            };
+            let server_response = match salt {
+                Ok(salt) => payload_builder(
+                    MessageType::DataTransfer,
+                    client_msg.instruction,
+                    &SaltResponse { salt },
+                ),
+                Err(err) => error_builder(err),
+            };
+            socket.send(server_response).await
         }
         _ if client_msg.instruction == CommandInst::Register as i64 => {
             register(sql_conn, socket, &client_msg).await
@@ -121,12 +103,74 @@ pub async fn handle_data(
         _ if client_msg.instruction == CommandInst::LoginMethod1 as i64 => {
             login_normal(sql_conn, socket, &client_msg).await
         }
+        _ if client_msg.instruction == CommandInst::Logout as i64
+            || client_msg.instruction == CommandInst::LogoutAll as i64 =>
+        {
+            logout(sql_conn, socket, &client_msg).await
+        }
+        _ if client_msg.instruction == CommandInst::RefreshToken as i64 => {
+            refresh_token(sql_conn, socket, &client_msg).await
+        }
+        _ if client_msg.instruction == CommandInst::ChangePassword as i64 => {
+            change_password(sql_conn, socket, &client_msg).await
+        }
+        _ if client_msg.instruction == CommandInst::ChangeEmail as i64 => {
+            change_email(sql_conn, socket, &client_msg).await
+        }
+        _ if client_msg.instruction == CommandInst::DeleteAccount as i64 => {
+            delete_account(sql_conn, socket, &client_msg).await
+        }
+        _ if client_msg.instruction == CommandInst::PurchaseAsset as i64
+            || client_msg.instruction == CommandInst::SellAsset as i64 =>
+        {
+            purchase_asset(sql_conn, state, socket, &client_msg).await
+        }
+        _ if client_msg.instruction == CommandInst::PlaceOrder as i64 => {
+            place_order(sql_conn, socket, &client_msg).await
+        }
+        _ if client_msg.instruction == CommandInst::CancelOrder as i64 => {
+            cancel_order(sql_conn, socket, &client_msg).await
+        }
+        _ if client_msg.instruction == CommandInst::ClosePosition as i64 => {
+            close_position(sql_conn, state, socket, &client_msg).await
+        }
         _ if client_msg.instruction == DataTransferInst::GetUserPortfolio as i64 => {
-            retrieve_portfolio(socket, &client_msg).await
+            retrieve_portfolio(sql_conn, socket, &client_msg).await
         }
         _ if client_msg.instruction == DataTransferInst::GetUserTransactionHist as i64 => {
             retrieve_transactions(sql_conn, socket, &client_msg).await
         }
-        _ => Ok(()),
+        _ if client_msg.instruction == DataTransferInst::GetUserLedger as i64 => {
+            retrieve_ledger(sql_conn, socket, &client_msg).await
+        }
+        _ if client_msg.instruction == DataTransferInst::GetUserOrders as i64 => {
+            retrieve_orders(sql_conn, socket, &client_msg).await
+        }
+        _ if client_msg.instruction == DataTransferInst::GetUserValuation as i64 => {
+            retrieve_valuation(sql_conn, socket, &client_msg).await
+        }
+        _ if client_msg.instruction == DataTransferInst::GetAssetInfo as i64 => {
+            get_asset_info(sql_conn, socket, &client_msg).await
+        }
+        _ if client_msg.instruction == DataTransferInst::GetAssetValue as i64 => {
+            get_asset_data(sql_conn, socket, &client_msg).await
+        }
+        _ if client_msg.instruction == DataTransferInst::GetAssetValueCurrent as i64 => {
+            get_asset_data_current(sql_conn, state, socket, &client_msg).await
+        }
+        _ if client_msg.instruction == DataTransferInst::SubscribeQuotes as i64
+            || client_msg.instruction == DataTransferInst::UnsubscribeQuotes as i64 =>
+        {
+            subscribe_quotes(state, subscription, socket, &client_msg).await
+        }
+        _ => {
+            warn!(
+                "HANDLE_DATA_UNKNOWN_INSTRUCTION: {}",
+                client_msg.instruction
+            );
+            socket
+                .send(error_builder(ReturnFlags::ServerHandleDataRcvdInvMsg))
+                .await
+        }
     }
 }
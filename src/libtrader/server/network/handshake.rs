@@ -0,0 +1,90 @@
+use log::{info, warn};
+
+use crate::common::message::handshake::{negotiate_capabilities, negotiate_version};
+use crate::common::message::inst::CommandInst;
+use crate::common::message::message_builder::{error_builder, payload_builder};
+use crate::common::message::message_codec::MessageCodec;
+use crate::common::message::message_type::MessageType;
+use crate::common::message::request::HelloRequest;
+use crate::common::message::response::HelloResponse;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use futures::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio_rustls::server::TlsStream;
+use tokio_util::codec::Framed;
+
+/// Performs the server side of the connection handshake.
+///
+/// Reads the first message of a connection, which must be a ```Hello```, and answers with the
+/// negotiated protocol version and capabilities. Clients that do not open with a ```Hello```, or
+/// that share no protocol version with the server, are answered with
+/// ```ServerProtocolUpgradeRequired```.
+///
+/// Arguments:
+/// socket - The TLS stream of the new connection.
+///
+/// Returns: the negotiated hello on success, nothing if the connection should be closed.
+///
+/// Example:
+/// ```rust
+///     if handshake(&mut socket).await?.is_none() {
+///         return Ok(());
+///     }
+/// ```
+pub async fn handshake(
+    socket: &mut Framed<TlsStream<TcpStream>, MessageCodec>,
+) -> std::io::Result<Option<HelloResponse>> {
+    let message = match socket.next().await {
+        Some(message) => message?,
+        None => return Ok(None),
+    };
+
+    /* clients predating the handshake open with any other instruction */
+    if message.msgtype != MessageType::Command || message.instruction != CommandInst::Hello as i64 {
+        warn!("HANDSHAKE_NO_HELLO: {}", message.instruction);
+        socket
+            .send(error_builder(ReturnFlags::ServerProtocolUpgradeRequired))
+            .await?;
+        return Ok(None);
+    }
+
+    let request: HelloRequest = match message.payload() {
+        Ok(request) => request,
+        Err(_) => {
+            warn!("HANDSHAKE_INVALID_MESSAGE");
+            socket
+                .send(error_builder(ReturnFlags::ServerHandshakeInvMsg))
+                .await?;
+            return Ok(None);
+        }
+    };
+
+    let version = match negotiate_version(request.min_version, request.max_version) {
+        Some(version) => version,
+        None => {
+            warn!(
+                "HANDSHAKE_UNSUPPORTED_VERSION: {}-{}",
+                request.min_version, request.max_version
+            );
+            socket
+                .send(error_builder(ReturnFlags::ServerProtocolUpgradeRequired))
+                .await?;
+            return Ok(None);
+        }
+    };
+
+    let response = HelloResponse {
+        version,
+        capabilities: negotiate_capabilities(&request.capabilities),
+    };
+    info!("HANDSHAKE: version {}", version);
+    socket
+        .send(payload_builder(
+            MessageType::ServerReturn,
+            CommandInst::Hello as i64,
+            &response,
+        ))
+        .await?;
+    Ok(Some(response))
+}
@@ -1,84 +1,106 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use data_encoding::HEXLOWER;
+use ring::rand::{self, SecureRandom};
+
 use crate::common::misc::return_flags::ReturnFlags;
 use crate::common::sessions::jwt_claim::JWTClaim;
-use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use crate::server::config::server_config;
+use crate::server::network::jwt_keys::JwtKeySet;
 
-pub static JWT_SECRET: &'static str = "seecreet";
+/// Seconds a token is still accepted past its expiry, to allow for clock drift.
+const JWT_LEEWAY: u64 = 25;
 
 /// Encodes a JWT token.
 ///
-/// Takes in the authorized user id and expiry date of the authorization.
-/// Outputs a string of the token.
+/// Takes in the authorized user id and outputs a string of the token, signed with the current
+/// signing key and valid for the configured ```jwt_ttl```, along with its claim.
 ///
 /// Arguments:
 /// user_id - The DB entry id of the authorized user.
-/// exp - The unix epoch at which the token expires
 ///
-/// Returns: a string of the token on success, string on error.
+/// Returns: a string of the token and its claim on success, ReturnFlags on error.
 ///
 /// Example:
 /// ```rust
-///     let token = create_jwt_token(auth_user_id, unix_expiry_epoch).unwrap();
+///     let (token, claim) = create_jwt_token(auth_user_id).unwrap();
 /// ```
-pub fn create_jwt_token(user_id: i64, exp: u64) -> Result<String, ReturnFlags> {
-    let mut header = Header::default();
-    header.alg = Algorithm::HS512;
-
-    let claim = JWTClaim {
-        user_id: user_id,
-        exp: exp,
-    };
-    match encode(
-        &header,
-        &claim,
-        &EncodingKey::from_secret(JWT_SECRET.as_bytes()),
-    ) {
-        Ok(token) => Ok(token),
-        Err(_) => Err(ReturnFlags::ServerCreateJwtTokenFailed),
-    }
+pub fn create_jwt_token(user_id: i64) -> Result<(String, JWTClaim), ReturnFlags> {
+    let config = server_config();
+    sign_jwt_token(&config.jwt_keys, user_id, now(), config.jwt_ttl)
 }
 
 /// Decodes and verifies a JWT token.
 ///
-/// Takes in an encoded JWT token and outputs it's JWTClaim.
+/// Takes in an encoded JWT token and outputs it's JWTClaim. Tokens signed by the signing key
+/// or by a retired key are accepted.
 ///
 /// Arguments:
 /// token - the JWT token to be decoded
 ///
-/// Returns: JWTClaim on success, nothing on error.
+/// Returns: JWTClaim on success, ReturnFlags on error; ```ServerAccTokenExpired``` when the token
+/// has expired, ```ServerAccUnauthorized``` otherwise.
 ///
 /// Example:
 /// ```rust
 ///     assert_eq!(verify_jwt_token(token).unwrap(), true);
 /// ```
-pub fn verify_jwt_token(token: String) -> Result<JWTClaim, ()> {
-    let mut validation = Validation::new(Algorithm::HS512);
-    validation.leeway = 25;
-    match decode::<JWTClaim>(
-        &token,
-        &DecodingKey::from_secret(JWT_SECRET.as_bytes()),
-        &validation,
-    ) {
-        Ok(data) => Ok(data.claims),
-        Err(_) => Err(()),
+pub fn verify_jwt_token(token: String) -> Result<JWTClaim, ReturnFlags> {
+    check_jwt_token(&server_config().jwt_keys, &token, now())
+}
+
+/// Signs a token issued at ```iat``` and valid for ```ttl``` seconds.
+fn sign_jwt_token(
+    keys: &JwtKeySet,
+    user_id: i64,
+    iat: u64,
+    ttl: u64,
+) -> Result<(String, JWTClaim), ReturnFlags> {
+    let mut jti = [0u8; 16];
+    rand::SystemRandom::new()
+        .fill(&mut jti)
+        .map_err(|_| ReturnFlags::ServerCreateJwtTokenFailed)?;
+
+    let claim = JWTClaim {
+        user_id,
+        exp: iat + ttl,
+        iat,
+        jti: HEXLOWER.encode(&jti),
+    };
+    Ok((keys.sign(&claim)?, claim))
+}
+
+/// Verifies a token against the time ```now```.
+fn check_jwt_token(keys: &JwtKeySet, token: &str, now: u64) -> Result<JWTClaim, ReturnFlags> {
+    let claim: JWTClaim = keys.verify(token)?;
+    if claim.exp + JWT_LEEWAY < now {
+        return Err(ReturnFlags::ServerAccTokenExpired);
     }
+    Ok(claim)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::server::config::set_test_server_config;
 
     #[test]
     fn test_create_jwt_token() {
-        use std::time::{Duration, SystemTime, UNIX_EPOCH};
-        let start = SystemTime::now() + Duration::from_secs(4 * 60 * 60);
-        match create_jwt_token(1i64, start.duration_since(UNIX_EPOCH).unwrap().as_secs()) {
-            Ok(token) => {
+        set_test_server_config();
+        match create_jwt_token(1i64) {
+            Ok((token, claim)) => {
                 let claims = verify_jwt_token(token).unwrap();
                 assert_eq!(claims.user_id, 1i64);
-                assert_eq!(
-                    claims.exp,
-                    start.duration_since(UNIX_EPOCH).unwrap().as_secs()
-                );
+                assert_eq!(claims.exp, claims.iat + server_config().jwt_ttl);
+                assert_eq!(claims.jti.len(), 32);
+                assert_eq!(claims.jti, claim.jti);
             }
             Err(_) => panic!("TEST_CREATE_JWT_TOKEN_FAILED"),
         }
@@ -86,19 +108,29 @@ mod test {
 
     #[test]
     fn test_verify_jwt_token() {
-        use std::time::{Duration, SystemTime, UNIX_EPOCH};
-        let start = SystemTime::now() + Duration::from_secs(4 * 60 * 60);
-        let token =
-            create_jwt_token(1i64, start.duration_since(UNIX_EPOCH).unwrap().as_secs()).unwrap();
+        set_test_server_config();
+        let (token, _) = create_jwt_token(1i64).unwrap();
         match verify_jwt_token(token) {
             Ok(claims) => {
                 assert_eq!(claims.user_id, 1i64);
-                assert_eq!(
-                    claims.exp,
-                    start.duration_since(UNIX_EPOCH).unwrap().as_secs()
-                );
+                assert!(claims.iat <= now());
             }
             Err(_) => panic!("TEST_VERIFY_JWT_TOKEN_FAILED"),
         }
     }
+
+    #[test]
+    fn test_verify_jwt_token_expired() {
+        set_test_server_config();
+        let keys = &server_config().jwt_keys;
+        let (token, _) = sign_jwt_token(keys, 1i64, now() - 2 * 60 * 60, 60 * 60).unwrap();
+        assert_eq!(
+            check_jwt_token(keys, &token, now()).err(),
+            Some(ReturnFlags::ServerAccTokenExpired)
+        );
+        assert_eq!(
+            verify_jwt_token("not.a.token".into()).err(),
+            Some(ReturnFlags::ServerAccUnauthorized)
+        );
+    }
 }
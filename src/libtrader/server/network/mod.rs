@@ -1,4 +1,7 @@
 pub mod cmd;
 pub mod gen_tls_server_config;
 pub mod handle_data;
+pub mod handshake;
+pub mod jwt_keys;
 pub mod jwt_wrapper;
+pub mod quote_subscription;
@@ -0,0 +1,199 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use log::debug;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+
+use crate::common::message::inst::DataTransferInst;
+use crate::common::message::message::Message;
+use crate::common::message::message_builder::stream_builder;
+use crate::common::message::response::QuoteUpdate;
+use crate::common::misc::return_flags::ReturnFlags;
+
+use crate::server::ds::quote_stream::{QuoteStream, QuoteTick};
+
+/// The most symbols a connection can subscribe to.
+pub static MAX_QUOTE_SUBSCRIPTIONS: usize = 100;
+
+/// The quote stream subscription of a connection.
+///
+/// A subscription that falls behind the quote stream only sends the latest tick of each of its
+/// symbols, stale ticks are dropped.
+///
+/// Example:
+/// ```rust
+///     subscription.subscribe(&state.read().unwrap().quote_stream, &["AAPL".into()])?;
+///     for frame in subscription.next_frames().await {
+///         socket.send(frame).await?;
+///     }
+/// ```
+#[derive(Debug, Default)]
+pub struct QuoteSubscription {
+    symbols: BTreeSet<String>,
+    receiver: Option<broadcast::Receiver<QuoteTick>>,
+}
+
+impl QuoteSubscription {
+    /// Returns the subscribed symbols, in order.
+    pub fn symbols(&self) -> Vec<String> {
+        self.symbols.iter().cloned().collect()
+    }
+
+    /// Adds symbols to the subscription.
+    ///
+    /// Arguments:
+    /// quote_stream - The quote stream to subscribe to.
+    /// symbols - The symbols to add, in any case.
+    ///
+    /// Returns: nothing on success, ```ServerSubscribeQuotesInvMsg``` for an empty symbol, and
+    /// ```ServerSubscribeQuotesTooMany``` past ```MAX_QUOTE_SUBSCRIPTIONS``` symbols.
+    pub fn subscribe(
+        &mut self,
+        quote_stream: &QuoteStream,
+        symbols: &[String],
+    ) -> Result<(), ReturnFlags> {
+        let mut subscribed = self.symbols.clone();
+        for symbol in symbols {
+            let symbol = symbol.trim().to_uppercase();
+            if symbol.is_empty() {
+                return Err(ReturnFlags::ServerSubscribeQuotesInvMsg);
+            }
+            subscribed.insert(symbol);
+        }
+        if subscribed.len() > MAX_QUOTE_SUBSCRIPTIONS {
+            return Err(ReturnFlags::ServerSubscribeQuotesTooMany);
+        }
+
+        self.symbols = subscribed;
+        if self.receiver.is_none() && !self.symbols.is_empty() {
+            self.receiver = Some(quote_stream.subscribe());
+        }
+        Ok(())
+    }
+
+    /// Removes symbols from the subscription, leaving the quote stream without any left.
+    pub fn unsubscribe(&mut self, symbols: &[String]) {
+        for symbol in symbols {
+            self.symbols.remove(&symbol.trim().to_uppercase());
+        }
+        if self.symbols.is_empty() {
+            self.receiver = None;
+        }
+    }
+
+    /// Waits for new ticks of the subscribed symbols.
+    ///
+    /// Takes every tick waiting for the connection, keeps the latest of each subscribed symbol
+    /// and builds their ```QuoteUpdate``` stream frames. Never completes without a subscription.
+    /// Cancelling the returned future does not lose any tick.
+    ///
+    /// Returns: the stream frames, in sequence order, possibly none.
+    pub async fn next_frames(&mut self) -> Vec<Message> {
+        let receiver = match &mut self.receiver {
+            Some(receiver) => receiver,
+            None => return std::future::pending().await,
+        };
+
+        let mut ticks = Vec::new();
+        let mut lagged = 0;
+        let mut closed = false;
+        match receiver.recv().await {
+            Ok(tick) => ticks.push(tick),
+            Err(RecvError::Lagged(skipped)) => lagged += skipped,
+            Err(RecvError::Closed) => closed = true,
+        }
+        loop {
+            match receiver.try_recv() {
+                Ok(tick) => ticks.push(tick),
+                Err(TryRecvError::Lagged(skipped)) => lagged += skipped,
+                Err(_) => break,
+            }
+        }
+        if lagged > 0 {
+            debug!("QUOTE_STREAM_LAGGED: {}", lagged);
+        }
+        if closed {
+            self.receiver = None;
+        }
+
+        /* only the latest tick of each symbol is worth sending */
+        let newest = ticks.last().map_or(0, |tick| tick.sequence);
+        let mut latest: BTreeMap<&str, &QuoteTick> = BTreeMap::new();
+        for tick in &ticks {
+            if self.symbols.contains(&tick.stock_val.symbol) {
+                latest.insert(&tick.stock_val.symbol, tick);
+            }
+        }
+        let mut latest: Vec<&QuoteTick> = latest.into_values().collect();
+        latest.sort_by_key(|tick| tick.sequence);
+        latest
+            .into_iter()
+            .map(|tick| {
+                stream_builder(
+                    DataTransferInst::QuoteUpdate as i64,
+                    tick.sequence as usize,
+                    newest as usize,
+                    &QuoteUpdate {
+                        val: tick.stock_val.clone(),
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::common::generic::stock_val::StockVal;
+    use crate::server::ds::quote_stream::QUOTE_STREAM_CAPACITY;
+
+    fn quote(symbol: &str, time_epoch: i64) -> StockVal {
+        StockVal {
+            symbol: symbol.into(),
+            time_epoch,
+            ..StockVal::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quote_subscription() {
+        let quote_stream = QuoteStream::default();
+        let mut subscription = QuoteSubscription::default();
+        subscription
+            .subscribe(&quote_stream, &["aapl".into(), "MSFT".into()])
+            .unwrap();
+        assert_eq!(subscription.symbols(), vec!["AAPL", "MSFT"]);
+        assert_eq!(
+            subscription.subscribe(&quote_stream, &[" ".into()]),
+            Err(ReturnFlags::ServerSubscribeQuotesInvMsg)
+        );
+
+        /* stale ticks are dropped, unsubscribed symbols skipped */
+        quote_stream.publish(&quote("AAPL", 1));
+        quote_stream.publish(&quote("MSFT", 1));
+        quote_stream.publish(&quote("TSLA", 1));
+        quote_stream.publish(&quote("AAPL", 2));
+        let frames = subscription.next_frames().await;
+        assert_eq!(frames.len(), 2);
+        assert_eq!(
+            (frames[0].data_message_number, frames[0].data_message_max),
+            (2, 4)
+        );
+        let update: QuoteUpdate = frames[1].payload().unwrap();
+        assert_eq!(update.val, quote("AAPL", 2));
+
+        /* a subscriber that fell behind gets the newest ticks */
+        for time_epoch in 0..(QUOTE_STREAM_CAPACITY as i64 + 10) {
+            quote_stream.publish(&quote("MSFT", time_epoch));
+        }
+        let frames = subscription.next_frames().await;
+        assert_eq!(frames.len(), 1);
+        let update: QuoteUpdate = frames[0].payload().unwrap();
+        assert_eq!(update.val.time_epoch, QUOTE_STREAM_CAPACITY as i64 + 9);
+
+        subscription.unsubscribe(&["AAPL".into(), "msft".into()]);
+        assert!(subscription.symbols().is_empty());
+        assert!(subscription.receiver.is_none());
+    }
+}